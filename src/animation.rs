@@ -0,0 +1,534 @@
+use crate::{Interpolate, Matrix, Matrix3, Vector, Vector2, Vector3, Vector4};
+
+const CUBIC_RESAMPLE_STEPS: usize = 8;
+const MAX_RESAMPLE_KEYS: usize = 1 << 16;
+
+// Values that keyframe tracks can blend. Matrix3 values are rotations: they interpolate
+// along the shortest arc and stay orthonormal
+pub trait Animatable: Copy {
+    fn zero_tangent() -> Self;
+    fn interpolate(self, to: Self, t: f32) -> Self;
+    // Cubic Hermite segment, tangents are per second and get scaled by the segment duration
+    fn hermite(self, out_tangent: Self, to: Self, in_tangent: Self, duration: f32, t: f32) -> Self;
+    // Error metric used when compressing tracks
+    fn distance(self, other: Self) -> f32;
+}
+
+fn hermite<T: Interpolate>(
+    from: T,
+    out_tangent: T,
+    to: T,
+    in_tangent: T,
+    duration: f32,
+    t: f32,
+) -> T {
+    let (t2, t3) = (t * t, t * t * t);
+    from * (2. * t3 - 3. * t2 + 1.)
+        + out_tangent * ((t3 - 2. * t2 + t) * duration)
+        + to * (-2. * t3 + 3. * t2)
+        + in_tangent * ((t3 - t2) * duration)
+}
+
+impl Animatable for f32 {
+    fn zero_tangent() -> Self {
+        0.
+    }
+
+    fn interpolate(self, to: Self, t: f32) -> Self {
+        self.lerp(to, t)
+    }
+
+    fn hermite(self, out_tangent: Self, to: Self, in_tangent: Self, duration: f32, t: f32) -> Self {
+        hermite(self, out_tangent, to, in_tangent, duration, t)
+    }
+
+    fn distance(self, other: Self) -> f32 {
+        (self - other).abs()
+    }
+}
+impl Animatable for Vector2 {
+    fn zero_tangent() -> Self {
+        Self::zero()
+    }
+
+    fn interpolate(self, to: Self, t: f32) -> Self {
+        self.lerp(to, t)
+    }
+
+    fn hermite(self, out_tangent: Self, to: Self, in_tangent: Self, duration: f32, t: f32) -> Self {
+        hermite(self, out_tangent, to, in_tangent, duration, t)
+    }
+
+    fn distance(self, other: Self) -> f32 {
+        (self - other).len()
+    }
+}
+impl Animatable for Vector3 {
+    fn zero_tangent() -> Self {
+        Self::zero()
+    }
+
+    fn interpolate(self, to: Self, t: f32) -> Self {
+        self.lerp(to, t)
+    }
+
+    fn hermite(self, out_tangent: Self, to: Self, in_tangent: Self, duration: f32, t: f32) -> Self {
+        hermite(self, out_tangent, to, in_tangent, duration, t)
+    }
+
+    fn distance(self, other: Self) -> f32 {
+        (self - other).len()
+    }
+}
+impl Animatable for Vector4 {
+    fn zero_tangent() -> Self {
+        Self::zero()
+    }
+
+    fn interpolate(self, to: Self, t: f32) -> Self {
+        self.lerp(to, t)
+    }
+
+    fn hermite(self, out_tangent: Self, to: Self, in_tangent: Self, duration: f32, t: f32) -> Self {
+        hermite(self, out_tangent, to, in_tangent, duration, t)
+    }
+
+    fn distance(self, other: Self) -> f32 {
+        (self - other).len()
+    }
+}
+impl Animatable for Matrix3 {
+    fn zero_tangent() -> Self {
+        Self::zero()
+    }
+
+    // self * exp(t * log(self^T * to)), falls back to an orthonormalized lerp for half turns
+    fn interpolate(self, to: Self, t: f32) -> Self {
+        let geodesic = (self.transpose() * to)
+            .try_log()
+            .and_then(|log| (self * (log * t).exp()).try_orthonormalize());
+        geodesic
+            .or_else(|| self.lerp(to, t).try_orthonormalize())
+            .unwrap_or(if t < 0.5 { self } else { to })
+    }
+
+    // Component-wise like glTF does for quaternions, then projected back onto rotations
+    fn hermite(self, out_tangent: Self, to: Self, in_tangent: Self, duration: f32, t: f32) -> Self {
+        hermite(self, out_tangent, to, in_tangent, duration, t)
+            .try_orthonormalize()
+            .unwrap_or_else(|| self.interpolate(to, t))
+    }
+
+    // Angle of the rotation between the two
+    fn distance(self, other: Self) -> f32 {
+        let cos = ((self.transpose() * other).trace() - 1.) / 2.;
+        cos.clamp(-1., 1.).acos()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    Step,
+    Linear,
+    // Hermite spline through the keys using their in and out tangents
+    CubicSpline,
+}
+
+// How sample times outside the keyframes are mapped back onto the track
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    #[default]
+    Clamp,
+    Loop,
+    PingPong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+    pub in_tangent: T,
+    pub out_tangent: T,
+}
+impl<T: Animatable> Keyframe<T> {
+    pub fn new(time: f32, value: T) -> Self {
+        Self::with_tangents(time, value, T::zero_tangent(), T::zero_tangent())
+    }
+
+    pub fn with_tangents(time: f32, value: T, in_tangent: T, out_tangent: T) -> Self {
+        Self {
+            time,
+            value,
+            in_tangent,
+            out_tangent,
+        }
+    }
+}
+
+// Keyframes with strictly increasing times, sampled with glTF interpolation semantics
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationTrack<T> {
+    keyframes: Vec<Keyframe<T>>,
+    interpolation: Interpolation,
+    wrap_mode: WrapMode,
+}
+impl<T: Animatable> AnimationTrack<T> {
+    pub fn try_new(keyframes: Vec<Keyframe<T>>, interpolation: Interpolation) -> Option<Self> {
+        let increasing = keyframes.windows(2).all(|pair| pair[0].time < pair[1].time);
+        if keyframes.is_empty() || !increasing || keyframes.iter().any(|key| !key.time.is_finite())
+        {
+            return None;
+        }
+        Some(Self {
+            keyframes,
+            interpolation,
+            wrap_mode: WrapMode::default(),
+        })
+    }
+
+    pub fn new(keyframes: Vec<Keyframe<T>>, interpolation: Interpolation) -> Self {
+        Self::try_new(keyframes, interpolation).expect(
+            "It is impossible to build a track without keyframes at strictly increasing times",
+        )
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn wrap_mode(&self) -> WrapMode {
+        self.wrap_mode
+    }
+
+    pub fn set_wrap_mode(&mut self, wrap_mode: WrapMode) {
+        self.wrap_mode = wrap_mode;
+    }
+
+    pub fn start(&self) -> f32 {
+        self.keyframes[0].time
+    }
+
+    pub fn end(&self) -> f32 {
+        self.keyframes[self.keyframes.len() - 1].time
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.end() - self.start()
+    }
+
+    // Time mapped into [start, end] according to the wrap mode
+    pub fn wrap_time(&self, time: f32) -> f32 {
+        let (start, duration) = (self.start(), self.duration());
+        if duration <= 0. {
+            return start;
+        }
+        match self.wrap_mode {
+            WrapMode::Clamp => time.clamp(start, self.end()),
+            WrapMode::Loop => start + (time - start).rem_euclid(duration),
+            WrapMode::PingPong => {
+                let offset = (time - start).rem_euclid(2. * duration);
+                start
+                    + if offset > duration {
+                        2. * duration - offset
+                    } else {
+                        offset
+                    }
+            }
+        }
+    }
+
+    pub fn sample(&self, time: f32) -> T {
+        let time = self.wrap_time(time);
+        // Last keyframe at or before time
+        let i = self.keyframes.partition_point(|key| key.time <= time);
+        if i == 0 {
+            return self.keyframes[0].value;
+        }
+        if i == self.keyframes.len() {
+            return self.keyframes[i - 1].value;
+        }
+        let (from, to) = (&self.keyframes[i - 1], &self.keyframes[i]);
+        let duration = to.time - from.time;
+        let t = (time - from.time) / duration;
+        match self.interpolation {
+            Interpolation::Step => from.value,
+            Interpolation::Linear => from.value.interpolate(to.value, t),
+            Interpolation::CubicSpline => {
+                from.value
+                    .hermite(from.out_tangent, to.value, to.in_tangent, duration, t)
+            }
+        }
+    }
+
+    // Keys at a fixed interval covering the whole track, ends included. Cubic tracks turn into
+    // linear ones, so a fine interval keeps their shape. The interval is widened when it would
+    // need more than MAX_RESAMPLE_KEYS keys
+    pub fn try_resample(&self, interval: f32) -> Option<Self> {
+        if !interval.is_finite() || interval <= 0. {
+            return None;
+        }
+        let interpolation = match self.interpolation {
+            Interpolation::Step => Interpolation::Step,
+            _ => Interpolation::Linear,
+        };
+        let interval = interval.max(self.duration() / MAX_RESAMPLE_KEYS as f32);
+        let count = (self.duration() / interval).ceil() as usize;
+        // Rounding can put the last generated time on or past the end, which is added exactly
+        let mut times: Vec<f32> = (0..count)
+            .map(|i| self.start() + interval * i as f32)
+            .take_while(|&time| time < self.end())
+            .collect();
+        // Below one ulp of the track times neighbouring keys round to the same time
+        times.dedup();
+        times.push(self.end());
+        let keyframes = times
+            .into_iter()
+            .map(|time| Keyframe::new(time, self.sample(time)))
+            .collect();
+        Some(Self {
+            keyframes,
+            interpolation,
+            wrap_mode: self.wrap_mode,
+        })
+    }
+
+    pub fn resample(&self, interval: f32) -> Self {
+        self.try_resample(interval)
+            .expect("It is impossible to resample a track with a non-positive interval")
+    }
+
+    // Drops keyframes the remaining ones reproduce within tolerance at every original key.
+    // Cubic tracks are resampled into linear ones first
+    pub fn compress(&self, tolerance: f32) -> Self {
+        let source = match self.interpolation {
+            Interpolation::CubicSpline if self.keyframes.len() > 1 => {
+                let steps = (self.keyframes.len() - 1).max(1) * CUBIC_RESAMPLE_STEPS;
+                self.resample(self.duration() / steps as f32)
+            }
+            _ => self.clone(),
+        };
+        let keys = &source.keyframes;
+        let mut kept = vec![keys[0]];
+        let mut anchor = 0;
+        for candidate in 1..keys.len() {
+            // Can the span anchor..=candidate + 1 be replaced by its two ends?
+            let Some(next) = keys.get(candidate + 1) else {
+                break;
+            };
+            let reproduced = (anchor + 1..=candidate).all(|j| {
+                let key = &keys[j];
+                let value = match source.interpolation {
+                    Interpolation::Step => keys[anchor].value,
+                    _ => {
+                        let t = (key.time - keys[anchor].time) / (next.time - keys[anchor].time);
+                        keys[anchor].value.interpolate(next.value, t)
+                    }
+                };
+                value.distance(key.value) <= tolerance
+            });
+            if !reproduced {
+                kept.push(keys[candidate]);
+                anchor = candidate;
+            }
+        }
+        if keys.len() > 1 {
+            kept.push(keys[keys.len() - 1]);
+        }
+        Self {
+            keyframes: kept,
+            ..source
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        functions::constants::{EPSILON, PI},
+        transform::rotate3z,
+    };
+
+    const PI_HALF: f32 = PI / 2.;
+
+    fn scalar_track(interpolation: Interpolation) -> AnimationTrack<f32> {
+        let keyframes = vec![
+            Keyframe::new(0., 0.),
+            Keyframe::new(1., 2.),
+            Keyframe::new(3., 1.),
+        ];
+        AnimationTrack::new(keyframes, interpolation)
+    }
+
+    // Function try_new()
+    #[test]
+    fn test1_animationtracktry_new() {
+        assert!(AnimationTrack::<f32>::try_new(vec![], Interpolation::Linear).is_none());
+        let keyframes = vec![Keyframe::new(1., 0.), Keyframe::new(1., 2.)];
+        assert!(AnimationTrack::try_new(keyframes, Interpolation::Linear).is_none());
+        let keyframes = vec![Keyframe::new(0., 0.), Keyframe::new(f32::NAN, 2.)];
+        assert!(AnimationTrack::try_new(keyframes, Interpolation::Linear).is_none());
+        let track = AnimationTrack::new(vec![Keyframe::new(2., 5.)], Interpolation::CubicSpline);
+        assert_eq!(track.sample(-3.), 5.);
+        assert_eq!(track.duration(), 0.);
+    }
+    // Method sample(time)
+    #[test]
+    fn test1_animationtracksample() {
+        let track = scalar_track(Interpolation::Step);
+        assert_eq!(track.sample(0.99), 0.);
+        assert_eq!(track.sample(1.), 2.);
+        assert_eq!(track.sample(2.5), 2.);
+        assert_eq!(track.sample(4.), 1.);
+        let track = scalar_track(Interpolation::Linear);
+        assert_eq!(track.sample(-1.), 0.);
+        assert_eq!(track.sample(0.25), 0.5);
+        assert_eq!(track.sample(2.), 1.5);
+        assert_eq!(track.sample(3.), 1.);
+    }
+    #[test]
+    fn test2_animationtracksample() {
+        // glTF cubic spline tangents are per second, scaled by the key interval
+        let keyframes = vec![
+            Keyframe::with_tangents(
+                0.,
+                Vector2::new(0., 0.),
+                Vector2::zero(),
+                Vector2::new(1., 0.),
+            ),
+            Keyframe::with_tangents(
+                2.,
+                Vector2::new(2., 0.),
+                Vector2::new(1., 0.),
+                Vector2::zero(),
+            ),
+        ];
+        let track = AnimationTrack::new(keyframes, Interpolation::CubicSpline);
+        // Matching tangents reproduce the straight line at constant speed
+        assert_eq!(track.sample(0.5), Vector2::new(0.5, 0.));
+        assert_eq!(track.sample(1.5), Vector2::new(1.5, 0.));
+        let track = scalar_track(Interpolation::CubicSpline);
+        // Zero tangents ease in and out of every key
+        assert_eq!(track.sample(0.5), 1.);
+        assert_eq!(track.sample(1.), 2.);
+        assert!(track.sample(0.1) < 0.2);
+    }
+    #[test]
+    fn test3_animationtracksample() {
+        let keyframes = vec![
+            Keyframe::new(0., Matrix3::idenity()),
+            Keyframe::new(1., rotate3z(PI_HALF)),
+        ];
+        let track = AnimationTrack::new(keyframes, Interpolation::Linear);
+        let sample = track.sample(0.25);
+        assert_eq!(sample, rotate3z(PI_HALF / 4.));
+        assert!(sample.is_rotation());
+        let track = AnimationTrack::new(track.keyframes().to_vec(), Interpolation::CubicSpline);
+        let sample = track.sample(0.5);
+        assert_eq!(sample, rotate3z(PI_HALF / 2.));
+        assert!(sample.is_rotation());
+    }
+    // Method wrap_time(time)
+    #[test]
+    fn test1_animationtrackwrap_time() {
+        let mut track = scalar_track(Interpolation::Linear);
+        assert_eq!(track.wrap_time(4.), 3.);
+        track.set_wrap_mode(WrapMode::Loop);
+        assert_eq!(track.wrap_time(4.), 1.);
+        assert_eq!(track.wrap_time(-0.5), 2.5);
+        assert_eq!(track.sample(6.25), 0.5);
+        track.set_wrap_mode(WrapMode::PingPong);
+        assert_eq!(track.wrap_time(4.), 2.);
+        assert_eq!(track.wrap_time(6.5), 0.5);
+        assert_eq!(track.wrap_time(-1.), 1.);
+        assert_eq!(track.sample(5.75), 0.5);
+    }
+    // Method resample(interval)
+    #[test]
+    fn test1_animationtrackresample() {
+        let track = scalar_track(Interpolation::CubicSpline);
+        let resampled = track.resample(0.4);
+        assert_eq!(resampled.interpolation(), Interpolation::Linear);
+        assert_eq!(resampled.keyframes().len(), 9);
+        assert_eq!(resampled.end(), 3.);
+        for key in resampled.keyframes() {
+            assert_eq!(key.value, track.sample(key.time));
+        }
+        assert_eq!(
+            scalar_track(Interpolation::Step).resample(0.5).sample(2.9),
+            2.
+        );
+    }
+    #[test]
+    fn test2_animationtrackresample() {
+        // Rounding can land the last generated time on the end, which must not repeat
+        let keyframes = vec![Keyframe::new(0., 0.), Keyframe::new(0.275, 1.)];
+        let track = AnimationTrack::new(keyframes, Interpolation::Linear);
+        let resampled = track.resample(0.005);
+        let times: Vec<f32> = resampled.keyframes().iter().map(|key| key.time).collect();
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(times.last(), Some(&0.275));
+    }
+    #[test]
+    fn test3_animationtrackresample() {
+        // Intervals below one ulp of the key times still give strictly increasing keys
+        let keyframes = vec![Keyframe::new(1000., 0.), Keyframe::new(1001., 1.)];
+        let track = AnimationTrack::new(keyframes, Interpolation::Linear);
+        let resampled = track.resample(1e-5);
+        let times: Vec<f32> = resampled.keyframes().iter().map(|key| key.time).collect();
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!((times[0], times[times.len() - 1]), (1000., 1001.));
+        // Tiny intervals are widened instead of allocating without bound
+        let resampled = scalar_track(Interpolation::Linear).resample(1e-30);
+        assert!(resampled.keyframes().len() <= MAX_RESAMPLE_KEYS + 1);
+        assert!(track.try_resample(0.).is_none());
+        assert!(track.try_resample(-1.).is_none());
+        assert!(track.try_resample(f32::NAN).is_none());
+        assert!(track.try_resample(f32::INFINITY).is_none());
+    }
+    // Method compress(tolerance)
+    #[test]
+    fn test1_animationtrackcompress() {
+        let keyframes = (0..=20)
+            .map(|i| {
+                let time = i as f32 / 10.;
+                // Straight line up to 1, then a sharp turn
+                let value = if time <= 1. {
+                    time
+                } else {
+                    1. + 3. * (time - 1.)
+                };
+                Keyframe::new(time, Vector3::new(value, 0., 0.))
+            })
+            .collect();
+        let track = AnimationTrack::new(keyframes, Interpolation::Linear);
+        let compressed = track.compress(EPSILON);
+        let times: Vec<f32> = compressed.keyframes().iter().map(|key| key.time).collect();
+        assert_eq!(times, vec![0., 1., 2.]);
+        for i in 0..=40 {
+            let time = i as f32 / 20.;
+            assert!(compressed.sample(time).distance(track.sample(time)) <= EPSILON);
+        }
+    }
+    #[test]
+    fn test2_animationtrackcompress() {
+        let keyframes = [0., 0., 0., 1., 1., 0.]
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| Keyframe::new(i as f32, value))
+            .collect();
+        let track = AnimationTrack::new(keyframes, Interpolation::Step);
+        let compressed = track.compress(EPSILON);
+        let times: Vec<f32> = compressed.keyframes().iter().map(|key| key.time).collect();
+        assert_eq!(times, vec![0., 3., 5.]);
+        let track = scalar_track(Interpolation::CubicSpline);
+        let compressed = track.compress(0.01);
+        assert_eq!(compressed.interpolation(), Interpolation::Linear);
+        assert!(compressed.keyframes().len() < 17);
+        assert!((compressed.sample(1.5) - track.sample(1.5)).abs() < 0.05);
+    }
+}
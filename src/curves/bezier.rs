@@ -0,0 +1,426 @@
+use super::Curve;
+use crate::{solve_quadratic, Aabb3, Interpolate, Rect, Vector, Vector2, Vector3};
+use std::ops::Index;
+
+// Real roots of a * t^2 + b * t + c inside (0, 1)
+fn unit_roots(a: f32, b: f32, c: f32) -> Vec<f32> {
+    let mut roots = solve_quadratic(a, b, c);
+    roots.retain(|t| *t > 0. && *t < 1.);
+    roots
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct QuadraticBezier<V> {
+    pub p0: V,
+    pub p1: V,
+    pub p2: V,
+}
+impl<V> QuadraticBezier<V>
+where
+    V: Vector + Interpolate,
+{
+    pub fn new(p0: V, p1: V, p2: V) -> Self {
+        Self { p0, p1, p2 }
+    }
+
+    // Two curves covering [0, t] and [t, 1]
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let middle = a.lerp(b, t);
+        (Self::new(self.p0, a, middle), Self::new(middle, b, self.p2))
+    }
+
+    // The same curve as a cubic
+    pub fn elevate(&self) -> CubicBezier<V> {
+        CubicBezier::new(
+            self.p0,
+            self.p0.lerp(self.p1, 2. / 3.),
+            self.p2.lerp(self.p1, 2. / 3.),
+            self.p2,
+        )
+    }
+}
+impl<V> QuadraticBezier<V>
+where
+    V: Vector + Interpolate + Index<usize, Output = f32>,
+{
+    // Parameters in (0, 1) where some coordinate has a local extremum
+    pub fn extrema(&self) -> Vec<f32> {
+        let (d0, d1) = (self.p1 - self.p0, self.p2 - self.p1);
+        (0..self.p0.dim())
+            .flat_map(|axis| unit_roots(0., d1[axis] - d0[axis], d0[axis]))
+            .collect()
+    }
+}
+impl QuadraticBezier<Vector2> {
+    pub fn bounds(&self) -> Rect {
+        let mut rect = Rect::new(self.p0.min(self.p2), self.p0.max(self.p2));
+        for t in self.extrema() {
+            rect = rect.expand_to(self.point(t));
+        }
+        rect
+    }
+}
+impl QuadraticBezier<Vector3> {
+    pub fn bounds(&self) -> Aabb3 {
+        let mut aabb = Aabb3::new(self.p0.min(self.p2), self.p0.max(self.p2));
+        for t in self.extrema() {
+            aabb = aabb.expand_to(self.point(t));
+        }
+        aabb
+    }
+}
+impl<V> Curve for QuadraticBezier<V>
+where
+    V: Vector + Interpolate,
+{
+    type Point = V;
+
+    fn point(&self, t: f32) -> V {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        a.lerp(b, t)
+    }
+
+    fn derivative(&self, t: f32) -> V {
+        (self.p1 - self.p0).lerp(self.p2 - self.p1, t) * 2.
+    }
+
+    fn second_derivative(&self, _t: f32) -> V {
+        (self.p2 - self.p1 - (self.p1 - self.p0)) * 2.
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier<V> {
+    pub p0: V,
+    pub p1: V,
+    pub p2: V,
+    pub p3: V,
+}
+impl<V> CubicBezier<V>
+where
+    V: Vector + Interpolate,
+{
+    pub fn new(p0: V, p1: V, p2: V, p3: V) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    // Two curves covering [0, t] and [t, 1]
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let c = self.p2.lerp(self.p3, t);
+        let ab = a.lerp(b, t);
+        let bc = b.lerp(c, t);
+        let middle = ab.lerp(bc, t);
+        (
+            Self::new(self.p0, a, ab, middle),
+            Self::new(middle, bc, c, self.p3),
+        )
+    }
+}
+impl<V> CubicBezier<V>
+where
+    V: Vector + Interpolate + Index<usize, Output = f32>,
+{
+    // Parameters in (0, 1) where some coordinate has a local extremum
+    pub fn extrema(&self) -> Vec<f32> {
+        let (d0, d1, d2) = (self.p1 - self.p0, self.p2 - self.p1, self.p3 - self.p2);
+        (0..self.p0.dim())
+            .flat_map(|axis| {
+                let (d0, d1, d2) = (d0[axis], d1[axis], d2[axis]);
+                unit_roots(d0 - 2. * d1 + d2, 2. * (d1 - d0), d0)
+            })
+            .collect()
+    }
+}
+impl CubicBezier<Vector2> {
+    pub fn bounds(&self) -> Rect {
+        let mut rect = Rect::new(self.p0.min(self.p3), self.p0.max(self.p3));
+        for t in self.extrema() {
+            rect = rect.expand_to(self.point(t));
+        }
+        rect
+    }
+}
+impl CubicBezier<Vector3> {
+    pub fn bounds(&self) -> Aabb3 {
+        let mut aabb = Aabb3::new(self.p0.min(self.p3), self.p0.max(self.p3));
+        for t in self.extrema() {
+            aabb = aabb.expand_to(self.point(t));
+        }
+        aabb
+    }
+}
+impl<V> Curve for CubicBezier<V>
+where
+    V: Vector + Interpolate,
+{
+    type Point = V;
+
+    fn point(&self, t: f32) -> V {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let c = self.p2.lerp(self.p3, t);
+        a.lerp(b, t).lerp(b.lerp(c, t), t)
+    }
+
+    fn derivative(&self, t: f32) -> V {
+        let (d0, d1, d2) = (self.p1 - self.p0, self.p2 - self.p1, self.p3 - self.p2);
+        d0.lerp(d1, t).lerp(d1.lerp(d2, t), t) * 3.
+    }
+
+    fn second_derivative(&self, t: f32) -> V {
+        let first = self.p2 - self.p1 - (self.p1 - self.p0);
+        let second = self.p3 - self.p2 - (self.p2 - self.p1);
+        first.lerp(second, t) * 6.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curves::{ArcLengthTable, Curve2, Curve3},
+        functions::constants::{EPSILON, PI},
+        Matrix, Matrix3,
+    };
+
+    // Quarter circle of radius 1 approximated by a cubic
+    fn quarter_circle() -> CubicBezier<Vector2> {
+        let k = 0.552_284_8;
+        CubicBezier::new(
+            Vector2::new(1., 0.),
+            Vector2::new(1., k),
+            Vector2::new(k, 1.),
+            Vector2::new(0., 1.),
+        )
+    }
+
+    fn helix_like() -> CubicBezier<Vector3> {
+        CubicBezier::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 0., 1.),
+            Vector3::new(1., 1., 2.),
+            Vector3::new(0., 1., 3.),
+        )
+    }
+
+    // Method point(t)
+    #[test]
+    fn test1_quadraticbezierpoint() {
+        let curve = QuadraticBezier::new(
+            Vector2::new(0., 0.),
+            Vector2::new(1., 2.),
+            Vector2::new(2., 0.),
+        );
+        assert_eq!(curve.point(0.), curve.p0);
+        assert_eq!(curve.point(1.), curve.p2);
+        assert_eq!(curve.point(0.5), Vector2::new(1., 1.));
+    }
+    #[test]
+    fn test1_cubicbezierpoint() {
+        let curve = quarter_circle();
+        assert_eq!(curve.point(0.), curve.p0);
+        assert_eq!(curve.point(1.), curve.p3);
+        for i in 0..=10 {
+            assert!((curve.point(i as f32 / 10.).len() - 1.).abs() < EPSILON);
+        }
+    }
+    // Methods derivative(t), second_derivative(t)
+    #[test]
+    fn test1_cubicbezierderivative() {
+        let curve = helix_like();
+        let h = 1e-3;
+        for t in [0.1, 0.5, 0.8] {
+            let numeric = (curve.point(t + h) - curve.point(t - h)) / (2. * h);
+            assert!((numeric - curve.derivative(t)).len() < 1e-2);
+            let numeric = (curve.derivative(t + h) - curve.derivative(t - h)) / (2. * h);
+            assert!((numeric - curve.second_derivative(t)).len() < 1e-2);
+        }
+        assert_eq!(curve.derivative(0.), (curve.p1 - curve.p0) * 3.);
+    }
+    #[test]
+    fn test1_quadraticbezierderivative() {
+        let curve = QuadraticBezier::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 2., 0.),
+            Vector3::new(2., 0., 1.),
+        );
+        assert_eq!(curve.derivative(1.), (curve.p2 - curve.p1) * 2.);
+        assert_eq!(curve.second_derivative(0.3), Vector3::new(0., -8., 2.));
+        assert_eq!(curve.elevate().point(0.3), curve.point(0.3));
+        assert_eq!(curve.elevate().derivative(0.7), curve.derivative(0.7));
+    }
+    // Method split(t)
+    #[test]
+    fn test1_cubicbeziersplit() {
+        let curve = helix_like();
+        let (left, right) = curve.split(0.3);
+        assert_eq!(left.point(1.), curve.point(0.3));
+        assert_eq!(left.point(0.5), curve.point(0.15));
+        assert_eq!(right.point(0.5), curve.point(0.65));
+        let (left, right) = QuadraticBezier::new(curve.p0, curve.p1, curve.p2).split(0.5);
+        assert_eq!(left.p2, right.p0);
+    }
+    // Method bounds()
+    #[test]
+    fn test1_cubicbezierbounds() {
+        let bounds = quarter_circle().bounds();
+        assert_eq!(
+            bounds,
+            Rect::new(Vector2::new(0., 0.), Vector2::new(1., 1.))
+        );
+        let curve = CubicBezier::new(
+            Vector2::new(0., 0.),
+            Vector2::new(0., 4.),
+            Vector2::new(4., 4.),
+            Vector2::new(4., 0.),
+        );
+        assert_eq!(
+            curve.bounds(),
+            Rect::new(Vector2::new(0., 0.), Vector2::new(4., 3.))
+        );
+    }
+    #[test]
+    fn test1_quadraticbezierbounds() {
+        let curve = QuadraticBezier::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 2., -2.),
+            Vector3::new(2., 0., 0.),
+        );
+        let bounds = curve.bounds();
+        assert_eq!(bounds.min, Vector3::new(0., 0., -1.));
+        assert_eq!(bounds.max, Vector3::new(2., 1., 0.));
+        assert!(helix_like().bounds().contains(helix_like().point(0.37)));
+    }
+    // Arc length
+    #[test]
+    fn test1_cubicbezierlength() {
+        assert!((quarter_circle().length() - PI / 2.).abs() < EPSILON);
+        let line = CubicBezier::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(0., 0., 1.),
+            Vector3::new(0., 0., 2.),
+            Vector3::new(0., 0., 3.),
+        );
+        assert!((line.length() - 3.).abs() < EPSILON);
+    }
+    #[test]
+    fn test1_arclengthtableparameter_at() {
+        let curve = quarter_circle();
+        let table = ArcLengthTable::new(&curve, 512);
+        let t = table.parameter_at(PI / 4.);
+        assert_eq!(curve.point(t), Vector2::new(0.5_f32.sqrt(), 0.5_f32.sqrt()));
+        assert_eq!(table.parameter_at(-1.), 0.);
+        assert_eq!(table.parameter_at(10.), 1.);
+        let points: Vec<Vector2> = table
+            .evenly_spaced(7)
+            .into_iter()
+            .map(|t| curve.point(t))
+            .collect();
+        for pair in points.windows(2) {
+            assert!(((pair[1] - pair[0]).len() - 0.2610).abs() < EPSILON);
+        }
+    }
+    // Method project(point)
+    #[test]
+    fn test1_cubicbezierproject() {
+        let curve = quarter_circle();
+        let (t, point) = curve.project(Vector2::new(3., 3.));
+        assert_eq!(point, Vector2::new(0.5_f32.sqrt(), 0.5_f32.sqrt()));
+        assert!((t - 0.5).abs() < EPSILON);
+        let (t, point) = curve.project(Vector2::new(2., -1.));
+        assert_eq!((t, point), (0., curve.p0));
+    }
+    // Method flatten(tolerance)
+    #[test]
+    fn test1_cubicbezierflatten() {
+        let curve = quarter_circle();
+        let coarse = curve.flatten(0.01);
+        let fine = curve.flatten(0.0001);
+        assert!(coarse.len() < fine.len());
+        assert_eq!(coarse[0], curve.p0);
+        assert_eq!(*coarse.last().unwrap(), curve.p3);
+        for pair in coarse.windows(2) {
+            let middle = (pair[0] + pair[1]) / 2.;
+            assert!(1. - middle.len() < 0.011);
+        }
+    }
+    #[test]
+    fn test2_cubicbezierflatten() {
+        // S-shaped curve whose midpoint lies on the chord
+        let curve = CubicBezier::new(
+            Vector2::new(0., 0.),
+            Vector2::new(1., 1.),
+            Vector2::new(1., -1.),
+            Vector2::new(2., 0.),
+        );
+        assert!(curve.flatten(0.01).len() > 4);
+    }
+    // Frames
+    #[test]
+    fn test1_cubicbeziernormal() {
+        let curve = quarter_circle();
+        // The left normal of a counterclockwise circle points to its centre
+        assert_eq!(curve.normal(0.5), -curve.point(0.5).normalize());
+    }
+    #[test]
+    fn test1_cubicbezierframe() {
+        let curve = helix_like();
+        for t in [0., 0.4, 1.] {
+            let frame = curve.frame(t);
+            let matrix = frame.matrix();
+            assert_eq!(matrix.transpose() * matrix, Matrix3::idenity());
+            assert!((matrix.det() - 1.).abs() < EPSILON);
+            assert_eq!(frame.tangent, curve.tangent(t));
+            assert!(frame.binormal.dot(curve.derivative(t)).abs() < EPSILON);
+        }
+    }
+    #[test]
+    fn test2_cubicbezierframe() {
+        // Slow parameterizations still have a direction, frames do not depend on scale
+        let curve = helix_like();
+        let tiny = CubicBezier::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1e-4, 0., 1e-4),
+            Vector3::new(1e-4, 1e-4, 2e-4),
+            Vector3::new(0., 1e-4, 3e-4),
+        );
+        for t in [0., 0.4, 1.] {
+            assert_eq!(tiny.frame(t), curve.frame(t));
+        }
+        let line = CubicBezier::new(
+            Vector2::new(0., 0.),
+            Vector2::new(1e-4, 0.),
+            Vector2::new(2e-4, 0.),
+            Vector2::new(3e-4, 0.),
+        );
+        assert_eq!(line.tangent(0.5), Vector2::new(1., 0.));
+        assert_eq!(
+            line.by_arc_length(16).derivative(1e-4),
+            Vector2::new(1., 0.)
+        );
+    }
+    #[test]
+    fn test1_cubicbezierrotation_minimizing_frames() {
+        let curve = helix_like();
+        let frames = curve.rotation_minimizing_frames(64);
+        assert_eq!(frames.len(), 64);
+        for frame in &frames {
+            assert!(frame.normal.dot(frame.tangent).abs() < EPSILON);
+            assert!((frame.normal.len() - 1.).abs() < EPSILON);
+        }
+        // A straight line has no Frenet frame but still gets a constant one
+        let line = CubicBezier::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(2., 0., 0.),
+            Vector3::new(3., 0., 0.),
+        );
+        let frames = line.rotation_minimizing_frames(5);
+        assert!(frames.iter().all(|frame| frame == &frames[0]));
+    }
+}
@@ -0,0 +1,449 @@
+use super::Curve;
+use crate::{Interpolate, Vector};
+
+// Evaluation works on degree + 1 control points in a fixed buffer of this many
+pub(crate) const MAX_DEGREE: usize = 15;
+
+// B-spline of degree up to MAX_DEGREE with n control points and n + degree + 1 finite,
+// non-decreasing knots, defined on [knots[degree], knots[n]]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline<V> {
+    degree: usize,
+    control_points: Vec<V>,
+    knots: Vec<f32>,
+}
+impl<V> BSpline<V>
+where
+    V: Vector + Interpolate,
+{
+    pub fn try_new(degree: usize, control_points: Vec<V>, knots: Vec<f32>) -> Option<Self> {
+        let n = control_points.len();
+        if n <= degree
+            || degree > MAX_DEGREE
+            || knots.len() != n + degree + 1
+            || knots.iter().any(|knot| !knot.is_finite())
+            || knots.windows(2).any(|pair| pair[0] > pair[1])
+            || knots[degree] >= knots[n]
+        {
+            return None;
+        }
+        Some(Self {
+            degree,
+            control_points,
+            knots,
+        })
+    }
+
+    pub fn new(degree: usize, control_points: Vec<V>, knots: Vec<f32>) -> Self {
+        Self::try_new(degree, control_points, knots)
+            .expect("It is impossible to build a B-spline from these control points and knots")
+    }
+
+    // Knots 0, 1, 2, ... shifted so the domain is [0, n - degree]
+    pub fn try_uniform(degree: usize, control_points: Vec<V>) -> Option<Self> {
+        let knots = (0..control_points.len() + degree + 1)
+            .map(|i| i as f32 - degree as f32)
+            .collect();
+        Self::try_new(degree, control_points, knots)
+    }
+
+    pub fn uniform(degree: usize, control_points: Vec<V>) -> Self {
+        Self::try_uniform(degree, control_points).expect(
+            "It is impossible to build a B-spline with no more control points than its degree",
+        )
+    }
+
+    // Uniform inside, with the end knots repeated so the curve starts and ends at the
+    // first and last control points. Domain is [0, n - degree]
+    pub fn try_clamped(degree: usize, control_points: Vec<V>) -> Option<Self> {
+        let segments = control_points.len().checked_sub(degree)?;
+        let knots = (0..control_points.len() + degree + 1)
+            .map(|i| i.saturating_sub(degree).min(segments) as f32)
+            .collect();
+        Self::try_new(degree, control_points, knots)
+    }
+
+    pub fn clamped(degree: usize, control_points: Vec<V>) -> Self {
+        Self::try_clamped(degree, control_points).expect(
+            "It is impossible to build a B-spline with no more control points than its degree",
+        )
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    pub fn control_points(&self) -> &[V] {
+        &self.control_points
+    }
+
+    pub fn knots(&self) -> &[f32] {
+        &self.knots
+    }
+
+    // Derivative curve, a B-spline of one degree less
+    pub fn hodograph(&self) -> Self {
+        let (p, n) = (self.degree, self.control_points.len());
+        if p == 0 {
+            return Self {
+                degree: 0,
+                control_points: vec![V::zero(); n],
+                knots: self.knots.clone(),
+            };
+        }
+        let control_points = (0..n - 1)
+            .map(|i| {
+                let span = self.knots[i + p + 1] - self.knots[i + 1];
+                if span <= 0. {
+                    V::zero()
+                } else {
+                    (self.control_points[i + 1] - self.control_points[i]) * (p as f32 / span)
+                }
+            })
+            .collect();
+        Self {
+            degree: p - 1,
+            control_points,
+            knots: self.knots[1..self.knots.len() - 1].to_vec(),
+        }
+    }
+
+    // Boehm's algorithm, the same curve with one more knot and control point
+    pub fn insert_knot(&self, t: f32) -> Self {
+        if t.is_nan() {
+            return self.clone();
+        }
+        let (p, n) = (self.degree, self.control_points.len());
+        let (t, k) = find_span(&self.knots, p, n, t);
+        let control_points = (0..=n)
+            .map(|i| {
+                if i + p <= k {
+                    self.control_points[i]
+                } else if i > k {
+                    self.control_points[i - 1]
+                } else {
+                    let alpha = (t - self.knots[i]) / (self.knots[i + p] - self.knots[i]);
+                    self.control_points[i - 1].lerp(self.control_points[i], alpha)
+                }
+            })
+            .collect();
+        let mut knots = self.knots.clone();
+        knots.insert(k + 1, t);
+        Self {
+            degree: p,
+            control_points,
+            knots,
+        }
+    }
+
+    // Point (order 0) or derivative on the span holding t, without building hodographs
+    fn evaluate(&self, t: f32, order: usize) -> V {
+        let p = self.degree;
+        let (t, k) = find_span(&self.knots, p, self.control_points.len(), t);
+        let mut points = [V::zero(); MAX_DEGREE + 1];
+        points[..=p].copy_from_slice(&self.control_points[k - p..=k]);
+        evaluate_span(p, points, &self.knots, k, t, order)
+    }
+}
+impl<V> Curve for BSpline<V>
+where
+    V: Vector + Interpolate,
+{
+    type Point = V;
+
+    fn domain(&self) -> (f32, f32) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+
+    fn point(&self, t: f32) -> V {
+        self.evaluate(t, 0)
+    }
+
+    fn derivative(&self, t: f32) -> V {
+        self.evaluate(t, 1)
+    }
+
+    fn second_derivative(&self, t: f32) -> V {
+        self.evaluate(t, 2)
+    }
+}
+
+// Parameter clamped to the domain and the index k of the knot span
+// knots[k] <= t < knots[k + 1], the last span also owns the end of the domain
+pub(crate) fn find_span(knots: &[f32], degree: usize, n: usize, t: f32) -> (f32, usize) {
+    // NaN compares false with every knot, evaluate on the first span so it propagates instead
+    if t.is_nan() {
+        return (t, degree);
+    }
+    let t = t.clamp(knots[degree], knots[n]);
+    let spans = if t >= knots[n] {
+        knots[degree..n].partition_point(|&knot| knot < t)
+    } else {
+        knots[degree..n].partition_point(|&knot| knot <= t)
+    };
+    let k = degree + spans - 1;
+    (t, k)
+}
+
+// Derivative of the given order on span k, where points[j] is control point k - degree + j.
+// The points are differentiated in place into those of the hodograph, whose knots are the
+// original ones shifted by one, then de Boor's algorithm runs on what is left
+pub(crate) fn evaluate_span<V>(
+    degree: usize,
+    mut points: [V; MAX_DEGREE + 1],
+    knots: &[f32],
+    k: usize,
+    t: f32,
+    order: usize,
+) -> V
+where
+    V: Vector + Interpolate,
+{
+    let p = degree;
+    if order > p {
+        return V::zero();
+    }
+    for r in 1..=order {
+        let scale = (p - r + 1) as f32;
+        for j in 0..=p - r {
+            let span = knots[k + j + 1] - knots[k + j + r - p];
+            points[j] = if span > 0. {
+                (points[j + 1] - points[j]) * (scale / span)
+            } else {
+                V::zero()
+            };
+        }
+    }
+    for r in 1..=p - order {
+        for j in (r..=p - order).rev() {
+            let i = j + k - p;
+            let span = knots[i + 1 + p - r] - knots[i + order];
+            let alpha = if span > 0. {
+                (t - knots[i + order]) / span
+            } else {
+                0.
+            };
+            points[j] = points[j - 1].lerp(points[j], alpha);
+        }
+    }
+    points[p - order]
+}
+
+// Values and first derivatives of the degree + 1 basis functions that are non-zero on span k,
+// entry j belongs to basis function k - degree + j
+pub(crate) fn basis_functions(
+    degree: usize,
+    knots: &[f32],
+    k: usize,
+    t: f32,
+) -> ([f32; MAX_DEGREE + 1], [f32; MAX_DEGREE + 1]) {
+    let p = degree;
+    let ratio = |numerator: f32, span: f32| if span > 0. { numerator / span } else { 0. };
+    let mut values = [0.; MAX_DEGREE + 1];
+    let mut derivatives = [0.; MAX_DEGREE + 1];
+    values[0] = 1.;
+    for d in 1..=p {
+        // The derivative of degree p comes from the values of degree p - 1
+        if d == p {
+            for j in 0..=p {
+                let i = k + j - p;
+                let left = if j > 0 {
+                    ratio(values[j - 1], knots[i + p] - knots[i])
+                } else {
+                    0.
+                };
+                let right = if j < p {
+                    ratio(values[j], knots[i + p + 1] - knots[i + 1])
+                } else {
+                    0.
+                };
+                derivatives[j] = p as f32 * (left - right);
+            }
+        }
+        // Raise the degree of the d non-zero functions on the span to d + 1 of degree d
+        let mut carry = 0.;
+        for j in 0..d {
+            let (low, high) = (knots[k + j + 1 - d], knots[k + j + 1]);
+            let term = ratio(values[j], high - low);
+            values[j] = carry + (high - t) * term;
+            carry = (t - low) * term;
+        }
+        values[d] = carry;
+    }
+    (values, derivatives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    fn control_points() -> Vec<Vector2> {
+        vec![
+            Vector2::new(0., 0.),
+            Vector2::new(1., 2.),
+            Vector2::new(3., 2.),
+            Vector2::new(4., 0.),
+            Vector2::new(6., 1.),
+        ]
+    }
+
+    // Function try_new()
+    #[test]
+    fn test1_bsplinetry_new() {
+        let points = control_points();
+        assert!(BSpline::try_new(2, points.clone(), vec![0.; 7]).is_none());
+        assert!(BSpline::try_new(2, points.clone(), vec![0., 0., 0., 1., 2., 3., 3.]).is_none());
+        assert!(
+            BSpline::try_new(2, points.clone(), vec![0., 0., 0., 2., 1., 3., 3., 3.]).is_none()
+        );
+        assert!(
+            BSpline::try_new(2, points.clone(), vec![0., 0., 0., 1., 2., 3., 3., 3.]).is_some()
+        );
+        assert!(BSpline::try_uniform(5, points.clone()).is_none());
+        assert!(BSpline::try_clamped(6, points).is_none());
+    }
+    #[test]
+    fn test2_bsplinetry_new() {
+        let points = vec![Vector2::new(0., 0.), Vector2::new(1., 1.)];
+        assert!(BSpline::try_new(1, points.clone(), vec![0., f32::NAN, 1., 1.]).is_none());
+        assert!(BSpline::try_new(1, points, vec![0., 0., 1., f32::INFINITY]).is_none());
+        let points = vec![Vector2::zero(); MAX_DEGREE + 2];
+        assert!(BSpline::try_clamped(MAX_DEGREE + 1, points.clone()).is_none());
+        assert!(BSpline::try_clamped(MAX_DEGREE, points).is_some());
+    }
+    // Function clamped()
+    #[test]
+    fn test1_bsplineclamped() {
+        let curve = BSpline::clamped(3, control_points());
+        assert_eq!(curve.knots(), &[0., 0., 0., 0., 1., 2., 2., 2., 2.]);
+        assert_eq!(curve.domain(), (0., 2.));
+        assert_eq!(curve.point(0.), control_points()[0]);
+        assert_eq!(curve.point(2.), control_points()[4]);
+        // End tangents follow the control polygon
+        assert_eq!(
+            curve.derivative(0.),
+            (control_points()[1] - control_points()[0]) * 3.
+        );
+    }
+    // Function uniform()
+    #[test]
+    fn test1_bsplineuniform() {
+        let curve = BSpline::uniform(3, control_points());
+        assert_eq!(curve.domain(), (0., 2.));
+        let p = control_points();
+        assert_eq!(curve.point(0.), (p[0] + p[1] * 4. + p[2]) / 6.);
+        assert_eq!(curve.point(1.), (p[1] + p[2] * 4. + p[3]) / 6.);
+        assert_eq!(curve.derivative(1.), (p[3] - p[1]) / 2.);
+        assert_eq!(curve.second_derivative(1.), p[3] - p[2] * 2. + p[1]);
+    }
+    #[test]
+    fn test2_bsplineuniform() {
+        // Degree 1 is the control polygon
+        let curve = BSpline::uniform(1, control_points());
+        assert_eq!(curve.point(2.5), Vector2::new(3.5, 1.));
+        assert_eq!(curve.derivative(2.5), Vector2::new(1., -2.));
+        assert_eq!(curve.second_derivative(2.5), Vector2::zero());
+    }
+    // Methods derivative(t), second_derivative(t)
+    #[test]
+    fn test1_bsplinederivative() {
+        let points = vec![
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 2., 0.),
+            Vector3::new(3., 2., 1.),
+            Vector3::new(4., 0., 2.),
+            Vector3::new(6., 1., 0.),
+            Vector3::new(7., 3., 1.),
+        ];
+        let knots = vec![0., 0., 0., 0., 0.3, 0.5, 1., 1., 1., 1.];
+        let curve = BSpline::new(3, points.clone(), knots);
+        assert_eq!(curve.point(0.), points[0]);
+        assert_eq!(curve.point(1.), points[5]);
+        let h = 1e-3;
+        for t in [0.1, 0.3, 0.45, 0.8] {
+            let numeric = (curve.point(t + h) - curve.point(t - h)) / (2. * h);
+            assert!((numeric - curve.derivative(t)).len() < 5e-2);
+            let numeric = (curve.derivative(t + h) - curve.derivative(t - h)) / (2. * h);
+            assert!((numeric - curve.second_derivative(t)).len() < 0.5);
+        }
+    }
+    #[test]
+    fn test2_bsplinederivative() {
+        // Derivatives on the span match the hodographs, also across repeated knots
+        let knots = vec![0., 0., 0., 0., 0.5, 0.5, 2., 2., 2., 2.];
+        let points = [control_points(), vec![Vector2::new(8., -1.)]].concat();
+        let curve = BSpline::new(3, points, knots);
+        let (first, second) = (curve.hodograph(), curve.hodograph().hodograph());
+        for i in 0..=40 {
+            let t = i as f32 / 20.;
+            assert_eq!(curve.derivative(t), first.point(t));
+            assert_eq!(curve.second_derivative(t), second.point(t));
+        }
+        let line = BSpline::uniform(1, control_points());
+        assert_eq!(
+            line.second_derivative(1.5),
+            line.hodograph().hodograph().point(1.5)
+        );
+    }
+    // Method insert_knot(t)
+    #[test]
+    fn test1_bsplineinsert_knot() {
+        let curve = BSpline::clamped(3, control_points());
+        let refined = curve.insert_knot(0.5).insert_knot(0.5).insert_knot(1.7);
+        assert_eq!(refined.control_points().len(), 8);
+        assert_eq!(
+            refined.knots(),
+            &[0., 0., 0., 0., 0.5, 0.5, 1., 1.7, 2., 2., 2., 2.]
+        );
+        for t in [0., 0.25, 0.5, 0.9, 1.7, 2.] {
+            assert_eq!(refined.point(t), curve.point(t));
+            assert_eq!(refined.derivative(t), curve.derivative(t));
+        }
+        // Knots at full multiplicity make the curve pass through a control point
+        let refined = refined.insert_knot(0.5);
+        assert!(refined.control_points().contains(&curve.point(0.5)));
+    }
+    #[test]
+    fn test1_bsplinepoint() {
+        // Sums of basis functions are one, so a constant polygon gives a constant curve
+        let points = vec![Vector2::new(2., -1.); 6];
+        let curve = BSpline::new(2, points, vec![0., 1., 1., 2., 4., 4., 5., 7., 8.]);
+        for t in [1., 1.5, 3., 4.9, 5.] {
+            assert_eq!(curve.point(t), Vector2::new(2., -1.));
+        }
+        assert_eq!(curve.domain(), (1., 5.));
+    }
+    #[test]
+    fn test2_bsplinepoint() {
+        let curve = BSpline::clamped(2, control_points());
+        assert!(curve.point(f32::NAN).x.is_nan());
+        assert!(curve.derivative(f32::NAN).y.is_nan());
+        assert_eq!(curve.point(f32::INFINITY), control_points()[4]);
+    }
+    // Function basis_functions()
+    #[test]
+    fn test1_basis_functions() {
+        // Weighting the control points by the basis functions gives the point and derivative
+        let curve = BSpline::new(
+            3,
+            control_points(),
+            vec![0., 0., 0., 0., 1., 2., 2., 2., 2.],
+        );
+        for t in [0., 0.5, 1., 1.5, 2.] {
+            let (t, k) = find_span(curve.knots(), 3, 5, t);
+            let (values, derivatives) = basis_functions(3, curve.knots(), k, t);
+            assert!((values.iter().sum::<f32>() - 1.).abs() < 1e-6);
+            let (mut point, mut derivative) = (Vector2::zero(), Vector2::zero());
+            for j in 0..=3 {
+                point += control_points()[k - 3 + j] * values[j];
+                derivative += control_points()[k - 3 + j] * derivatives[j];
+            }
+            assert!((point - curve.point(t)).len() < 1e-5);
+            assert!((derivative - curve.derivative(t)).len() < 1e-4);
+        }
+    }
+}
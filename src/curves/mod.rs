@@ -0,0 +1,344 @@
+pub mod bezier;
+pub mod bspline;
+pub mod nurbs;
+pub mod spline;
+
+use crate::{Interpolate, Matrix3, Vector, Vector2, Vector3};
+
+const PROJECTION_SAMPLES: usize = 32;
+const PROJECTION_ITERATIONS: usize = 8;
+const FLATTEN_MAX_DEPTH: u32 = 16;
+const LENGTH_SAMPLES: usize = 256;
+
+// Unit vector along vec for any length that squares without underflow. Vector::try_normalize
+// rejects lengths under EPSILON, but a curve with a slow parameterization still has a direction
+pub(crate) fn try_direction<V: Vector + Interpolate>(vec: V) -> Option<V> {
+    let len_squared = vec.inner(vec);
+    if len_squared <= f32::MIN_POSITIVE || !len_squared.is_finite() {
+        return None;
+    }
+    Some(vec * (1. / len_squared.sqrt()))
+}
+
+// Parametric curve over a finite parameter domain
+pub trait Curve {
+    type Point: Vector + Interpolate;
+
+    fn point(&self, t: f32) -> Self::Point;
+    fn derivative(&self, t: f32) -> Self::Point;
+    fn second_derivative(&self, t: f32) -> Self::Point;
+
+    fn domain(&self) -> (f32, f32) {
+        (0., 1.)
+    }
+
+    // Unit tangent, zero where the curve is stationary
+    fn tangent(&self, t: f32) -> Self::Point {
+        try_direction(self.derivative(t)).unwrap_or_else(Self::Point::zero)
+    }
+
+    fn length(&self) -> f32
+    where
+        Self: Sized,
+    {
+        ArcLengthTable::new(self, LENGTH_SAMPLES).length()
+    }
+
+    // The same curve parameterized by distance travelled along it
+    fn by_arc_length(self, samples: usize) -> ArcLengthCurve<Self>
+    where
+        Self: Sized,
+    {
+        ArcLengthCurve::new(self, samples)
+    }
+
+    // Parameter and position of the curve point closest to the given point
+    fn project(&self, point: Self::Point) -> (f32, Self::Point) {
+        let (start, end) = self.domain();
+        let parameter = |i: usize| start + (end - start) * i as f32 / PROJECTION_SAMPLES as f32;
+        let distance = |t: f32| (self.point(t) - point).len();
+        let mut t = (0..=PROJECTION_SAMPLES)
+            .map(parameter)
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap_or(start);
+        // Newton on (P(t) - p) . P'(t) = 0
+        for _ in 0..PROJECTION_ITERATIONS {
+            let offset = self.point(t) - point;
+            let first = self.derivative(t);
+            let slope = first.inner(first) + offset.inner(self.second_derivative(t));
+            if slope.abs() <= f32::MIN_POSITIVE {
+                break;
+            }
+            let next = (t - offset.inner(first) / slope).clamp(start, end);
+            if distance(next) > distance(t) {
+                break;
+            }
+            t = next;
+        }
+        (t, self.point(t))
+    }
+
+    // Polyline whose points lie on the curve and whose segments stay within tolerance of it
+    fn flatten(&self, tolerance: f32) -> Vec<Self::Point> {
+        let (start, end) = self.domain();
+        let mut points = vec![self.point(start)];
+        flatten_range(self, start, end, tolerance, 0, &mut points);
+        points
+    }
+}
+
+fn segment_distance<P: Vector + Interpolate>(point: P, a: P, b: P) -> f32 {
+    let t = a.inverse_lerp(b, point).clamp(0., 1.);
+    (point - a.lerp(b, t)).len()
+}
+
+fn flatten_range<C: Curve + ?Sized>(
+    curve: &C,
+    start: f32,
+    end: f32,
+    tolerance: f32,
+    depth: u32,
+    points: &mut Vec<C::Point>,
+) {
+    let (a, b) = (curve.point(start), curve.point(end));
+    // Quarter points as well, a single midpoint misses S-shaped spans crossing the chord
+    let flat = [0.25, 0.5, 0.75].iter().all(|&s| {
+        let point = curve.point(start + (end - start) * s);
+        segment_distance(point, a, b) <= tolerance
+    });
+    if (flat && depth > 0) || depth == FLATTEN_MAX_DEPTH {
+        points.push(b);
+        return;
+    }
+    let middle = (start + end) / 2.;
+    flatten_range(curve, start, middle, tolerance, depth + 1, points);
+    flatten_range(curve, middle, end, tolerance, depth + 1, points);
+}
+
+// Cumulative chord lengths over evenly spaced parameters, maps distance along the curve
+// back to a parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable {
+    parameters: Vec<f32>,
+    lengths: Vec<f32>,
+}
+impl ArcLengthTable {
+    pub fn new<C: Curve>(curve: &C, samples: usize) -> Self {
+        let samples = samples.max(1);
+        let (start, end) = curve.domain();
+        let parameters: Vec<f32> = (0..=samples)
+            .map(|i| start + (end - start) * i as f32 / samples as f32)
+            .collect();
+        let mut lengths = Vec::with_capacity(parameters.len());
+        let mut length = 0.;
+        let mut previous = curve.point(start);
+        for &t in &parameters {
+            let point = curve.point(t);
+            length += (point - previous).len();
+            lengths.push(length);
+            previous = point;
+        }
+        Self {
+            parameters,
+            lengths,
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or(0.)
+    }
+
+    // Distance is clamped to [0, length()]
+    pub fn parameter_at(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0., self.length());
+        let i = self.lengths.partition_point(|&length| length < distance);
+        if i == 0 {
+            return self.parameters[0];
+        }
+        let (before, after) = (self.lengths[i - 1], self.lengths[i]);
+        let s = if after > before {
+            (distance - before) / (after - before)
+        } else {
+            0.
+        };
+        self.parameters[i - 1].lerp(self.parameters[i], s)
+    }
+
+    // Fraction 0..=1 of the total length
+    pub fn parameter_at_fraction(&self, fraction: f32) -> f32 {
+        self.parameter_at(fraction * self.length())
+    }
+
+    // Parameters of count points spaced evenly by arc length, ends included
+    pub fn evenly_spaced(&self, count: usize) -> Vec<f32> {
+        match count {
+            0 => Vec::new(),
+            1 => vec![self.parameter_at(0.)],
+            _ => (0..count)
+                .map(|i| self.parameter_at_fraction(i as f32 / (count - 1) as f32))
+                .collect(),
+        }
+    }
+}
+
+// Unit speed reparameterization of a curve over [0, length]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthCurve<C> {
+    curve: C,
+    table: ArcLengthTable,
+}
+impl<C: Curve> ArcLengthCurve<C> {
+    pub fn new(curve: C, samples: usize) -> Self {
+        let table = ArcLengthTable::new(&curve, samples);
+        Self { curve, table }
+    }
+
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    pub fn table(&self) -> &ArcLengthTable {
+        &self.table
+    }
+
+    // Parameter of the underlying curve at the given distance
+    pub fn parameter(&self, distance: f32) -> f32 {
+        self.table.parameter_at(distance)
+    }
+
+    // Count points at constant spacing along the curve, ends included
+    pub fn sample(&self, count: usize) -> Vec<C::Point> {
+        self.table
+            .evenly_spaced(count)
+            .into_iter()
+            .map(|t| self.curve.point(t))
+            .collect()
+    }
+}
+impl<C: Curve> Curve for ArcLengthCurve<C> {
+    type Point = C::Point;
+
+    fn domain(&self) -> (f32, f32) {
+        (0., self.table.length())
+    }
+
+    fn point(&self, s: f32) -> C::Point {
+        self.curve.point(self.parameter(s))
+    }
+
+    fn derivative(&self, s: f32) -> C::Point {
+        self.curve.tangent(self.parameter(s))
+    }
+
+    // Curvature vector, the part of C'' normal to the tangent divided by |C'|^2
+    fn second_derivative(&self, s: f32) -> C::Point {
+        let t = self.parameter(s);
+        let first = self.curve.derivative(t);
+        let speed_squared = first.inner(first);
+        if speed_squared <= f32::MIN_POSITIVE {
+            return C::Point::zero();
+        }
+        let tangent = first * (1. / speed_squared.sqrt());
+        let second = self.curve.second_derivative(t);
+        (second - tangent * second.inner(tangent)) * (1. / speed_squared)
+    }
+
+    fn length(&self) -> f32 {
+        self.table.length()
+    }
+}
+
+// Orthonormal frame along a 3D curve, as matrix columns tangent, normal, binormal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub tangent: Vector3,
+    pub normal: Vector3,
+    pub binormal: Vector3,
+}
+impl Frame {
+    pub fn matrix(&self) -> Matrix3 {
+        Matrix3::from_cols(self.tangent, self.normal, self.binormal)
+    }
+}
+
+fn any_perpendicular(vec: Vector3) -> Vector3 {
+    let axis = if vec.x.abs() < 0.9 {
+        Vector3::new(1., 0., 0.)
+    } else {
+        Vector3::new(0., 1., 0.)
+    };
+    vec.cross(axis).normalize()
+}
+
+pub trait Curve2: Curve<Point = Vector2> {
+    // Tangent rotated counterclockwise by a right angle
+    fn normal(&self, t: f32) -> Vector2 {
+        let tangent = self.tangent(t);
+        Vector2::new(-tangent.y, tangent.x)
+    }
+}
+impl<C: Curve<Point = Vector2>> Curve2 for C {}
+
+pub trait Curve3: Curve<Point = Vector3> {
+    // Frenet frame, on straight spans the normal is an arbitrary perpendicular
+    fn frame(&self, t: f32) -> Frame {
+        let first = self.derivative(t);
+        let tangent = try_direction(first).unwrap_or(Vector3::new(1., 0., 0.));
+        let binormal = try_direction(tangent.cross(self.second_derivative(t)))
+            .unwrap_or_else(|| tangent.cross(any_perpendicular(tangent)));
+        Frame {
+            tangent,
+            normal: binormal.cross(tangent),
+            binormal,
+        }
+    }
+
+    fn normal(&self, t: f32) -> Vector3 {
+        self.frame(t).normal
+    }
+
+    fn binormal(&self, t: f32) -> Vector3 {
+        self.frame(t).binormal
+    }
+
+    // Frames at count evenly spaced parameters that do not twist around the tangent, using
+    // the double reflection method. Unlike Frenet frames they stay defined on straight spans
+    fn rotation_minimizing_frames(&self, count: usize) -> Vec<Frame> {
+        let (start, end) = self.domain();
+        let parameter = |i: usize| start + (end - start) * i as f32 / (count.max(2) - 1) as f32;
+        let mut frames: Vec<Frame> = Vec::with_capacity(count);
+        for i in 0..count {
+            let t = parameter(i);
+            let Some(previous) = frames.last() else {
+                frames.push(self.frame(t));
+                continue;
+            };
+            let tangent = self.tangent(t);
+            let tangent = if tangent == Vector3::zero() {
+                previous.tangent
+            } else {
+                tangent
+            };
+            let reflect = |vec: Vector3, axis: Vector3| {
+                let len_squared = axis.dot(axis);
+                if len_squared <= f32::MIN_POSITIVE {
+                    return vec;
+                }
+                vec - axis * (2. * axis.dot(vec) / len_squared)
+            };
+            let step = self.point(t) - self.point(parameter(i - 1));
+            let normal_left = reflect(previous.normal, step);
+            let tangent_left = reflect(previous.tangent, step);
+            let normal = reflect(normal_left, tangent - tangent_left)
+                .try_normalize()
+                .unwrap_or(previous.normal);
+            frames.push(Frame {
+                tangent,
+                normal,
+                binormal: tangent.cross(normal),
+            });
+        }
+        frames
+    }
+}
+impl<C: Curve<Point = Vector3>> Curve3 for C {}
@@ -0,0 +1,478 @@
+use super::{
+    bspline::{basis_functions, find_span, BSpline},
+    try_direction, Curve,
+};
+use crate::{Vector, Vector2, Vector3, Vector4};
+
+// Cartesian part and weight of a homogeneous point (x w, y w, z w, w)
+fn split(point: Vector4) -> (Vector3, f32) {
+    (Vector3::new(point.x, point.y, point.z), point.w)
+}
+
+// Rational derivative (A' - C w') / w from the homogeneous derivative of A / w
+fn rational_derivative(point: Vector3, weight: f32, derivative: Vector4) -> Vector3 {
+    let (first, weight_first) = split(derivative);
+    (first - point * weight_first) / weight
+}
+
+// Rational B-spline curve, control points are homogeneous (x w, y w, z w, w) with w > 0
+#[derive(Debug, Clone, PartialEq)]
+pub struct NurbsCurve {
+    spline: BSpline<Vector4>,
+}
+impl NurbsCurve {
+    pub fn try_new(degree: usize, control_points: Vec<Vector4>, knots: Vec<f32>) -> Option<Self> {
+        if control_points.iter().any(|point| point.w <= 0.) {
+            return None;
+        }
+        BSpline::try_new(degree, control_points, knots).map(|spline| Self { spline })
+    }
+
+    pub fn new(degree: usize, control_points: Vec<Vector4>, knots: Vec<f32>) -> Self {
+        Self::try_new(degree, control_points, knots)
+            .expect("It is impossible to build a NURBS curve from these control points and knots")
+    }
+
+    pub fn degree(&self) -> usize {
+        self.spline.degree()
+    }
+
+    pub fn control_points(&self) -> &[Vector4] {
+        self.spline.control_points()
+    }
+
+    pub fn knots(&self) -> &[f32] {
+        self.spline.knots()
+    }
+
+    // The same curve with one more knot and control point
+    pub fn insert_knot(&self, t: f32) -> Self {
+        Self {
+            spline: self.spline.insert_knot(t),
+        }
+    }
+}
+impl Curve for NurbsCurve {
+    type Point = Vector3;
+
+    fn domain(&self) -> (f32, f32) {
+        self.spline.domain()
+    }
+
+    fn point(&self, t: f32) -> Vector3 {
+        let (point, weight) = split(self.spline.point(t));
+        point / weight
+    }
+
+    fn derivative(&self, t: f32) -> Vector3 {
+        let (point, weight) = split(self.spline.point(t));
+        let point = point / weight;
+        rational_derivative(point, weight, self.spline.derivative(t))
+    }
+
+    fn second_derivative(&self, t: f32) -> Vector3 {
+        let (point, weight) = split(self.spline.point(t));
+        let point = point / weight;
+        let first = self.spline.derivative(t);
+        let derivative = rational_derivative(point, weight, first);
+        let (second, weight_second) = split(self.spline.second_derivative(t));
+        (second - derivative * (2. * first.w) - point * weight_second) / weight
+    }
+}
+
+// Indexed triangle list, triangles wind counterclockwise around the normals
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TriangleMesh {
+    pub positions: Vec<Vector3>,
+    pub normals: Vec<Vector3>,
+    pub uvs: Vec<Vector2>,
+    pub indices: Vec<[u32; 3]>,
+}
+
+// Tensor product rational surface, control_points[i][j] is the homogeneous control point i
+// along u and j along v
+#[derive(Debug, Clone, PartialEq)]
+pub struct NurbsSurface {
+    degree_u: usize,
+    degree_v: usize,
+    control_points: Vec<Vec<Vector4>>,
+    knots_u: Vec<f32>,
+    knots_v: Vec<f32>,
+}
+impl NurbsSurface {
+    pub fn try_new(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<Vector4>>,
+        knots_u: Vec<f32>,
+        knots_v: Vec<f32>,
+    ) -> Option<Self> {
+        let first = control_points.first()?;
+        if control_points.iter().any(|row| row.len() != first.len())
+            || control_points.iter().flatten().any(|point| point.w <= 0.)
+        {
+            return None;
+        }
+        // Validate both knot vectors against one row and one column
+        BSpline::try_new(
+            degree_u,
+            vec![Vector4::zero(); control_points.len()],
+            knots_u.clone(),
+        )?;
+        BSpline::try_new(degree_v, first.clone(), knots_v.clone())?;
+        Some(Self {
+            degree_u,
+            degree_v,
+            control_points,
+            knots_u,
+            knots_v,
+        })
+    }
+
+    pub fn new(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<Vector4>>,
+        knots_u: Vec<f32>,
+        knots_v: Vec<f32>,
+    ) -> Self {
+        Self::try_new(degree_u, degree_v, control_points, knots_u, knots_v)
+            .expect("It is impossible to build a NURBS surface from these control points and knots")
+    }
+
+    pub fn degrees(&self) -> (usize, usize) {
+        (self.degree_u, self.degree_v)
+    }
+
+    pub fn control_points(&self) -> &[Vec<Vector4>] {
+        &self.control_points
+    }
+
+    pub fn knots_u(&self) -> &[f32] {
+        &self.knots_u
+    }
+
+    pub fn knots_v(&self) -> &[f32] {
+        &self.knots_v
+    }
+
+    pub fn domain(&self) -> ((f32, f32), (f32, f32)) {
+        let (n, m) = (self.control_points.len(), self.control_points[0].len());
+        (
+            (self.knots_u[self.degree_u], self.knots_u[n]),
+            (self.knots_v[self.degree_v], self.knots_v[m]),
+        )
+    }
+
+    fn row(&self, i: usize) -> BSpline<Vector4> {
+        BSpline::new(
+            self.degree_v,
+            self.control_points[i].clone(),
+            self.knots_v.clone(),
+        )
+    }
+
+    fn column(&self, points: Vec<Vector4>) -> BSpline<Vector4> {
+        BSpline::new(self.degree_u, points, self.knots_u.clone())
+    }
+
+    // Homogeneous point and its partial derivatives along u and v, from the basis functions
+    // of the two knot spans holding (u, v)
+    fn homogeneous(&self, u: f32, v: f32) -> (Vector4, Vector4, Vector4) {
+        let (p, q) = (self.degree_u, self.degree_v);
+        let (n, m) = (self.control_points.len(), self.control_points[0].len());
+        let (u, k) = find_span(&self.knots_u, p, n, u);
+        let (v, l) = find_span(&self.knots_v, q, m, v);
+        let (values_u, derivatives_u) = basis_functions(p, &self.knots_u, k, u);
+        let (values_v, derivatives_v) = basis_functions(q, &self.knots_v, l, v);
+        let mut result = (Vector4::zero(), Vector4::zero(), Vector4::zero());
+        for (a, row) in self.control_points[k - p..=k].iter().enumerate() {
+            let (mut point, mut along_v) = (Vector4::zero(), Vector4::zero());
+            for (b, &control_point) in row[l - q..=l].iter().enumerate() {
+                point += control_point * values_v[b];
+                along_v += control_point * derivatives_v[b];
+            }
+            result.0 += point * values_u[a];
+            result.1 += point * derivatives_u[a];
+            result.2 += along_v * values_u[a];
+        }
+        result
+    }
+
+    pub fn point(&self, u: f32, v: f32) -> Vector3 {
+        let (point, _, _) = self.homogeneous(u, v);
+        let (point, weight) = split(point);
+        point / weight
+    }
+
+    // Partial derivatives along u and v
+    pub fn derivatives(&self, u: f32, v: f32) -> (Vector3, Vector3) {
+        let (point, along_u, along_v) = self.homogeneous(u, v);
+        let (point, weight) = split(point);
+        let point = point / weight;
+        (
+            rational_derivative(point, weight, along_u),
+            rational_derivative(point, weight, along_v),
+        )
+    }
+
+    // Unit normal along du x dv, zero where the surface is degenerate
+    pub fn normal(&self, u: f32, v: f32) -> Vector3 {
+        self.point_normal(u, v).1
+    }
+
+    fn point_normal(&self, u: f32, v: f32) -> (Vector3, Vector3) {
+        let (point, along_u, along_v) = self.homogeneous(u, v);
+        let (point, weight) = split(point);
+        let point = point / weight;
+        let along_u = rational_derivative(point, weight, along_u);
+        let along_v = rational_derivative(point, weight, along_v);
+        let normal = try_direction(along_u.cross(along_v)).unwrap_or_else(Vector3::zero);
+        (point, normal)
+    }
+
+    pub fn insert_knot_u(&self, t: f32) -> Self {
+        let m = self.control_points[0].len();
+        let columns: Vec<BSpline<Vector4>> = (0..m)
+            .map(|j| {
+                let points = self.control_points.iter().map(|row| row[j]).collect();
+                self.column(points).insert_knot(t)
+            })
+            .collect();
+        let control_points = (0..=self.control_points.len())
+            .map(|i| {
+                columns
+                    .iter()
+                    .map(|column| column.control_points()[i])
+                    .collect()
+            })
+            .collect();
+        Self {
+            control_points,
+            knots_u: columns[0].knots().to_vec(),
+            ..self.clone()
+        }
+    }
+
+    pub fn insert_knot_v(&self, t: f32) -> Self {
+        let rows: Vec<BSpline<Vector4>> = (0..self.control_points.len())
+            .map(|i| self.row(i).insert_knot(t))
+            .collect();
+        Self {
+            knots_v: rows[0].knots().to_vec(),
+            control_points: rows
+                .iter()
+                .map(|row| row.control_points().to_vec())
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    // Grid of (segments_u + 1) x (segments_v + 1) vertices evenly spaced in parameter space,
+    // uvs are the parameters rescaled to [0, 1]
+    pub fn tessellate(&self, segments_u: usize, segments_v: usize) -> TriangleMesh {
+        let (segments_u, segments_v) = (segments_u.max(1), segments_v.max(1));
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let mut mesh = TriangleMesh::default();
+        for i in 0..=segments_u {
+            for j in 0..=segments_v {
+                let uv = Vector2::new(i as f32 / segments_u as f32, j as f32 / segments_v as f32);
+                let (u, v) = (u0 + (u1 - u0) * uv.x, v0 + (v1 - v0) * uv.y);
+                let (point, normal) = self.point_normal(u, v);
+                mesh.positions.push(point);
+                mesh.normals.push(normal);
+                mesh.uvs.push(uv);
+            }
+        }
+        let index = |i: usize, j: usize| (i * (segments_v + 1) + j) as u32;
+        for i in 0..segments_u {
+            for j in 0..segments_v {
+                let (a, b) = (index(i, j), index(i + 1, j));
+                let (c, d) = (index(i + 1, j + 1), index(i, j + 1));
+                mesh.indices.push([a, b, c]);
+                mesh.indices.push([a, c, d]);
+            }
+        }
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constants::EPSILON;
+
+    const CIRCLE_KNOTS: [f32; 12] = [0., 0., 0., 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1., 1., 1.];
+
+    // Unit circle in the xy plane at height z as nine weighted control points
+    fn circle_points(z: f32) -> Vec<Vector4> {
+        let w = 0.5_f32.sqrt();
+        [
+            (1., 0., 1.),
+            (1., 1., w),
+            (0., 1., 1.),
+            (-1., 1., w),
+            (-1., 0., 1.),
+            (-1., -1., w),
+            (0., -1., 1.),
+            (1., -1., w),
+            (1., 0., 1.),
+        ]
+        .map(|(x, y, w)| Vector4::new(x * w, y * w, z * w, w))
+        .to_vec()
+    }
+
+    fn circle() -> NurbsCurve {
+        NurbsCurve::new(2, circle_points(0.), CIRCLE_KNOTS.to_vec())
+    }
+
+    // Cylinder of radius 1 from z = 0 to z = 2
+    fn cylinder() -> NurbsSurface {
+        let control_points = circle_points(0.)
+            .into_iter()
+            .zip(circle_points(2.))
+            .map(|(bottom, top)| vec![bottom, top])
+            .collect();
+        NurbsSurface::new(
+            2,
+            1,
+            control_points,
+            CIRCLE_KNOTS.to_vec(),
+            vec![0., 0., 1., 1.],
+        )
+        .insert_knot_v(0.5)
+    }
+
+    // Function try_new()
+    #[test]
+    fn test1_nurbscurvetry_new() {
+        let mut points = circle_points(0.);
+        points[3].w = 0.;
+        assert!(NurbsCurve::try_new(2, points, CIRCLE_KNOTS.to_vec()).is_none());
+        assert!(NurbsCurve::try_new(2, circle_points(0.), CIRCLE_KNOTS[1..].to_vec()).is_none());
+        assert!(NurbsSurface::try_new(1, 1, vec![], vec![], vec![]).is_none());
+        let ragged = vec![circle_points(0.), circle_points(0.)[1..].to_vec()];
+        assert!(
+            NurbsSurface::try_new(1, 1, ragged, vec![0., 0., 1., 1.], CIRCLE_KNOTS.to_vec())
+                .is_none()
+        );
+        let mut knots = CIRCLE_KNOTS.to_vec();
+        knots[4] = f32::NAN;
+        assert!(NurbsCurve::try_new(2, circle_points(0.), knots.clone()).is_none());
+        let rows = vec![circle_points(0.), circle_points(1.)];
+        assert!(NurbsSurface::try_new(1, 2, rows, vec![0., 0., 1., 1.], knots).is_none());
+    }
+    // Method point(t)
+    #[test]
+    fn test1_nurbscurvepoint() {
+        let curve = circle();
+        for i in 0..=40 {
+            let point = curve.point(i as f32 / 40.);
+            assert!((point.len() - 1.).abs() < EPSILON);
+            assert_eq!(point.z, 0.);
+        }
+        assert_eq!(curve.point(0.25), Vector3::new(0., 1., 0.));
+        assert_eq!(
+            curve.point(0.625),
+            Vector3::new(-(0.5_f32.sqrt()), -(0.5_f32.sqrt()), 0.)
+        );
+    }
+    #[test]
+    fn test2_nurbscurvepoint() {
+        let curve = circle();
+        assert!(curve.point(f32::NAN).x.is_nan());
+        assert!(curve.derivative(f32::NAN).x.is_nan());
+    }
+    // Methods derivative(t), second_derivative(t)
+    #[test]
+    fn test1_nurbscurvederivative() {
+        let curve = circle();
+        let h = 1e-3;
+        for t in [0.1, 0.3, 0.6, 0.9] {
+            let numeric = (curve.point(t + h) - curve.point(t - h)) / (2. * h);
+            assert!((numeric - curve.derivative(t)).len() < 2e-2);
+            let numeric = (curve.derivative(t + h) - curve.derivative(t - h)) / (2. * h);
+            assert!((numeric - curve.second_derivative(t)).len() < 0.5);
+            // Velocity is tangent to the circle
+            assert!(curve.derivative(t).dot(curve.point(t)).abs() < 1e-2);
+        }
+    }
+    // Method insert_knot(t)
+    #[test]
+    fn test1_nurbscurveinsert_knot() {
+        let curve = circle();
+        let refined = curve.insert_knot(0.1).insert_knot(0.6);
+        assert_eq!(refined.control_points().len(), 11);
+        assert_eq!(refined.degree(), 2);
+        for t in [0., 0.1, 0.33, 0.6, 0.95, 1.] {
+            assert_eq!(refined.point(t), curve.point(t));
+        }
+    }
+    // Surface evaluation
+    #[test]
+    fn test1_nurbssurfacepoint() {
+        let surface = cylinder();
+        assert_eq!(surface.domain(), ((0., 1.), (0., 1.)));
+        assert_eq!(surface.knots_v(), &[0., 0., 0.5, 1., 1.]);
+        for (u, v) in [(0., 0.), (0.2, 0.5), (0.7, 0.9), (1., 1.)] {
+            let point = surface.point(u, v);
+            assert!((Vector2::new(point.x, point.y).len() - 1.).abs() < EPSILON);
+            assert!((point.z - 2. * v).abs() < EPSILON);
+            let normal = surface.normal(u, v);
+            assert_eq!(normal, Vector3::new(point.x, point.y, 0.));
+        }
+    }
+    #[test]
+    fn test2_nurbssurfacepoint() {
+        let surface = cylinder();
+        assert!(surface.point(0.5, f32::NAN).z.is_nan());
+        assert!(surface.point(f32::NAN, 0.5).x.is_nan());
+    }
+    #[test]
+    fn test1_nurbssurfacederivatives() {
+        let surface = cylinder().insert_knot_u(0.4);
+        assert_eq!(surface.control_points().len(), 10);
+        let h = 1e-3;
+        let (along_u, along_v) = surface.derivatives(0.3, 0.6);
+        let numeric = (surface.point(0.3 + h, 0.6) - surface.point(0.3 - h, 0.6)) / (2. * h);
+        assert!((numeric - along_u).len() < 2e-2);
+        assert_eq!(along_v, Vector3::new(0., 0., 2.));
+    }
+    #[test]
+    fn test2_nurbssurfacederivatives() {
+        // The span basis functions agree with evaluating the rows and then a column as splines
+        let surface = cylinder().insert_knot_u(0.4);
+        for (u, v) in [(0., 0.), (0.25, 0.5), (0.4, 0.3), (0.8, 0.75), (1., 1.)] {
+            let rows: Vec<BSpline<Vector4>> = (0..surface.control_points().len())
+                .map(|i| surface.row(i))
+                .collect();
+            let points = rows.iter().map(|row| row.point(v)).collect();
+            let derivatives = rows.iter().map(|row| row.derivative(v)).collect();
+            let column = surface.column(points);
+            let expected = (
+                column.point(u),
+                column.derivative(u),
+                surface.column(derivatives).point(u),
+            );
+            let (point, along_u, along_v) = surface.homogeneous(u, v);
+            assert!((point - expected.0).len() < 1e-5);
+            assert!((along_u - expected.1).len() < 1e-5);
+            assert!((along_v - expected.2).len() < 1e-5);
+        }
+    }
+    // Method tessellate()
+    #[test]
+    fn test1_nurbssurfacetessellate() {
+        let surface = cylinder();
+        let mesh = surface.tessellate(16, 4);
+        assert_eq!(mesh.positions.len(), 17 * 5);
+        assert_eq!(mesh.normals.len(), mesh.positions.len());
+        assert_eq!(mesh.uvs[5 * 3 + 2], Vector2::new(3. / 16., 0.5));
+        assert_eq!(mesh.indices.len(), 16 * 4 * 2);
+        for [a, b, c] in mesh.indices {
+            let [a, b, c] = [a, b, c].map(|i| mesh.positions[i as usize]);
+            let normal = (b - a).cross(c - a);
+            assert!(normal.dot(a) > 0.);
+        }
+    }
+}
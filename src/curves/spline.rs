@@ -0,0 +1,434 @@
+use super::Curve;
+use crate::{Interpolate, Vector};
+
+// Value or derivative of the cubic Hermite segment from p0 to p1 at u in [0, 1]
+fn hermite<V: Interpolate>(p0: V, m0: V, p1: V, m1: V, u: f32, order: u32) -> V {
+    let (u2, u3) = (u * u, u * u * u);
+    let [h00, h10, h01, h11] = match order {
+        0 => [
+            2. * u3 - 3. * u2 + 1.,
+            u3 - 2. * u2 + u,
+            -2. * u3 + 3. * u2,
+            u3 - u2,
+        ],
+        1 => [
+            6. * u2 - 6. * u,
+            3. * u2 - 4. * u + 1.,
+            -6. * u2 + 6. * u,
+            3. * u2 - 2. * u,
+        ],
+        _ => [12. * u - 6., 6. * u - 4., -12. * u + 6., 6. * u - 2.],
+    };
+    p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+}
+
+// Segment index and local parameter of t for a spline of unit length segments
+fn segment(t: f32, segments: usize) -> (usize, f32) {
+    let t = t.clamp(0., segments as f32);
+    let i = (t.floor() as usize).min(segments - 1);
+    (i, t - i as f32)
+}
+
+// Catmull-Rom spline through points[1..n - 1], the first and last points only shape the
+// ends. Knot spacing is |p(i+1) - p(i)|^alpha: 0 uniform, 0.5 centripetal, 1 chordal.
+// Segment i covers t in [i, i + 1]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<V> {
+    points: Vec<V>,
+    alpha: f32,
+}
+impl<V> CatmullRom<V>
+where
+    V: Vector + Interpolate,
+{
+    pub fn try_new(points: Vec<V>, alpha: f32) -> Option<Self> {
+        if points.len() < 4 {
+            return None;
+        }
+        Some(Self { points, alpha })
+    }
+
+    pub fn new(points: Vec<V>, alpha: f32) -> Self {
+        Self::try_new(points, alpha)
+            .expect("It is impossible to build a Catmull-Rom spline from less than 4 points")
+    }
+
+    pub fn uniform(points: Vec<V>) -> Self {
+        Self::new(points, 0.)
+    }
+
+    // Never forms cusps or self intersections within a segment
+    pub fn centripetal(points: Vec<V>) -> Self {
+        Self::new(points, 0.5)
+    }
+
+    pub fn chordal(points: Vec<V>) -> Self {
+        Self::new(points, 1.)
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    pub fn segments(&self) -> usize {
+        self.points.len() - 3
+    }
+
+    // Hermite form of the segment around t: start, start tangent, end, end tangent, u
+    fn hermite_segment(&self, t: f32) -> (V, V, V, V, f32) {
+        let (i, u) = segment(t, self.segments());
+        let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|k| self.points[i + k]);
+        let spacing = |a: V, b: V| {
+            let d = (b - a).len().powf(self.alpha);
+            if d <= f32::EPSILON {
+                1.
+            } else {
+                d
+            }
+        };
+        let (t01, t12, t23) = (spacing(p0, p1), spacing(p1, p2), spacing(p2, p3));
+        let m1 = p2 - p1 + ((p1 - p0) * (1. / t01) - (p2 - p0) * (1. / (t01 + t12))) * t12;
+        let m2 = p2 - p1 + ((p3 - p2) * (1. / t23) - (p3 - p1) * (1. / (t12 + t23))) * t12;
+        (p1, m1, p2, m2, u)
+    }
+}
+impl<V> Curve for CatmullRom<V>
+where
+    V: Vector + Interpolate,
+{
+    type Point = V;
+
+    fn domain(&self) -> (f32, f32) {
+        (0., self.segments() as f32)
+    }
+
+    fn point(&self, t: f32) -> V {
+        let (p1, m1, p2, m2, u) = self.hermite_segment(t);
+        hermite(p1, m1, p2, m2, u, 0)
+    }
+
+    fn derivative(&self, t: f32) -> V {
+        let (p1, m1, p2, m2, u) = self.hermite_segment(t);
+        hermite(p1, m1, p2, m2, u, 1)
+    }
+
+    fn second_derivative(&self, t: f32) -> V {
+        let (p1, m1, p2, m2, u) = self.hermite_segment(t);
+        hermite(p1, m1, p2, m2, u, 2)
+    }
+}
+
+// Piecewise cubic Hermite spline through points with explicit tangents,
+// segment i covers t in [i, i + 1]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicHermite<V> {
+    points: Vec<V>,
+    tangents: Vec<V>,
+}
+impl<V> CubicHermite<V>
+where
+    V: Vector + Interpolate,
+{
+    pub fn try_new(points: Vec<V>, tangents: Vec<V>) -> Option<Self> {
+        if points.len() < 2 || points.len() != tangents.len() {
+            return None;
+        }
+        Some(Self { points, tangents })
+    }
+
+    pub fn new(points: Vec<V>, tangents: Vec<V>) -> Self {
+        Self::try_new(points, tangents).expect(
+            "It is impossible to build a Hermite spline without a tangent for each of at least 2 points",
+        )
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn tangents(&self) -> &[V] {
+        &self.tangents
+    }
+
+    pub fn segments(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    fn evaluate(&self, t: f32, order: u32) -> V {
+        let (i, u) = segment(t, self.segments());
+        let (p0, m0) = (self.points[i], self.tangents[i]);
+        let (p1, m1) = (self.points[i + 1], self.tangents[i + 1]);
+        hermite(p0, m0, p1, m1, u, order)
+    }
+}
+impl<V> Curve for CubicHermite<V>
+where
+    V: Vector + Interpolate,
+{
+    type Point = V;
+
+    fn domain(&self) -> (f32, f32) {
+        (0., self.segments() as f32)
+    }
+
+    fn point(&self, t: f32) -> V {
+        self.evaluate(t, 0)
+    }
+
+    fn derivative(&self, t: f32) -> V {
+        self.evaluate(t, 1)
+    }
+
+    fn second_derivative(&self, t: f32) -> V {
+        self.evaluate(t, 2)
+    }
+}
+
+// C2 interpolating cubic spline with zero curvature at both ends,
+// point i is reached at t = i
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaturalCubicSpline<V> {
+    points: Vec<V>,
+    second_derivatives: Vec<V>,
+}
+impl<V> NaturalCubicSpline<V>
+where
+    V: Vector + Interpolate,
+{
+    pub fn try_new(points: Vec<V>) -> Option<Self> {
+        let n = points.len();
+        if n < 2 {
+            return None;
+        }
+        // Thomas algorithm on M(i - 1) + 4 M(i) + M(i + 1) = 6 (P(i + 1) - 2 P(i) + P(i - 1))
+        let mut upper = vec![0.; n];
+        let mut rhs = vec![V::zero(); n];
+        for i in 1..n - 1 {
+            let d = (points[i + 1] - points[i] - (points[i] - points[i - 1])) * 6.;
+            let pivot = 4. - upper[i - 1];
+            upper[i] = 1. / pivot;
+            rhs[i] = (d - rhs[i - 1]) * (1. / pivot);
+        }
+        let mut second_derivatives = vec![V::zero(); n];
+        for i in (1..n - 1).rev() {
+            second_derivatives[i] = rhs[i] - second_derivatives[i + 1] * upper[i];
+        }
+        Some(Self {
+            points,
+            second_derivatives,
+        })
+    }
+
+    pub fn new(points: Vec<V>) -> Self {
+        Self::try_new(points)
+            .expect("It is impossible to build a natural spline from less than 2 points")
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn segments(&self) -> usize {
+        self.points.len() - 1
+    }
+}
+impl<V> Curve for NaturalCubicSpline<V>
+where
+    V: Vector + Interpolate,
+{
+    type Point = V;
+
+    fn domain(&self) -> (f32, f32) {
+        (0., self.segments() as f32)
+    }
+
+    fn point(&self, t: f32) -> V {
+        let (i, u) = segment(t, self.segments());
+        let (m0, m1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+        let v = 1. - u;
+        self.points[i] * v
+            + self.points[i + 1] * u
+            + m0 * ((v * v * v - v) / 6.)
+            + m1 * ((u * u * u - u) / 6.)
+    }
+
+    fn derivative(&self, t: f32) -> V {
+        let (i, u) = segment(t, self.segments());
+        let (m0, m1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+        let v = 1. - u;
+        self.points[i + 1] - self.points[i] + m1 * ((3. * u * u - 1.) / 6.)
+            - m0 * ((3. * v * v - 1.) / 6.)
+    }
+
+    fn second_derivative(&self, t: f32) -> V {
+        let (i, u) = segment(t, self.segments());
+        self.second_derivatives[i].lerp(self.second_derivatives[i + 1], u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Vector2, Vector3};
+
+    fn path() -> Vec<Vector2> {
+        vec![
+            Vector2::new(0., 0.),
+            Vector2::new(1., 0.),
+            Vector2::new(1.5, 2.),
+            Vector2::new(4., 2.),
+            Vector2::new(5., 0.),
+        ]
+    }
+
+    fn check_derivatives<C: Curve<Point = Vector2>>(curve: &C, parameters: &[f32]) {
+        let h = 1e-3;
+        for &t in parameters {
+            let numeric = (curve.point(t + h) - curve.point(t - h)) / (2. * h);
+            assert!((numeric - curve.derivative(t)).len() < 2e-2);
+            let numeric = (curve.derivative(t + h) - curve.derivative(t - h)) / (2. * h);
+            assert!((numeric - curve.second_derivative(t)).len() < 5e-2);
+        }
+    }
+
+    // Function try_new()
+    #[test]
+    fn test1_catmullromtry_new() {
+        assert!(CatmullRom::try_new(path()[..3].to_vec(), 0.5).is_none());
+        assert!(CubicHermite::try_new(path(), path()[1..].to_vec()).is_none());
+        assert!(NaturalCubicSpline::<Vector2>::try_new(vec![]).is_none());
+    }
+    #[test]
+    #[should_panic]
+    fn test2_catmullromtry_new() {
+        CatmullRom::centripetal(vec![Vector2::zero(); 2]);
+    }
+    // Catmull-Rom
+    #[test]
+    fn test1_catmullrompoint() {
+        for curve in [
+            CatmullRom::uniform(path()),
+            CatmullRom::centripetal(path()),
+            CatmullRom::chordal(path()),
+        ] {
+            assert_eq!(curve.domain(), (0., 2.));
+            for i in 0..=2 {
+                assert_eq!(curve.point(i as f32), path()[i + 1]);
+            }
+            // Tangent direction is continuous across segment joins
+            assert_eq!(curve.tangent(1. - 1e-4), curve.tangent(1.));
+            check_derivatives(&curve, &[0.3, 1.5]);
+        }
+    }
+    #[test]
+    fn test1_catmullromderivative() {
+        // Uniform Catmull-Rom tangents are half the neighbour difference
+        let curve = CatmullRom::uniform(path());
+        assert_eq!(curve.derivative(1.), (path()[3] - path()[1]) / 2.);
+        assert_eq!(curve.derivative(0.), (path()[2] - path()[0]) / 2.);
+    }
+    #[test]
+    fn test2_catmullrompoint() {
+        // Coincident control points must not produce NaN
+        let points = vec![
+            Vector3::zero(),
+            Vector3::zero(),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(1., 0., 0.),
+        ];
+        let curve = CatmullRom::centripetal(points);
+        let point = curve.point(0.5);
+        assert!(point.x.is_finite());
+        assert_eq!(point, Vector3::new(0.5, 0., 0.));
+    }
+    // Cubic Hermite
+    #[test]
+    fn test1_cubichermitepoint() {
+        let points = vec![
+            Vector2::new(0., 0.),
+            Vector2::new(2., 1.),
+            Vector2::new(3., 3.),
+        ];
+        let tangents = vec![
+            Vector2::new(1., 0.),
+            Vector2::new(0., 2.),
+            Vector2::new(1., 1.),
+        ];
+        let curve = CubicHermite::new(points.clone(), tangents.clone());
+        for i in 0..3 {
+            assert_eq!(curve.point(i as f32), points[i]);
+            assert_eq!(curve.derivative(i as f32), tangents[i]);
+        }
+        assert_eq!(curve.point(-1.), points[0]);
+        assert_eq!(curve.point(5.), points[2]);
+        check_derivatives(&curve, &[0.25, 1.6]);
+    }
+    // Natural cubic spline
+    #[test]
+    fn test1_naturalcubicsplinepoint() {
+        let curve = NaturalCubicSpline::new(path());
+        for (i, point) in path().into_iter().enumerate() {
+            assert_eq!(curve.point(i as f32), point);
+        }
+        assert_eq!(curve.second_derivative(0.), Vector2::zero());
+        assert_eq!(curve.second_derivative(4.), Vector2::zero());
+        // C2 across segment joins
+        for i in 1..4 {
+            let t = i as f32;
+            assert_eq!(curve.derivative(t - 1e-4), curve.derivative(t));
+            assert_eq!(
+                curve.second_derivative(t - 1e-4),
+                curve.second_derivative(t)
+            );
+        }
+        check_derivatives(&curve, &[0.5, 2.2, 3.7]);
+    }
+    #[test]
+    fn test2_naturalcubicsplinepoint() {
+        // Collinear evenly spaced points give a straight line at constant speed
+        let points: Vec<Vector2> = (0..4)
+            .map(|i| Vector2::new(i as f32, 2. * i as f32))
+            .collect();
+        let curve = NaturalCubicSpline::new(points);
+        assert_eq!(curve.point(1.5), Vector2::new(1.5, 3.));
+        assert_eq!(curve.derivative(2.7), Vector2::new(1., 2.));
+    }
+    // Arc length reparameterization
+    #[test]
+    fn test1_catmullromby_arc_length() {
+        let curve = CatmullRom::centripetal(path()).by_arc_length(1024);
+        let length = curve.length();
+        assert_eq!(curve.domain(), (0., length));
+        for s in [0.1, 1., 2.5, length - 0.1] {
+            assert!((curve.derivative(s).len() - 1.).abs() < EPSILON);
+        }
+        let h = 1e-2;
+        let numeric = (curve.point(1. + h) - curve.point(1. - h)) / (2. * h);
+        assert_eq!(numeric, curve.derivative(1.));
+        let points = curve.sample(65);
+        let step = length / 64.;
+        for pair in points.windows(2) {
+            assert!(((pair[1] - pair[0]).len() - step).abs() < 1e-3);
+        }
+    }
+    #[test]
+    fn test1_arclengthcurvesecond_derivative() {
+        // Curvature of a circle of radius 2 is 1 / 2, pointing to the centre
+        let k = 0.552_284_8 * 2.;
+        let curve = crate::CubicBezier::new(
+            Vector2::new(2., 0.),
+            Vector2::new(2., k),
+            Vector2::new(k, 2.),
+            Vector2::new(0., 2.),
+        )
+        .by_arc_length(512);
+        let s = curve.length() / 2.;
+        let curvature = curve.second_derivative(s);
+        assert!((curvature.len() - 0.5).abs() < 1e-2);
+        assert!(curvature.dot(curve.point(s)) < 0.);
+    }
+}
@@ -0,0 +1,506 @@
+// Easing curves mapping normalized time t in [0, 1] to progress, f(0) = 0 and f(1) = 1.
+// Back and elastic overshoot outside [0, 1] on purpose
+use crate::functions::constants::PI;
+
+const BACK_OVERSHOOT: f32 = 1.70158;
+const BOUNCE_SCALE: f32 = 7.5625;
+const BOUNCE_WIDTH: f32 = 2.75;
+
+fn power_in(t: f32, power: i32) -> f32 {
+    t.powi(power)
+}
+fn power_out(t: f32, power: i32) -> f32 {
+    1. - (1. - t).powi(power)
+}
+fn power_in_out(t: f32, power: i32) -> f32 {
+    if t < 0.5 {
+        2_f32.powi(power - 1) * t.powi(power)
+    } else {
+        1. - (2. - 2. * t).powi(power) / 2.
+    }
+}
+
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+pub fn quad_in(t: f32) -> f32 {
+    power_in(t, 2)
+}
+pub fn quad_out(t: f32) -> f32 {
+    power_out(t, 2)
+}
+pub fn quad_in_out(t: f32) -> f32 {
+    power_in_out(t, 2)
+}
+
+pub fn cubic_in(t: f32) -> f32 {
+    power_in(t, 3)
+}
+pub fn cubic_out(t: f32) -> f32 {
+    power_out(t, 3)
+}
+pub fn cubic_in_out(t: f32) -> f32 {
+    power_in_out(t, 3)
+}
+
+pub fn quart_in(t: f32) -> f32 {
+    power_in(t, 4)
+}
+pub fn quart_out(t: f32) -> f32 {
+    power_out(t, 4)
+}
+pub fn quart_in_out(t: f32) -> f32 {
+    power_in_out(t, 4)
+}
+
+pub fn quint_in(t: f32) -> f32 {
+    power_in(t, 5)
+}
+pub fn quint_out(t: f32) -> f32 {
+    power_out(t, 5)
+}
+pub fn quint_in_out(t: f32) -> f32 {
+    power_in_out(t, 5)
+}
+
+pub fn sine_in(t: f32) -> f32 {
+    1. - (t * PI / 2.).cos()
+}
+pub fn sine_out(t: f32) -> f32 {
+    (t * PI / 2.).sin()
+}
+pub fn sine_in_out(t: f32) -> f32 {
+    (1. - (t * PI).cos()) / 2.
+}
+
+pub fn expo_in(t: f32) -> f32 {
+    if t <= 0. {
+        return 0.;
+    }
+    2_f32.powf(10. * t - 10.)
+}
+pub fn expo_out(t: f32) -> f32 {
+    if t >= 1. {
+        return 1.;
+    }
+    1. - 2_f32.powf(-10. * t)
+}
+pub fn expo_in_out(t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t.clamp(0., 1.);
+    }
+    if t < 0.5 {
+        2_f32.powf(20. * t - 10.) / 2.
+    } else {
+        (2. - 2_f32.powf(-20. * t + 10.)) / 2.
+    }
+}
+
+pub fn circ_in(t: f32) -> f32 {
+    1. - (1. - t * t).max(0.).sqrt()
+}
+pub fn circ_out(t: f32) -> f32 {
+    (1. - (t - 1.) * (t - 1.)).max(0.).sqrt()
+}
+pub fn circ_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        circ_in(2. * t) / 2.
+    } else {
+        (circ_out(2. * t - 1.) + 1.) / 2.
+    }
+}
+
+pub fn back_in(t: f32) -> f32 {
+    (BACK_OVERSHOOT + 1.) * t * t * t - BACK_OVERSHOOT * t * t
+}
+pub fn back_out(t: f32) -> f32 {
+    1. - back_in(1. - t)
+}
+pub fn back_in_out(t: f32) -> f32 {
+    let overshoot = BACK_OVERSHOOT * 1.525;
+    if t < 0.5 {
+        (2. * t).powi(2) * ((overshoot + 1.) * 2. * t - overshoot) / 2.
+    } else {
+        ((2. * t - 2.).powi(2) * ((overshoot + 1.) * (2. * t - 2.) + overshoot) + 2.) / 2.
+    }
+}
+
+pub fn elastic_in(t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t.clamp(0., 1.);
+    }
+    -(2_f32.powf(10. * t - 10.)) * ((10. * t - 10.75) * 2. * PI / 3.).sin()
+}
+pub fn elastic_out(t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t.clamp(0., 1.);
+    }
+    2_f32.powf(-10. * t) * ((10. * t - 0.75) * 2. * PI / 3.).sin() + 1.
+}
+pub fn elastic_in_out(t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t.clamp(0., 1.);
+    }
+    let sin = ((20. * t - 11.125) * 2. * PI / 4.5).sin();
+    if t < 0.5 {
+        -(2_f32.powf(20. * t - 10.) * sin) / 2.
+    } else {
+        2_f32.powf(-20. * t + 10.) * sin / 2. + 1.
+    }
+}
+
+pub fn bounce_out(t: f32) -> f32 {
+    if t < 1. / BOUNCE_WIDTH {
+        BOUNCE_SCALE * t * t
+    } else if t < 2. / BOUNCE_WIDTH {
+        let t = t - 1.5 / BOUNCE_WIDTH;
+        BOUNCE_SCALE * t * t + 0.75
+    } else if t < 2.5 / BOUNCE_WIDTH {
+        let t = t - 2.25 / BOUNCE_WIDTH;
+        BOUNCE_SCALE * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / BOUNCE_WIDTH;
+        BOUNCE_SCALE * t * t + 0.984375
+    }
+}
+pub fn bounce_in(t: f32) -> f32 {
+    1. - bounce_out(1. - t)
+}
+pub fn bounce_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        (1. - bounce_out(1. - 2. * t)) / 2.
+    } else {
+        (1. + bounce_out(2. * t - 1.)) / 2.
+    }
+}
+
+// CSS cubic-bezier(x1, y1, x2, y2) timing function, the curve runs from (0, 0) to (1, 1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezierTiming {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+impl CubicBezierTiming {
+    pub const EASE: Self = Self::new(0.25, 0.1, 0.25, 1.);
+    pub const EASE_IN: Self = Self::new(0.42, 0., 1., 1.);
+    pub const EASE_OUT: Self = Self::new(0., 0., 0.58, 1.);
+    pub const EASE_IN_OUT: Self = Self::new(0.42, 0., 0.58, 1.);
+
+    // Like CSS, x1 and x2 have to lie in [0, 1] so that the curve is a function of time
+    pub const fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self {
+            x1: x1.clamp(0., 1.),
+            y1,
+            x2: x2.clamp(0., 1.),
+            y2,
+        }
+    }
+
+    fn bezier(s: f32, p1: f32, p2: f32) -> f32 {
+        let u = 1. - s;
+        3. * u * u * s * p1 + 3. * u * s * s * p2 + s * s * s
+    }
+
+    fn bezier_derivative(s: f32, p1: f32, p2: f32) -> f32 {
+        let u = 1. - s;
+        3. * u * u * p1 + 6. * u * s * (p2 - p1) + 3. * s * s * (1. - p2)
+    }
+
+    // Curve parameter s with x(s) = x: Newton steps, bisection if they stall
+    fn solve_x(&self, x: f32) -> f32 {
+        let mut s = x;
+        for _ in 0..8 {
+            let error = Self::bezier(s, self.x1, self.x2) - x;
+            if error.abs() < 1e-6 {
+                return s;
+            }
+            let derivative = Self::bezier_derivative(s, self.x1, self.x2);
+            if derivative.abs() < 1e-6 {
+                break;
+            }
+            s -= error / derivative;
+        }
+        let (mut low, mut high) = (0_f32, 1_f32);
+        s = x;
+        for _ in 0..32 {
+            let value = Self::bezier(s, self.x1, self.x2);
+            if (value - x).abs() < 1e-6 {
+                break;
+            }
+            if value < x {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.;
+        }
+        s
+    }
+
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        Self::bezier(self.solve_x(t), self.y1, self.y2)
+    }
+}
+
+// Where the jumps of a CSS steps() timing function happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepPosition {
+    JumpStart,
+    JumpEnd,
+    JumpNone,
+    JumpBoth,
+}
+
+// CSS steps(count, position) timing function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Steps {
+    pub count: u32,
+    pub position: StepPosition,
+}
+impl Steps {
+    pub fn new(count: u32, position: StepPosition) -> Self {
+        Self { count, position }
+    }
+
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        let count = self.count.max(1) as f32;
+        let mut step = (t * count).floor();
+        if matches!(
+            self.position,
+            StepPosition::JumpStart | StepPosition::JumpBoth
+        ) {
+            step += 1.;
+        }
+        let jumps = match self.position {
+            StepPosition::JumpStart | StepPosition::JumpEnd => count,
+            StepPosition::JumpNone => (count - 1.).max(1.),
+            StepPosition::JumpBoth => count + 1.,
+        };
+        step.min(jumps) / jumps
+    }
+}
+
+// Any of the above as a value, e.g. for curves chosen in data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    CubicBezier(CubicBezierTiming),
+    Steps(Steps),
+}
+impl Easing {
+    pub fn ease(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => linear(t),
+            Easing::QuadIn => quad_in(t),
+            Easing::QuadOut => quad_out(t),
+            Easing::QuadInOut => quad_in_out(t),
+            Easing::CubicIn => cubic_in(t),
+            Easing::CubicOut => cubic_out(t),
+            Easing::CubicInOut => cubic_in_out(t),
+            Easing::QuartIn => quart_in(t),
+            Easing::QuartOut => quart_out(t),
+            Easing::QuartInOut => quart_in_out(t),
+            Easing::QuintIn => quint_in(t),
+            Easing::QuintOut => quint_out(t),
+            Easing::QuintInOut => quint_in_out(t),
+            Easing::SineIn => sine_in(t),
+            Easing::SineOut => sine_out(t),
+            Easing::SineInOut => sine_in_out(t),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => expo_out(t),
+            Easing::ExpoInOut => expo_in_out(t),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => circ_out(t),
+            Easing::CircInOut => circ_in_out(t),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => back_out(t),
+            Easing::BackInOut => back_in_out(t),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => elastic_out(t),
+            Easing::ElasticInOut => elastic_in_out(t),
+            Easing::BounceIn => bounce_in(t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => bounce_in_out(t),
+            Easing::CubicBezier(timing) => timing.ease(t),
+            Easing::Steps(steps) => steps.ease(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constants::EPSILON;
+
+    const ALL: [Easing; 31] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::QuintIn,
+        Easing::QuintOut,
+        Easing::QuintInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    // Endpoints and symmetry
+    #[test]
+    fn test1_easingease() {
+        for easing in ALL {
+            assert!(easing.ease(0.).abs() < EPSILON, "{easing:?}");
+            assert!((easing.ease(1.) - 1.).abs() < EPSILON, "{easing:?}");
+        }
+    }
+    #[test]
+    fn test2_easingease() {
+        // Every in-out curve passes through the middle and out(t) = 1 - in(1 - t)
+        for chunk in ALL[1..].chunks(3) {
+            let (ease_in, ease_out, ease_in_out) = (chunk[0], chunk[1], chunk[2]);
+            assert!(
+                (ease_in_out.ease(0.5) - 0.5).abs() < EPSILON,
+                "{ease_in_out:?}"
+            );
+            for i in 0..=10 {
+                let t = i as f32 / 10.;
+                let mirrored = 1. - ease_in.ease(1. - t);
+                assert!(
+                    (ease_out.ease(t) - mirrored).abs() < EPSILON,
+                    "{ease_out:?}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn test1_quad_in() {
+        assert_eq!(quad_in(0.5), 0.25);
+        assert_eq!(quad_out(0.5), 0.75);
+        assert_eq!(cubic_in_out(0.25), 0.0625);
+        assert_eq!(quint_in(0.5), 0.03125);
+    }
+    #[test]
+    fn test1_back_in() {
+        assert!(back_in(0.2) < 0.);
+        assert!(back_out(0.8) > 1.);
+        assert!(elastic_out(0.2) > 1.);
+    }
+    #[test]
+    fn test1_bounce_out() {
+        assert_eq!(bounce_out(1. / 2.75), 1.);
+        assert_eq!(bounce_out(1.5 / 2.75), 0.75);
+        assert!((0..=100).all(|i| (0. ..=1.).contains(&bounce_out(i as f32 / 100.))));
+    }
+    // CubicBezierTiming
+    #[test]
+    fn test1_cubicbeziertimingease() {
+        let linear = CubicBezierTiming::new(0., 0., 1., 1.);
+        for i in 0..=10 {
+            let t = i as f32 / 10.;
+            assert!((linear.ease(t) - t).abs() < 1e-4);
+        }
+        assert_eq!(CubicBezierTiming::EASE.ease(0.), 0.);
+        assert_eq!(CubicBezierTiming::EASE.ease(1.), 1.);
+    }
+    #[test]
+    fn test2_cubicbeziertimingease() {
+        // Reference values of the CSS keywords
+        assert!((CubicBezierTiming::EASE.ease(0.5) - 0.8024).abs() < 1e-3);
+        assert!((CubicBezierTiming::EASE_IN.ease(0.5) - 0.3153).abs() < 1e-3);
+        assert!((CubicBezierTiming::EASE_OUT.ease(0.5) - 0.6847).abs() < 1e-3);
+        assert!((CubicBezierTiming::EASE_IN_OUT.ease(0.5) - 0.5).abs() < 1e-3);
+    }
+    #[test]
+    fn test3_cubicbeziertimingease() {
+        let overshoot = CubicBezierTiming::new(0.3, -0.5, 0.7, 1.5);
+        assert!(overshoot.ease(0.1) < 0.);
+        assert!(overshoot.ease(0.9) > 1.);
+        let steep = CubicBezierTiming::new(1., 0., 0., 1.);
+        let values: Vec<f32> = (0..=20).map(|i| steep.ease(i as f32 / 20.)).collect();
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1] + 1e-5));
+        assert_eq!(
+            CubicBezierTiming::new(-1., 0., 2., 1.),
+            CubicBezierTiming::new(0., 0., 1., 1.)
+        );
+    }
+    // Steps
+    #[test]
+    fn test1_stepsease() {
+        let steps = Steps::new(4, StepPosition::JumpEnd);
+        assert_eq!(steps.ease(0.), 0.);
+        assert_eq!(steps.ease(0.3), 0.25);
+        assert_eq!(steps.ease(0.99), 0.75);
+        assert_eq!(steps.ease(1.), 1.);
+        let steps = Steps::new(4, StepPosition::JumpStart);
+        assert_eq!(steps.ease(0.), 0.25);
+        assert_eq!(steps.ease(0.8), 1.);
+    }
+    #[test]
+    fn test2_stepsease() {
+        let steps = Steps::new(3, StepPosition::JumpNone);
+        assert_eq!(steps.ease(0.), 0.);
+        assert_eq!(steps.ease(0.5), 0.5);
+        assert_eq!(steps.ease(1.), 1.);
+        let steps = Steps::new(3, StepPosition::JumpBoth);
+        assert_eq!(steps.ease(0.), 0.25);
+        assert_eq!(steps.ease(0.5), 0.5);
+        assert_eq!(steps.ease(1.), 1.);
+        assert_eq!(Easing::Steps(steps).ease(0.7), 0.75);
+    }
+}
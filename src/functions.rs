@@ -18,3 +18,257 @@ pub fn unpack<const SIZE: usize>(arr: [[f32; SIZE]; SIZE]) -> Vec<f32> {
 pub fn radians_from_degree(degree: f32) -> f32 {
     degree * constants::DEGREE
 }
+
+pub fn degrees_from_radians(radians: f32) -> f32 {
+    radians / constants::DEGREE
+}
+
+// Angle in (-pi, pi]
+pub fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + constants::PI).rem_euclid(2. * constants::PI) - constants::PI;
+    if wrapped == -constants::PI {
+        constants::PI
+    } else {
+        wrapped
+    }
+}
+
+// Signed shortest rotation from one angle to the other, in (-pi, pi]
+pub fn angle_difference(from: f32, to: f32) -> f32 {
+    wrap_angle(to - from)
+}
+
+// Fractional part, always in [0, 1) unlike f32::fract for negative values
+pub fn fract(value: f32) -> f32 {
+    value - value.floor()
+}
+
+// -1, 0 or 1, f32::signum gives 1 for zero
+pub fn sign(value: f32) -> f32 {
+    if value > 0. {
+        1.
+    } else if value < 0. {
+        -1.
+    } else {
+        0.
+    }
+}
+
+// 0 below edge, 1 from edge on, like GLSL step
+pub fn step(edge: f32, value: f32) -> f32 {
+    if value < edge {
+        0.
+    } else {
+        1.
+    }
+}
+
+pub fn clamp01(value: f32) -> f32 {
+    value.clamp(0., 1.)
+}
+
+pub fn approx_zero(value: f32) -> bool {
+    value.abs() < constants::EPSILON
+}
+
+// Bit trick estimate refined by one Newton step, relative error below 0.2%
+pub fn fast_inv_sqrt(value: f32) -> f32 {
+    let estimate = f32::from_bits(0x5f37_59df - (value.to_bits() >> 1));
+    estimate * (1.5 - 0.5 * value * estimate * estimate)
+}
+
+// a + (b - a) t with a single rounding of the product
+pub fn fma_lerp(a: f32, b: f32, t: f32) -> f32 {
+    (b - a).mul_add(t, a)
+}
+
+// a b - c d without the cancellation of the naive form (Kahan)
+pub fn difference_of_products(a: f32, b: f32, c: f32, d: f32) -> f32 {
+    let cd = c * d;
+    let error = (-c).mul_add(d, cd);
+    a.mul_add(b, -cd) + error
+}
+
+// a b + c d without the cancellation of the naive form (Kahan)
+pub fn sum_of_products(a: f32, b: f32, c: f32, d: f32) -> f32 {
+    let cd = c * d;
+    let error = c.mul_add(d, -cd);
+    a.mul_add(b, cd) + error
+}
+
+// Bits of 2 / pi after the binary point, enough to reduce the largest f32
+const TWO_OVER_PI_BITS: [u32; 8] = [
+    0xA2F9836E, 0x4E441529, 0xFC2757D1, 0xF534DDC0, 0xDB629599, 0x3C439041, 0xFE5163AB, 0xDEBBC561,
+];
+
+// The 96 bits of 2 / pi ending at position last, bit k weighs 2^-k
+fn two_over_pi_window(last: usize) -> u128 {
+    (0..96).fold(0, |window, i| {
+        // Position 0 is the integer part, which is zero
+        let bit = match (last + i + 1).saturating_sub(96) {
+            0 => 0,
+            k => (TWO_OVER_PI_BITS[(k - 1) / 32] >> (31 - (k - 1) % 32)) & 1,
+        };
+        window << 1 | u128::from(bit)
+    })
+}
+
+// angle = quadrant * pi / 2 + x with |x| <= pi / 4, quadrant taken mod 4. Below 2^23 f64 is
+// exact enough, above every f32 is an integer m 2^e and only the bits of 2 / pi that land
+// near the binary point of m 2^e 2 / pi matter (Payne-Hanek)
+fn reduce_half_pi(angle: f32) -> (u32, f32) {
+    if angle.abs() < 8_388_608. {
+        let quadrant = (f64::from(angle) * std::f64::consts::FRAC_2_PI).round();
+        let x = (f64::from(angle) - quadrant * std::f64::consts::FRAC_PI_2) as f32;
+        return ((quadrant as i64).rem_euclid(4) as u32, x);
+    }
+    let bits = angle.abs().to_bits();
+    let mantissa = u128::from(bits & 0x7f_ffff | 0x80_0000);
+    let exponent = (bits >> 23) as usize - 150;
+    // m 2^e 2 / pi mod 4 in fixed point with 94 fractional bits, lower bits of 2 / pi add
+    // less than 2^-70
+    let product = (mantissa * two_over_pi_window(exponent + 94)) & ((1 << 96) - 1);
+    let quadrant = (product >> 94) as u32;
+    let fraction = (product & ((1 << 94) - 1)) as f64 / 2_f64.powi(94);
+    let (quadrant, fraction) = if fraction >= 0.5 {
+        ((quadrant + 1) % 4, fraction - 1.)
+    } else {
+        (quadrant, fraction)
+    };
+    let x = (fraction * std::f64::consts::FRAC_PI_2) as f32;
+    if angle < 0. {
+        ((4 - quadrant) % 4, -x)
+    } else {
+        (quadrant, x)
+    }
+}
+
+// Sine and cosine from basic arithmetic only, bit identical on every platform unlike the
+// libm backed f32::sin_cos. Exact range reduction and Cephes minimax polynomials
+pub fn sin_cos(angle: f32) -> (f32, f32) {
+    if !angle.is_finite() {
+        return (f32::NAN, f32::NAN);
+    }
+    let (quadrant, x) = reduce_half_pi(angle);
+    let x2 = x * x;
+    let sin = x + x * x2 * (-1.666_665_5e-1 + x2 * (8.332_161e-3 + x2 * -1.951_529_6e-4));
+    let cos =
+        1. - 0.5 * x2 + x2 * x2 * (4.166_664_6e-2 + x2 * (-1.388_731_6e-3 + x2 * 2.443_315_7e-5));
+    match quadrant {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{constants::PI, *};
+
+    // Functions radians_from_degree(), degrees_from_radians()
+    #[test]
+    fn test1_degrees_from_radians() {
+        assert!((degrees_from_radians(PI) - 180.).abs() < 1e-4);
+        assert!((degrees_from_radians(radians_from_degree(37.5)) - 37.5).abs() < 1e-4);
+    }
+    // Functions wrap_angle(), angle_difference()
+    #[test]
+    fn test1_wrap_angle() {
+        assert!((wrap_angle(3. * PI / 2.) + PI / 2.).abs() < 1e-5);
+        assert!((wrap_angle(-7. * PI / 2.) - PI / 2.).abs() < 1e-5);
+        assert_eq!(wrap_angle(PI), PI);
+        assert_eq!(wrap_angle(-PI), PI);
+        assert_eq!(wrap_angle(0.5), 0.5);
+    }
+    #[test]
+    fn test1_angle_difference() {
+        // Across the seam the short way round
+        let from = radians_from_degree(170.);
+        let to = radians_from_degree(-170.);
+        assert!((angle_difference(from, to) - radians_from_degree(20.)).abs() < 1e-5);
+        assert!((angle_difference(to, from) + radians_from_degree(20.)).abs() < 1e-5);
+        assert_eq!(angle_difference(1., 1.), 0.);
+    }
+    // Functions fract(), sign(), step(), clamp01(), approx_zero()
+    #[test]
+    fn test1_fract() {
+        assert_eq!(fract(2.25), 0.25);
+        assert_eq!(fract(-0.25), 0.75);
+        assert_eq!(fract(3.), 0.);
+    }
+    #[test]
+    fn test1_sign() {
+        assert_eq!(sign(-3.), -1.);
+        assert_eq!(sign(0.), 0.);
+        assert_eq!(sign(-0.), 0.);
+        assert_eq!(sign(1e-30), 1.);
+    }
+    #[test]
+    fn test1_step() {
+        assert_eq!(step(1., 0.999), 0.);
+        assert_eq!(step(1., 1.), 1.);
+        assert_eq!(clamp01(-2.), 0.);
+        assert_eq!(clamp01(0.3), 0.3);
+        assert_eq!(clamp01(4.), 1.);
+        assert!(approx_zero(1e-4));
+        assert!(!approx_zero(-0.01));
+    }
+    // Function fast_inv_sqrt()
+    #[test]
+    fn test1_fast_inv_sqrt() {
+        for value in [1e-6_f32, 0.01, 1., 2., 16., 12345.] {
+            let exact = 1. / value.sqrt();
+            assert!(((fast_inv_sqrt(value) - exact) / exact).abs() < 2e-3);
+        }
+    }
+    // Functions fma_lerp(), difference_of_products(), sum_of_products()
+    #[test]
+    fn test1_fma_lerp() {
+        assert_eq!(fma_lerp(2., 6., 0.25), 3.);
+        assert_eq!(fma_lerp(2., 6., 1.), 6.);
+        assert_eq!(fma_lerp(-1., 1., 0.5), 0.);
+    }
+    #[test]
+    fn test1_difference_of_products() {
+        // The naive form cancels to zero, the exact value is -2^-46
+        let (a, b) = (1. + f32::EPSILON, 1. - f32::EPSILON);
+        assert_eq!(a * b - 1., 0.);
+        assert_eq!(
+            difference_of_products(a, b, 1., 1.),
+            -f32::EPSILON * f32::EPSILON
+        );
+        assert_eq!(difference_of_products(3., 4., 2., 5.), 2.);
+        assert_eq!(sum_of_products(a, b, -1., 1.), -f32::EPSILON * f32::EPSILON);
+        assert_eq!(sum_of_products(3., 4., 2., 5.), 22.);
+    }
+    // Function sin_cos()
+    #[test]
+    fn test1_sin_cos() {
+        for i in -2000..=2000 {
+            let angle = i as f32 * 0.0137;
+            let (sin, cos) = sin_cos(angle);
+            assert!((sin - angle.sin()).abs() < 1e-6);
+            assert!((cos - angle.cos()).abs() < 1e-6);
+        }
+        assert_eq!(sin_cos(0.), (0., 1.));
+        let (sin, cos) = sin_cos(PI / 2.);
+        assert!((sin - 1.).abs() < 1e-7 && cos.abs() < 1e-7);
+        assert!(sin_cos(f32::INFINITY).0.is_nan());
+    }
+    #[test]
+    fn test2_sin_cos() {
+        // Huge angles are reduced exactly, compared against the correctly reduced f64 functions
+        let mut angles = vec![1e7, 8_388_608., 1e9, 1e15, 1e20, 1e30, 3e38, f32::MAX];
+        let mut seed = 12345_u32;
+        for _ in 0..2000 {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            angles.push(f32::from_bits((seed >> 1) & 0x7f7f_ffff));
+        }
+        for angle in angles.iter().flat_map(|&angle| [angle, -angle]) {
+            let (sin, cos) = sin_cos(angle);
+            assert!((f64::from(sin) - f64::from(angle).sin()).abs() < 1e-6);
+            assert!((f64::from(cos) - f64::from(angle).cos()).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{Vector, Vector3};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Aabb3 {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+impl Aabb3 {
+    pub fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    // Inverted box that acts as the identity for union()
+    pub fn empty() -> Self {
+        Self::new(
+            Vector3::from(f32::INFINITY),
+            Vector3::from(f32::NEG_INFINITY),
+        )
+    }
+
+    pub fn from_corners(a: Vector3, b: Vector3) -> Self {
+        Self::new(a.min(b), a.max(b))
+    }
+
+    pub fn from_center_half_size(center: Vector3, half_size: Vector3) -> Self {
+        Self::from_corners(center - half_size, center + half_size)
+    }
+
+    pub fn from_points(points: &[Vector3]) -> Self {
+        points
+            .iter()
+            .fold(Self::empty(), |aabb, point| aabb.expand_to(*point))
+    }
+
+    pub fn size(self) -> Vector3 {
+        self.max - self.min
+    }
+
+    pub fn half_size(self) -> Vector3 {
+        self.size() * 0.5
+    }
+
+    pub fn center(self) -> Vector3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn surface_area(self) -> f32 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let Vector3 { x, y, z } = self.size();
+        2. * (x * y + y * z + z * x)
+    }
+
+    pub fn volume(self) -> f32 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let Vector3 { x, y, z } = self.size();
+        x * y * z
+    }
+
+    // Only inverted boxes are empty, a flat box still contains its face
+    pub fn is_empty(self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn contains(self, point: Vector3) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+            && self.min.z <= point.z
+            && point.z <= self.max.z
+    }
+
+    pub fn contains_aabb(self, aabb: Aabb3) -> bool {
+        self.contains(aabb.min) && self.contains(aabb.max)
+    }
+
+    pub fn intersects(self, aabb: Aabb3) -> bool {
+        self.min.x <= aabb.max.x
+            && aabb.min.x <= self.max.x
+            && self.min.y <= aabb.max.y
+            && aabb.min.y <= self.max.y
+            && self.min.z <= aabb.max.z
+            && aabb.min.z <= self.max.z
+    }
+
+    pub fn intersects_sphere(self, center: Vector3, radius: f32) -> bool {
+        self.distance_squared_to(center) <= radius * radius
+    }
+
+    pub fn union(self, aabb: Aabb3) -> Aabb3 {
+        Self::new(self.min.min(aabb.min), self.max.max(aabb.max))
+    }
+
+    pub fn expand_to(self, point: Vector3) -> Aabb3 {
+        Self::new(self.min.min(point), self.max.max(point))
+    }
+
+    pub fn outset(self, amount: Vector3) -> Aabb3 {
+        Self::new(self.min - amount, self.max + amount)
+    }
+
+    pub fn closest_point(self, point: Vector3) -> Vector3 {
+        point.max(self.min).min(self.max)
+    }
+
+    pub fn distance_squared_to(self, point: Vector3) -> f32 {
+        let offset = point - self.closest_point(point);
+        offset.dot(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Constructors
+    #[test]
+    fn test1_aabb3from_corners() {
+        let aabb = Aabb3::from_corners(Vector3::new(1., -2., 3.), Vector3::new(-1., 2., 0.));
+        assert_eq!(aabb.min, Vector3::new(-1., -2., 0.));
+        assert_eq!(aabb.max, Vector3::new(1., 2., 3.));
+    }
+    #[test]
+    fn test1_aabb3from_points() {
+        let aabb = Aabb3::from_points(&[
+            Vector3::new(1., 0., 0.),
+            Vector3::new(-1., 4., 2.),
+            Vector3::new(0., -3., 1.),
+        ]);
+        assert_eq!(
+            aabb,
+            Aabb3::new(Vector3::new(-1., -3., 0.), Vector3::new(1., 4., 2.))
+        );
+    }
+    #[test]
+    fn test2_aabb3from_points() {
+        assert!(Aabb3::from_points(&[]).is_empty());
+    }
+    // Method is_empty()
+    #[test]
+    fn test1_aabb3is_empty() {
+        assert!(Aabb3::empty().is_empty());
+        // Zero extent is degenerate but not empty, like Rect
+        let point = Aabb3::from_points(&[Vector3::from(1.)]);
+        assert!(!point.is_empty());
+        assert!(point.contains(Vector3::from(1.)));
+        let flat = Aabb3::new(Vector3::zero(), Vector3::new(1., 2., 0.));
+        assert!(!flat.is_empty());
+        assert_eq!(flat.volume(), 0.);
+        assert_eq!(flat.surface_area(), 4.);
+    }
+    // Getters
+    #[test]
+    fn test1_aabb3surface_area() {
+        let aabb = Aabb3::new(Vector3::zero(), Vector3::new(1., 2., 3.));
+        assert_eq!(aabb.surface_area(), 22.);
+        assert_eq!(aabb.volume(), 6.);
+        assert_eq!(aabb.center(), Vector3::new(0.5, 1., 1.5));
+    }
+    #[test]
+    fn test2_aabb3surface_area() {
+        assert_eq!(Aabb3::empty().surface_area(), 0.);
+        assert_eq!(Aabb3::empty().volume(), 0.);
+    }
+    // Method contains(point)
+    #[test]
+    fn test1_aabb3contains() {
+        let aabb = Aabb3::new(Vector3::zero(), Vector3::from(1.));
+        assert!(aabb.contains(Vector3::from(0.5)));
+        assert!(aabb.contains(Vector3::from(1.)));
+        assert!(!aabb.contains(Vector3::new(0.5, 1.5, 0.5)));
+    }
+    // Method intersects(aabb)
+    #[test]
+    fn test1_aabb3intersects() {
+        let aabb1 = Aabb3::new(Vector3::zero(), Vector3::from(2.));
+        let aabb2 = Aabb3::new(Vector3::from(1.), Vector3::from(3.));
+        let aabb3 = Aabb3::new(Vector3::new(2.5, 0., 0.), Vector3::from(3.));
+        assert!(aabb1.intersects(aabb2));
+        assert!(aabb2.intersects(aabb1));
+        assert!(!aabb1.intersects(aabb3));
+    }
+    #[test]
+    fn test1_aabb3intersects_sphere() {
+        let aabb = Aabb3::new(Vector3::zero(), Vector3::from(1.));
+        assert!(aabb.intersects_sphere(Vector3::new(2., 0.5, 0.5), 1.));
+        assert!(!aabb.intersects_sphere(Vector3::new(2., 2., 2.), 1.));
+    }
+    // Method union(aabb)
+    #[test]
+    fn test1_aabb3union() {
+        let aabb1 = Aabb3::new(Vector3::zero(), Vector3::from(1.));
+        let aabb2 = Aabb3::new(Vector3::new(2., -1., 0.), Vector3::new(3., 0., 1.));
+        let union = Aabb3::new(Vector3::new(0., -1., 0.), Vector3::new(3., 1., 1.));
+        assert_eq!(aabb1.union(aabb2), union);
+        assert_eq!(Aabb3::empty().union(aabb1), aabb1);
+    }
+    // Method distance_squared_to(point)
+    #[test]
+    fn test1_aabb3distance_squared_to() {
+        let aabb = Aabb3::new(Vector3::zero(), Vector3::from(1.));
+        assert_eq!(aabb.distance_squared_to(Vector3::from(0.5)), 0.);
+        assert_eq!(aabb.distance_squared_to(Vector3::new(3., 1., 1.)), 4.);
+        assert_eq!(aabb.distance_squared_to(Vector3::new(2., 2., 0.5)), 2.);
+    }
+}
@@ -0,0 +1,121 @@
+use super::aabb::Aabb3;
+use crate::{Matrix, Matrix4, Vector, Vector3, Vector4};
+
+// Planes are stored as (a, b, c, d) with a*x + b*y + c*z + d >= 0 on the inner side
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    pub planes: [Vector4; 6],
+}
+impl Frustum {
+    pub fn new(planes: [Vector4; 6]) -> Self {
+        Self { planes }
+    }
+
+    // Gribb-Hartmann extraction from a projection * view matrix acting on column vectors,
+    // with clip space -w <= x, y, z <= w as produced by perspective3 and ortho3
+    pub fn from_matrix(matrix: Matrix4) -> Self {
+        let row = |i: usize| {
+            let [x, y, z, w] = matrix.get_row(i);
+            Vector4::new(x, y, z, w)
+        };
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+        let planes = [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r3 + r2, r3 - r2].map(|plane| {
+            let normal_len = Vector3::new(plane.x, plane.y, plane.z).len();
+            plane / normal_len
+        });
+        Self::new(planes)
+    }
+
+    pub fn contains(self, point: Vector3) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| signed_distance(*plane, point) >= 0.)
+    }
+
+    pub fn intersects_sphere(self, center: Vector3, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| signed_distance(*plane, center) >= -radius)
+    }
+
+    // Conservative test: may report boxes near frustum corners as intersecting
+    pub fn intersects_aabb(self, aabb: Aabb3) -> bool {
+        self.planes.iter().all(|plane| {
+            let positive_vertex = Vector3::new(
+                if plane.x >= 0. {
+                    aabb.max.x
+                } else {
+                    aabb.min.x
+                },
+                if plane.y >= 0. {
+                    aabb.max.y
+                } else {
+                    aabb.min.y
+                },
+                if plane.z >= 0. {
+                    aabb.max.z
+                } else {
+                    aabb.min.z
+                },
+            );
+            signed_distance(*plane, positive_vertex) >= 0.
+        })
+    }
+}
+
+fn signed_distance(plane: Vector4, point: Vector3) -> f32 {
+    plane.x * point.x + plane.y * point.y + plane.z * point.z + plane.w
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constants::PI;
+    use crate::transform::homogeneous::{lookat3, ortho3, perspective3};
+
+    // Constructors
+    #[test]
+    fn test1_frustumfrom_matrix() {
+        let frustum = Frustum::from_matrix(ortho3(-1., 1., -1., 1., 1., 10.));
+        assert!(frustum.contains(Vector3::new(0., 0., -5.)));
+        assert!(frustum.contains(Vector3::new(1., -1., -1.)));
+        assert!(!frustum.contains(Vector3::new(0., 0., -11.)));
+        assert!(!frustum.contains(Vector3::new(1.5, 0., -5.)));
+        assert!(!frustum.contains(Vector3::new(0., 0., 0.)));
+    }
+    #[test]
+    fn test2_frustumfrom_matrix() {
+        let projection = perspective3(100., 0.1, 1., PI / 2.);
+        let view = lookat3(
+            Vector3::zero(),
+            Vector3::new(0., 0., -1.),
+            Vector3::new(0., 1., 0.),
+        );
+        let frustum = Frustum::from_matrix(projection * view);
+        assert!(frustum.contains(Vector3::new(0., 0., -5.)));
+        assert!(frustum.contains(Vector3::new(4.9, 0., -5.)));
+        assert!(!frustum.contains(Vector3::new(5.1, 0., -5.)));
+        assert!(!frustum.contains(Vector3::new(0., 0., 5.)));
+        assert!(!frustum.contains(Vector3::new(0., 0., -101.)));
+    }
+    // Method intersects_sphere(center, radius)
+    #[test]
+    fn test1_frustumintersects_sphere() {
+        let frustum = Frustum::from_matrix(ortho3(-1., 1., -1., 1., 1., 10.));
+        assert!(frustum.intersects_sphere(Vector3::new(1.5, 0., -5.), 1.));
+        assert!(!frustum.intersects_sphere(Vector3::new(3., 0., -5.), 1.));
+    }
+    // Method intersects_aabb(aabb)
+    #[test]
+    fn test1_frustumintersects_aabb() {
+        let frustum = Frustum::from_matrix(ortho3(-1., 1., -1., 1., 1., 10.));
+        let inside = Aabb3::new(Vector3::new(-0.5, -0.5, -3.), Vector3::new(0.5, 0.5, -2.));
+        let straddling = Aabb3::new(Vector3::new(0.5, -0.5, -3.), Vector3::new(2., 0.5, -2.));
+        let outside = Aabb3::new(Vector3::new(2., -0.5, -3.), Vector3::new(3., 0.5, -2.));
+        let behind = Aabb3::new(Vector3::new(-0.5, -0.5, 1.), Vector3::new(0.5, 0.5, 2.));
+        assert!(frustum.intersects_aabb(inside));
+        assert!(frustum.intersects_aabb(straddling));
+        assert!(!frustum.intersects_aabb(outside));
+        assert!(!frustum.intersects_aabb(behind));
+    }
+}
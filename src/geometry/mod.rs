@@ -0,0 +1,4 @@
+pub mod aabb;
+pub mod frustum;
+pub mod ray;
+pub mod rect;
@@ -0,0 +1,72 @@
+use super::aabb::Aabb3;
+use crate::Vector3;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Ray3 {
+    pub origin: Vector3,
+    pub direction: Vector3,
+}
+impl Ray3 {
+    pub fn new(origin: Vector3, direction: Vector3) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn at(self, t: f32) -> Vector3 {
+        self.origin + self.direction * t
+    }
+
+    // Slab test, returns the parametric interval of the ray inside the box
+    pub fn intersect_aabb(self, aabb: Aabb3, max_t: f32) -> Option<(f32, f32)> {
+        let (mut t_min, mut t_max) = (0_f32, max_t);
+        for axis in 0..3 {
+            let inv = 1. / self.direction[axis];
+            let t1 = (aabb.min[axis] - self.origin[axis]) * inv;
+            let t2 = (aabb.max[axis] - self.origin[axis]) * inv;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+        if t_min > t_max {
+            return None;
+        }
+        Some((t_min, t_max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector;
+
+    // Method at(t)
+    #[test]
+    fn test1_ray3at() {
+        let ray = Ray3::new(Vector3::new(1., 0., 0.), Vector3::new(0., 2., 0.));
+        assert_eq!(ray.at(1.5), Vector3::new(1., 3., 0.));
+    }
+    // Method intersect_aabb(aabb, max_t)
+    #[test]
+    fn test1_ray3intersect_aabb() {
+        let aabb = Aabb3::new(Vector3::from(1.), Vector3::from(2.));
+        let ray = Ray3::new(Vector3::zero(), Vector3::from(1.));
+        assert_eq!(ray.intersect_aabb(aabb, f32::INFINITY), Some((1., 2.)));
+    }
+    #[test]
+    fn test2_ray3intersect_aabb() {
+        let aabb = Aabb3::new(Vector3::from(1.), Vector3::from(2.));
+        let ray = Ray3::new(Vector3::new(1.5, 1.5, 0.), Vector3::new(0., 0., 1.));
+        assert_eq!(ray.intersect_aabb(aabb, f32::INFINITY), Some((1., 2.)));
+        assert_eq!(ray.intersect_aabb(aabb, 0.5), None);
+    }
+    #[test]
+    fn test3_ray3intersect_aabb() {
+        let aabb = Aabb3::new(Vector3::from(1.), Vector3::from(2.));
+        let ray = Ray3::new(Vector3::zero(), Vector3::new(-1., 1., 1.));
+        assert_eq!(ray.intersect_aabb(aabb, f32::INFINITY), None);
+    }
+    #[test]
+    fn test4_ray3intersect_aabb() {
+        let aabb = Aabb3::new(Vector3::zero(), Vector3::from(2.));
+        let ray = Ray3::new(Vector3::from(1.), Vector3::new(1., 0., 0.));
+        assert_eq!(ray.intersect_aabb(aabb, f32::INFINITY), Some((0., 1.)));
+    }
+}
@@ -0,0 +1,384 @@
+use crate::{
+    transform::homogeneous::{scale2, translate2},
+    Matrix3, Vector, Vector2,
+};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Vector2,
+    pub max: Vector2,
+}
+impl Rect {
+    pub fn new(min: Vector2, max: Vector2) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_corners(a: Vector2, b: Vector2) -> Self {
+        Self::new(
+            Vector2::new(a.x.min(b.x), a.y.min(b.y)),
+            Vector2::new(a.x.max(b.x), a.y.max(b.y)),
+        )
+    }
+
+    pub fn from_origin_size(origin: Vector2, size: Vector2) -> Self {
+        Self::from_corners(origin, origin + size)
+    }
+
+    pub fn from_center_half_size(center: Vector2, half_size: Vector2) -> Self {
+        Self::from_corners(center - half_size, center + half_size)
+    }
+
+    pub fn width(self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn size(self) -> Vector2 {
+        self.max - self.min
+    }
+
+    pub fn half_size(self) -> Vector2 {
+        self.size() * 0.5
+    }
+
+    pub fn center(self) -> Vector2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn area(self) -> f32 {
+        self.width().max(0.) * self.height().max(0.)
+    }
+
+    pub fn aspect_ratio(self) -> f32 {
+        self.width() / self.height()
+    }
+
+    // Only inverted rects are empty, a zero width or height rect still contains its edge
+    pub fn is_empty(self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+
+    pub fn contains(self, point: Vector2) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+
+    pub fn contains_rect(self, rect: Rect) -> bool {
+        self.contains(rect.min) && self.contains(rect.max)
+    }
+
+    pub fn intersects(self, rect: Rect) -> bool {
+        self.min.x <= rect.max.x
+            && rect.min.x <= self.max.x
+            && self.min.y <= rect.max.y
+            && rect.min.y <= self.max.y
+    }
+
+    pub fn intersection(self, rect: Rect) -> Option<Rect> {
+        if !self.intersects(rect) {
+            return None;
+        }
+        Some(Self::new(
+            Vector2::new(self.min.x.max(rect.min.x), self.min.y.max(rect.min.y)),
+            Vector2::new(self.max.x.min(rect.max.x), self.max.y.min(rect.max.y)),
+        ))
+    }
+
+    pub fn intersects_circle(self, center: Vector2, radius: f32) -> bool {
+        self.distance_squared_to(center) <= radius * radius
+    }
+
+    pub fn closest_point(self, point: Vector2) -> Vector2 {
+        point.max(self.min).min(self.max)
+    }
+
+    pub fn distance_squared_to(self, point: Vector2) -> f32 {
+        let offset = point - self.closest_point(point);
+        offset.dot(offset)
+    }
+
+    pub fn union(self, rect: Rect) -> Rect {
+        Self::new(
+            Vector2::new(self.min.x.min(rect.min.x), self.min.y.min(rect.min.y)),
+            Vector2::new(self.max.x.max(rect.max.x), self.max.y.max(rect.max.y)),
+        )
+    }
+
+    pub fn expand_to(self, point: Vector2) -> Rect {
+        self.union(Self::new(point, point))
+    }
+
+    // Shrinks every edge towards the center by `amount`, collapsing onto the center
+    // instead of turning inside out
+    pub fn inset(self, amount: Vector2) -> Rect {
+        let center = self.center();
+        let half_size = self.half_size() - amount;
+        Self::from_center_half_size(
+            center,
+            Vector2::new(half_size.x.max(0.), half_size.y.max(0.)),
+        )
+    }
+
+    pub fn outset(self, amount: Vector2) -> Rect {
+        Self::new(self.min - amount, self.max + amount)
+    }
+
+    pub fn split_x(self, x: f32) -> (Rect, Rect) {
+        let x = x.clamp(self.min.x, self.max.x);
+        (
+            Self::new(self.min, Vector2::new(x, self.max.y)),
+            Self::new(Vector2::new(x, self.min.y), self.max),
+        )
+    }
+
+    pub fn split_y(self, y: f32) -> (Rect, Rect) {
+        let y = y.clamp(self.min.y, self.max.y);
+        (
+            Self::new(self.min, Vector2::new(self.max.x, y)),
+            Self::new(Vector2::new(self.min.x, y), self.max),
+        )
+    }
+
+    // Largest rect with the given width / height ratio centered inside `self`
+    pub fn aspect_fit(self, aspect_ratio: f32) -> Rect {
+        let (width, height) = (self.width(), self.height());
+        let size = if width > height * aspect_ratio {
+            Vector2::new(height * aspect_ratio, height)
+        } else {
+            Vector2::new(width, width / aspect_ratio)
+        };
+        Self::from_center_half_size(self.center(), size * 0.5)
+    }
+
+    // Smallest rect with the given width / height ratio centered on `self` and covering it
+    pub fn aspect_fill(self, aspect_ratio: f32) -> Rect {
+        let (width, height) = (self.width(), self.height());
+        let size = if width > height * aspect_ratio {
+            Vector2::new(width, width / aspect_ratio)
+        } else {
+            Vector2::new(height * aspect_ratio, height)
+        };
+        Self::from_center_half_size(self.center(), size * 0.5)
+    }
+
+    pub fn try_transform_to(self, target: Rect) -> Option<Matrix3> {
+        if self.width() <= 0. || self.height() <= 0. {
+            return None;
+        }
+        let scale = Vector2::new(
+            target.width() / self.width(),
+            target.height() / self.height(),
+        );
+        Some(translate2(target.min) * scale2(scale) * translate2(-self.min))
+    }
+
+    pub fn transform_to(self, target: Rect) -> Matrix3 {
+        self.try_transform_to(target)
+            .expect("It is impossible to map a rect without area onto another rect")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector3;
+
+    // Constructors
+    #[test]
+    fn test1_rectfrom_corners() {
+        let rect = Rect::from_corners(Vector2::new(4., -1.), Vector2::new(-2., 3.));
+        assert_eq!(rect.min, Vector2::new(-2., -1.));
+        assert_eq!(rect.max, Vector2::new(4., 3.));
+    }
+    #[test]
+    fn test1_rectfrom_origin_size() {
+        let rect = Rect::from_origin_size(Vector2::new(1., 2.), Vector2::new(3., 4.));
+        assert_eq!(rect, Rect::new(Vector2::new(1., 2.), Vector2::new(4., 6.)));
+    }
+    #[test]
+    fn test2_rectfrom_origin_size() {
+        let rect = Rect::from_origin_size(Vector2::new(1., 2.), Vector2::new(-3., 4.));
+        assert_eq!(rect, Rect::new(Vector2::new(-2., 2.), Vector2::new(1., 6.)));
+    }
+    #[test]
+    fn test1_rectfrom_center_half_size() {
+        let rect = Rect::from_center_half_size(Vector2::new(1., 1.), Vector2::new(2., 0.5));
+        assert_eq!(
+            rect,
+            Rect::new(Vector2::new(-1., 0.5), Vector2::new(3., 1.5))
+        );
+        assert_eq!(rect.center(), Vector2::new(1., 1.));
+        assert_eq!(rect.half_size(), Vector2::new(2., 0.5));
+    }
+    // Getters
+    #[test]
+    fn test1_rectsize() {
+        let rect = Rect::new(Vector2::new(-1., 2.), Vector2::new(3., 8.));
+        assert_eq!(rect.size(), Vector2::new(4., 6.));
+        assert_eq!(rect.area(), 24.);
+        assert_eq!(rect.aspect_ratio(), 4. / 6.);
+    }
+    #[test]
+    fn test1_rectis_empty() {
+        assert!(Rect::new(Vector2::from(1.), Vector2::zero()).is_empty());
+        assert!(!Rect::new(Vector2::zero(), Vector2::from(1.)).is_empty());
+    }
+    #[test]
+    fn test2_rectis_empty() {
+        // Zero extent is degenerate but not empty, like Aabb3
+        assert!(!Rect::default().is_empty());
+        let line = Rect::new(Vector2::zero(), Vector2::new(2., 0.));
+        assert!(!line.is_empty());
+        assert!(line.contains(Vector2::new(1., 0.)));
+        assert_eq!(line.area(), 0.);
+        assert!(line.try_transform_to(Rect::default()).is_none());
+    }
+    // Method contains(point)
+    #[test]
+    fn test1_rectcontains() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(2., 1.));
+        assert!(rect.contains(Vector2::new(1., 0.5)));
+        assert!(rect.contains(Vector2::new(2., 1.)));
+        assert!(!rect.contains(Vector2::new(2.1, 0.5)));
+        assert!(!rect.contains(Vector2::new(1., -0.1)));
+    }
+    #[test]
+    fn test1_rectcontains_rect() {
+        let rect = Rect::new(Vector2::zero(), Vector2::from(4.));
+        assert!(rect.contains_rect(Rect::new(Vector2::from(1.), Vector2::from(2.))));
+        assert!(!rect.contains_rect(Rect::new(Vector2::from(1.), Vector2::from(5.))));
+    }
+    // Method intersects(rect)
+    #[test]
+    fn test1_rectintersects() {
+        let rect1 = Rect::new(Vector2::zero(), Vector2::from(2.));
+        let rect2 = Rect::new(Vector2::from(1.), Vector2::from(3.));
+        assert!(rect1.intersects(rect2));
+        assert!(rect2.intersects(rect1));
+        assert_eq!(
+            rect1.intersection(rect2),
+            Some(Rect::new(Vector2::from(1.), Vector2::from(2.)))
+        );
+    }
+    #[test]
+    fn test2_rectintersects() {
+        let rect1 = Rect::new(Vector2::zero(), Vector2::from(1.));
+        let rect2 = Rect::new(Vector2::new(1.5, 0.), Vector2::new(2., 1.));
+        assert!(!rect1.intersects(rect2));
+        assert_eq!(rect1.intersection(rect2), None);
+    }
+    #[test]
+    fn test1_rectintersects_circle() {
+        let rect = Rect::new(Vector2::zero(), Vector2::from(1.));
+        assert!(rect.intersects_circle(Vector2::new(2., 0.5), 1.));
+        assert!(!rect.intersects_circle(Vector2::new(2., 2.), 1.));
+    }
+    // Method distance_squared_to(point)
+    #[test]
+    fn test1_rectdistance_squared_to() {
+        let rect = Rect::new(Vector2::zero(), Vector2::from(1.));
+        assert_eq!(rect.distance_squared_to(Vector2::from(0.5)), 0.);
+        assert_eq!(rect.distance_squared_to(Vector2::new(3., 1.)), 4.);
+        assert_eq!(rect.distance_squared_to(Vector2::new(-1., 2.)), 2.);
+    }
+    // Method union(rect)
+    #[test]
+    fn test1_rectunion() {
+        let rect1 = Rect::new(Vector2::zero(), Vector2::from(1.));
+        let rect2 = Rect::new(Vector2::new(3., -1.), Vector2::new(4., 0.5));
+        let union = Rect::new(Vector2::new(0., -1.), Vector2::new(4., 1.));
+        assert_eq!(rect1.union(rect2), union);
+        assert_eq!(rect2.union(rect1), union);
+    }
+    #[test]
+    fn test1_rectexpand_to() {
+        let rect = Rect::new(Vector2::zero(), Vector2::from(1.)).expand_to(Vector2::new(-1., 3.));
+        assert_eq!(rect, Rect::new(Vector2::new(-1., 0.), Vector2::new(1., 3.)));
+    }
+    // Methods inset(amount), outset(amount)
+    #[test]
+    fn test1_rectinset() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(10., 4.));
+        let inset = rect.inset(Vector2::from(1.));
+        assert_eq!(inset, Rect::new(Vector2::from(1.), Vector2::new(9., 3.)));
+        assert_eq!(inset.outset(Vector2::from(1.)), rect);
+    }
+    #[test]
+    fn test2_rectinset() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(10., 4.));
+        let inset = rect.inset(Vector2::from(3.));
+        assert_eq!(inset, Rect::new(Vector2::new(3., 2.), Vector2::new(7., 2.)));
+    }
+    // Methods split_x(x), split_y(y)
+    #[test]
+    fn test1_rectsplit_x() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(10., 4.));
+        let (left, right) = rect.split_x(3.);
+        assert_eq!(left, Rect::new(Vector2::zero(), Vector2::new(3., 4.)));
+        assert_eq!(
+            right,
+            Rect::new(Vector2::new(3., 0.), Vector2::new(10., 4.))
+        );
+    }
+    #[test]
+    fn test2_rectsplit_x() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(10., 4.));
+        let (left, right) = rect.split_x(12.);
+        assert_eq!(left, rect);
+        assert_eq!(right.width(), 0.);
+    }
+    #[test]
+    fn test1_rectsplit_y() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(10., 4.));
+        let (bottom, top) = rect.split_y(1.);
+        assert_eq!(bottom, Rect::new(Vector2::zero(), Vector2::new(10., 1.)));
+        assert_eq!(top, Rect::new(Vector2::new(0., 1.), Vector2::new(10., 4.)));
+    }
+    // Methods aspect_fit(aspect_ratio), aspect_fill(aspect_ratio)
+    #[test]
+    fn test1_rectaspect_fit() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(16., 10.));
+        let fit = rect.aspect_fit(1.);
+        assert_eq!(fit, Rect::new(Vector2::new(3., 0.), Vector2::new(13., 10.)));
+        assert!(rect.contains_rect(fit));
+    }
+    #[test]
+    fn test2_rectaspect_fit() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(10., 16.));
+        let fit = rect.aspect_fit(2.);
+        assert_eq!(
+            fit,
+            Rect::new(Vector2::new(0., 5.5), Vector2::new(10., 10.5))
+        );
+    }
+    #[test]
+    fn test1_rectaspect_fill() {
+        let rect = Rect::new(Vector2::zero(), Vector2::new(16., 10.));
+        let fill = rect.aspect_fill(1.);
+        assert_eq!(
+            fill,
+            Rect::new(Vector2::new(0., -3.), Vector2::new(16., 13.))
+        );
+        assert!(fill.contains_rect(rect));
+    }
+    // Method transform_to(rect)
+    #[test]
+    fn test1_recttransform_to() {
+        let from = Rect::new(Vector2::new(-1., -1.), Vector2::new(1., 1.));
+        let to = Rect::new(Vector2::new(0., 0.), Vector2::new(800., 600.));
+        let mat = from.transform_to(to);
+        assert_eq!(mat * Vector3::new(-1., -1., 1.), Vector3::new(0., 0., 1.));
+        assert_eq!(mat * Vector3::new(1., 1., 1.), Vector3::new(800., 600., 1.));
+        assert_eq!(mat * Vector3::new(0., 0., 1.), Vector3::new(400., 300., 1.));
+    }
+    #[test]
+    #[should_panic]
+    fn test2_recttransform_to() {
+        Rect::default().transform_to(Rect::new(Vector2::zero(), Vector2::from(1.)));
+    }
+}
@@ -0,0 +1,381 @@
+use crate::{Interpolate, Matrix3, Vector3};
+use std::ops::{Add, Mul, Sub};
+
+const SAFETY: f32 = 0.9;
+const MIN_SCALE: f32 = 0.2;
+const MAX_SCALE: f32 = 5.;
+const MIN_STEP_FRACTION: f32 = 1e-6;
+const MAX_STEPS: usize = 1 << 20;
+
+// Any Interpolate type is a state, derivative(t, state) returns its time derivative.
+// Second order systems use PhaseState to carry position and velocity together
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PhaseState<V> {
+    pub position: V,
+    pub velocity: V,
+}
+impl<V> PhaseState<V> {
+    pub fn new(position: V, velocity: V) -> Self {
+        Self { position, velocity }
+    }
+}
+impl<V: Interpolate> Add for PhaseState<V> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.position + rhs.position, self.velocity + rhs.velocity)
+    }
+}
+impl<V: Interpolate> Sub for PhaseState<V> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.position - rhs.position, self.velocity - rhs.velocity)
+    }
+}
+impl<V: Interpolate> Mul<f32> for PhaseState<V> {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.position * rhs, self.velocity * rhs)
+    }
+}
+impl<V: Interpolate> Interpolate for PhaseState<V> {
+    fn inner(self, other: Self) -> f32 {
+        self.position.inner(other.position) + self.velocity.inner(other.velocity)
+    }
+}
+
+pub fn explicit_euler<S, F>(state: S, t: f32, dt: f32, derivative: F) -> S
+where
+    S: Interpolate,
+    F: Fn(f32, S) -> S,
+{
+    state + derivative(t, state) * dt
+}
+
+// Symplectic Euler, velocity first and the position moves with the new velocity.
+// Keeps the energy of oscillators bounded where explicit Euler gains it
+pub fn semi_implicit_euler<V, F>(state: PhaseState<V>, dt: f32, acceleration: F) -> PhaseState<V>
+where
+    V: Interpolate,
+    F: Fn(V, V) -> V,
+{
+    let velocity = state.velocity + acceleration(state.position, state.velocity) * dt;
+    PhaseState::new(state.position + velocity * dt, velocity)
+}
+
+// Second order and time reversible, acceleration may only depend on the position
+pub fn velocity_verlet<V, F>(state: PhaseState<V>, dt: f32, acceleration: F) -> PhaseState<V>
+where
+    V: Interpolate,
+    F: Fn(V) -> V,
+{
+    let start = acceleration(state.position);
+    let position = state.position + state.velocity * dt + start * (dt * dt / 2.);
+    let end = acceleration(position);
+    PhaseState::new(position, state.velocity + (start + end) * (dt / 2.))
+}
+
+// Classic fourth order Runge-Kutta
+pub fn rk4<S, F>(state: S, t: f32, dt: f32, derivative: F) -> S
+where
+    S: Interpolate,
+    F: Fn(f32, S) -> S,
+{
+    let half = dt / 2.;
+    let k1 = derivative(t, state);
+    let k2 = derivative(t + half, state + k1 * half);
+    let k3 = derivative(t + half, state + k2 * half);
+    let k4 = derivative(t + dt, state + k3 * dt);
+    state + (k1 + (k2 + k3) * 2. + k4) * (dt / 6.)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveStep<S> {
+    pub state: S,
+    // Estimated local error of state, the step should be retried with next_dt when it
+    // exceeds the tolerance
+    pub error: f32,
+    pub accepted: bool,
+    pub next_dt: f32,
+}
+
+// One Dormand-Prince 5(4) step, state is the fifth order solution
+pub fn rk45_step<S, F>(state: S, t: f32, dt: f32, tolerance: f32, derivative: F) -> AdaptiveStep<S>
+where
+    S: Interpolate,
+    F: Fn(f32, S) -> S,
+{
+    let k1 = derivative(t, state);
+    let k2 = derivative(t + dt / 5., state + k1 * (dt / 5.));
+    let k3 = derivative(
+        t + dt * 3. / 10.,
+        state + (k1 * (3. / 40.) + k2 * (9. / 40.)) * dt,
+    );
+    let k4 = derivative(
+        t + dt * 4. / 5.,
+        state + (k1 * (44. / 45.) + k2 * (-56. / 15.) + k3 * (32. / 9.)) * dt,
+    );
+    let k5 = derivative(
+        t + dt * 8. / 9.,
+        state
+            + (k1 * (19372. / 6561.)
+                + k2 * (-25360. / 2187.)
+                + k3 * (64448. / 6561.)
+                + k4 * (-212. / 729.))
+                * dt,
+    );
+    let k6 = derivative(
+        t + dt,
+        state
+            + (k1 * (9017. / 3168.)
+                + k2 * (-355. / 33.)
+                + k3 * (46732. / 5247.)
+                + k4 * (49. / 176.)
+                + k5 * (-5103. / 18656.))
+                * dt,
+    );
+    let next = state
+        + (k1 * (35. / 384.)
+            + k3 * (500. / 1113.)
+            + k4 * (125. / 192.)
+            + k5 * (-2187. / 6784.)
+            + k6 * (11. / 84.))
+            * dt;
+    let k7 = derivative(t + dt, next);
+    // Fifth minus fourth order weights
+    let difference = (k1 * (71. / 57600.)
+        + k3 * (-71. / 16695.)
+        + k4 * (71. / 1920.)
+        + k5 * (-17253. / 339200.)
+        + k6 * (22. / 525.)
+        + k7 * (-1. / 40.))
+        * dt;
+    let error = difference.inner(difference).sqrt();
+    // A NaN error never passes the tolerance, shrink instead of growing the step
+    let scale = if error.is_nan() {
+        MIN_SCALE
+    } else if error > 0. {
+        (SAFETY * (tolerance / error).powf(0.2)).clamp(MIN_SCALE, MAX_SCALE)
+    } else {
+        MAX_SCALE
+    };
+    AdaptiveStep {
+        state: next,
+        error,
+        accepted: error <= tolerance,
+        next_dt: dt * scale,
+    }
+}
+
+// Integrates from t0 to t1 with adaptive Dormand-Prince steps starting at dt,
+// tolerance bounds the local error of each step. Returns the state and the time it was
+// reached, which falls short of t1 when a step has a non-finite error or after MAX_STEPS
+pub fn rk45<S, F>(state: S, t0: f32, t1: f32, dt: f32, tolerance: f32, derivative: F) -> (S, f32)
+where
+    S: Interpolate,
+    F: Fn(f32, S) -> S,
+{
+    let min_dt = (t1 - t0).abs() * MIN_STEP_FRACTION;
+    let mut dt = dt.abs().max(min_dt).copysign(t1 - t0);
+    let (mut state, mut t) = (state, t0);
+    let mut steps = 0;
+    while (t1 - t) * dt.signum() > 0. && steps < MAX_STEPS {
+        // Anything under one ulp of t would leave t where it is
+        let min_step = min_dt.max(t.abs() * f32::EPSILON);
+        let dt_step = dt.abs().max(min_step).copysign(dt);
+        // Land exactly on t1
+        let dt_step = if (t1 - t).abs() < dt_step.abs() {
+            t1 - t
+        } else {
+            dt_step
+        };
+        // Step by what t can represent, so the state and t stay in sync
+        let dt_step = (t + dt_step) - t;
+        let step = rk45_step(state, t, dt_step, tolerance, &derivative);
+        if !step.error.is_finite() {
+            break;
+        }
+        if step.accepted || dt_step.abs() <= min_step {
+            state = step.state;
+            t += dt_step;
+        }
+        dt = step.next_dt.abs().max(min_dt).copysign(dt);
+        steps += 1;
+    }
+    (state, t)
+}
+
+// Rotation after turning with a world space angular velocity for dt, exact for constant
+// angular velocity and re-orthonormalized against drift
+pub fn integrate_rotation(rotation: Matrix3, angular_velocity: Vector3, dt: f32) -> Matrix3 {
+    (Matrix3::from_rotation_vector(angular_velocity * dt) * rotation).orthonormalize()
+}
+
+// Same with the angular velocity given in the rotating body's own frame
+pub fn integrate_rotation_local(rotation: Matrix3, angular_velocity: Vector3, dt: f32) -> Matrix3 {
+    (rotation * Matrix3::from_rotation_vector(angular_velocity * dt)).orthonormalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        functions::constants::{EPSILON, PI},
+        transform::rotate3z,
+        Matrix, Vector, Vector2,
+    };
+
+    fn decay(_t: f32, state: f32) -> f32 {
+        -state
+    }
+
+    // Unit mass on a unit spring
+    fn spring(position: Vector2) -> Vector2 {
+        -position
+    }
+
+    fn energy(state: PhaseState<Vector2>) -> f32 {
+        (state.position.dot(state.position) + state.velocity.dot(state.velocity)) / 2.
+    }
+
+    fn run<F: Fn(PhaseState<Vector2>) -> PhaseState<Vector2>>(
+        steps: usize,
+        step: F,
+    ) -> PhaseState<Vector2> {
+        let mut state = PhaseState::new(Vector2::new(1., 0.), Vector2::new(0., 1.));
+        for _ in 0..steps {
+            state = step(state);
+        }
+        state
+    }
+
+    fn rk4_error(dt: f32) -> f32 {
+        let steps = (1. / dt).round() as usize;
+        let mut state = 1.;
+        for i in 0..steps {
+            state = rk4(state, i as f32 * dt, dt, decay);
+        }
+        (state - (-1_f32).exp()).abs()
+    }
+
+    // Function explicit_euler()
+    #[test]
+    fn test1_explicit_euler() {
+        assert_eq!(explicit_euler(2., 0., 0.1, decay), 1.8);
+        // Explicit Euler gains energy on an oscillator
+        let state = run(1000, |state| {
+            explicit_euler(state, 0., 0.01, |_, s: PhaseState<Vector2>| {
+                PhaseState::new(s.velocity, spring(s.position))
+            })
+        });
+        assert!(energy(state) > 0.55);
+    }
+    // Function semi_implicit_euler()
+    #[test]
+    fn test1_semi_implicit_euler() {
+        let state = run(10000, |state| {
+            semi_implicit_euler(state, 0.01, |x, _| spring(x))
+        });
+        assert!((energy(state) - 1.).abs() < 0.01);
+        // Damping through the velocity argument
+        let state = run(10000, |state| {
+            semi_implicit_euler(state, 0.01, |x, v| spring(x) - v * 0.5)
+        });
+        assert!(energy(state) < EPSILON);
+    }
+    // Function velocity_verlet()
+    #[test]
+    fn test1_velocity_verlet() {
+        // A full period of the circular orbit comes back to the start
+        let steps = 1000;
+        let dt = 2. * PI / steps as f32;
+        let state = run(steps, |state| velocity_verlet(state, dt, spring));
+        assert_eq!(state.position, Vector2::new(1., 0.));
+        assert_eq!(state.velocity, Vector2::new(0., 1.));
+        let state = run(100000, |state| velocity_verlet(state, 0.05, spring));
+        assert!((energy(state) - 1.).abs() < 0.01);
+    }
+    // Function rk4()
+    #[test]
+    fn test1_rk4() {
+        assert!(rk4_error(0.1) < 1e-5);
+        // Fourth order, halving dt divides the error by about 16
+        let ratio = rk4_error(0.5) / rk4_error(0.25);
+        assert!(ratio > 14. && ratio < 22.);
+        let state = run(1000, |state| {
+            rk4(state, 0., 2. * PI / 1000., |_, s: PhaseState<Vector2>| {
+                PhaseState::new(s.velocity, spring(s.position))
+            })
+        });
+        assert_eq!(state.position, Vector2::new(1., 0.));
+    }
+    // Functions rk45_step(), rk45()
+    #[test]
+    fn test1_rk45_step() {
+        let step = rk45_step(1., 0., 0.1, 1e-6, decay);
+        assert!(step.accepted);
+        assert!((step.state - (-0.1_f32).exp()).abs() < 1e-6);
+        assert!(step.next_dt > 0.1);
+        let step = rk45_step(1., 0., 3., 1e-6, decay);
+        assert!(!step.accepted);
+        assert!(step.next_dt < 3.);
+    }
+    #[test]
+    fn test1_rk45() {
+        let (state, t) = rk45(1., 0., 2., 0.01, 1e-6, decay);
+        assert!((state - (-2_f32).exp()).abs() < 1e-5);
+        assert_eq!(t, 2.);
+        // Backwards in time
+        let (state, t) = rk45(1., 0., -1., 0.5, 1e-6, decay);
+        assert!((state - 1_f32.exp()).abs() < 1e-4);
+        assert_eq!(t, -1.);
+        let start = PhaseState::new(Vector2::new(1., 0.), Vector2::new(0., 1.));
+        let (state, _) = rk45(start, 0., 2. * PI, 1., 1e-6, |_, s: PhaseState<Vector2>| {
+            PhaseState::new(s.velocity, spring(s.position))
+        });
+        assert_eq!(state, start);
+    }
+    #[test]
+    fn test2_rk45() {
+        // A NaN derivative stops the integration at the last good state instead of growing the
+        // step forever
+        let (state, t) = rk45(1., 0., 1., 0.1, 1e-6, |_, _: f32| f32::NAN);
+        assert_eq!((state, t), (1., 0.));
+        let nan_later = |t: f32, x: f32| if t < 0.5 { -x } else { f32::NAN };
+        let (state, t) = rk45(1., 0., 1., 0.1, 1e-6, nan_later);
+        assert!(t > 0. && t < 0.5);
+        assert!((state - (-t).exp()).abs() < 1e-5);
+        // Far from zero the minimum step is below one ulp of t
+        let (state, t) = rk45(1., 1e6, 1e6 + 1., 0.01, 1e-6, decay);
+        assert!((state - (-1_f32).exp()).abs() < 1e-4);
+        assert_eq!(t, 1e6 + 1.);
+    }
+    // Functions integrate_rotation(), integrate_rotation_local()
+    #[test]
+    fn test1_integrate_rotation() {
+        let omega = Vector3::new(0., 0., 2.);
+        let mut rotation = Matrix3::idenity();
+        for _ in 0..10000 {
+            rotation = integrate_rotation(rotation, omega, 0.001);
+        }
+        assert!(rotation.is_rotation());
+        assert_eq!(rotation, rotate3z(20.));
+    }
+    #[test]
+    fn test1_integrate_rotation_local() {
+        // Body axis z tilted onto world x, spinning about it turns the world y axis
+        let tilt = Matrix3::from_rotation_vector(Vector3::new(0., PI / 2., 0.));
+        let mut rotation = tilt;
+        for _ in 0..1000 {
+            rotation = integrate_rotation_local(rotation, Vector3::new(0., 0., PI / 2.), 0.001);
+        }
+        assert!(rotation.is_rotation());
+        assert_eq!(
+            rotation * Vector3::new(0., 0., 1.),
+            Vector3::new(1., 0., 0.)
+        );
+        assert_eq!(
+            rotation * Vector3::new(1., 0., 0.),
+            Vector3::new(0., 1., 0.)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{Matrix2, Matrix3, Matrix4, MatrixMxN, Vector, Vector2, Vector3, Vector4, VectorN};
+use std::ops::{Add, Mul, Sub};
+
+// Shared interpolation helpers. Parameters t outside [0, 1] extrapolate, except in
+// smoothstep/smootherstep which clamp them
+pub trait Interpolate
+where
+    Self: Copy,
+    Self: Add<Output = Self>,
+    Self: Sub<Output = Self>,
+    Self: Mul<f32, Output = Self>,
+{
+    // Inner product used for projections and distances (Frobenius for matrices)
+    fn inner(self, other: Self) -> f32;
+
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+
+    // Parameter t with self.lerp(to, t) closest to value, 0 when self == to
+    fn inverse_lerp(self, to: Self, value: Self) -> f32 {
+        let delta = to - self;
+        let len_squared = delta.inner(delta);
+        if len_squared == 0. {
+            return 0.;
+        }
+        (value - self).inner(delta) / len_squared
+    }
+
+    // Maps self from the range from.0..from.1 onto to.0..to.1
+    fn remap(self, from: (Self, Self), to: (Self, Self)) -> Self {
+        to.0.lerp(to.1, from.0.inverse_lerp(from.1, self))
+    }
+
+    // Hermite eased interpolation 3t^2 - 2t^3, t is clamped to [0, 1]
+    fn smoothstep(self, to: Self, t: f32) -> Self {
+        let t = t.clamp(0., 1.);
+        self.lerp(to, t * t * (3. - 2. * t))
+    }
+
+    // Ken Perlin's 6t^5 - 15t^4 + 10t^3, t is clamped to [0, 1]
+    fn smootherstep(self, to: Self, t: f32) -> Self {
+        let t = t.clamp(0., 1.);
+        self.lerp(to, t * t * t * (t * (6. * t - 15.) + 10.))
+    }
+
+    // Frame-rate independent replacement for self.lerp(to, k) every frame, decay is in 1/s
+    fn exp_decay(self, to: Self, decay: f32, dt: f32) -> Self {
+        to + (self - to) * (-decay * dt).exp()
+    }
+
+    // Moves towards the target by at most max_delta without overshooting it
+    fn move_towards(self, to: Self, max_delta: f32) -> Self {
+        let delta = to - self;
+        let distance = delta.inner(delta).sqrt();
+        if distance <= max_delta || distance == 0. {
+            return to;
+        }
+        self + delta * (max_delta / distance)
+    }
+}
+
+impl Interpolate for f32 {
+    fn inner(self, other: Self) -> f32 {
+        self * other
+    }
+}
+impl Interpolate for Vector2 {
+    fn inner(self, other: Self) -> f32 {
+        self.dot(other)
+    }
+}
+impl Interpolate for Vector3 {
+    fn inner(self, other: Self) -> f32 {
+        self.dot(other)
+    }
+}
+impl Interpolate for Vector4 {
+    fn inner(self, other: Self) -> f32 {
+        self.dot(other)
+    }
+}
+impl<const N: usize> Interpolate for VectorN<N> {
+    fn inner(self, other: Self) -> f32 {
+        self.dot(other)
+    }
+}
+impl Interpolate for Matrix2 {
+    fn inner(self, other: Self) -> f32 {
+        self.iter().zip(other.iter()).map(|(a, b)| a * b).sum()
+    }
+}
+impl Interpolate for Matrix3 {
+    fn inner(self, other: Self) -> f32 {
+        self.iter().zip(other.iter()).map(|(a, b)| a * b).sum()
+    }
+}
+impl Interpolate for Matrix4 {
+    fn inner(self, other: Self) -> f32 {
+        self.iter().zip(other.iter()).map(|(a, b)| a * b).sum()
+    }
+}
+impl<const M: usize, const N: usize> Interpolate for MatrixMxN<M, N> {
+    fn inner(self, other: Self) -> f32 {
+        let (a, b): ([[f32; N]; M], [[f32; N]; M]) = (self.into(), other.into());
+        a.iter()
+            .flatten()
+            .zip(b.iter().flatten())
+            .map(|(a, b)| a * b)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Matrix};
+
+    // Method lerp(to, t)
+    #[test]
+    fn test1_f32lerp() {
+        assert_eq!(2_f32.lerp(4., 0.25), 2.5);
+        assert_eq!(2_f32.lerp(4., 1.5), 5.);
+        assert_eq!(2_f32.lerp(4., -1.), 0.);
+    }
+    #[test]
+    fn test1_vector3lerp() {
+        let from = Vector3::new(1., 2., 3.);
+        let to = Vector3::new(3., 2., -1.);
+        assert_eq!(from.lerp(to, 0.), from);
+        assert_eq!(from.lerp(to, 1.), to);
+        assert_eq!(from.lerp(to, 0.5), Vector3::new(2., 2., 1.));
+    }
+    #[test]
+    fn test1_matrix4lerp() {
+        let from = Matrix4::zero();
+        let to = Matrix4::scalar(2.);
+        assert_eq!(from.lerp(to, 0.5), Matrix4::idenity());
+        let rect = Matrix2::zero().lerp(Matrix2::new([[1., 2.], [3., 4.]]), 0.5);
+        assert_eq!(rect, Matrix2::new([[0.5, 1.], [1.5, 2.]]));
+    }
+    // Method inverse_lerp(to, value)
+    #[test]
+    fn test1_f32inverse_lerp() {
+        assert_eq!(2_f32.inverse_lerp(4., 2.5), 0.25);
+        assert_eq!(2_f32.inverse_lerp(4., 0.), -1.);
+        assert_eq!(2_f32.inverse_lerp(2., 3.), 0.);
+    }
+    #[test]
+    fn test1_vector2inverse_lerp() {
+        let from = Vector2::new(0., 0.);
+        let to = Vector2::new(4., 0.);
+        assert_eq!(from.inverse_lerp(to, Vector2::new(1., 5.)), 0.25);
+        let value = from.lerp(to, 0.7);
+        assert!((from.inverse_lerp(to, value) - 0.7).abs() < EPSILON);
+    }
+    #[test]
+    fn test1_matrixmxninverse_lerp() {
+        let from = crate::Matrix2x3::zero();
+        let to = crate::Matrix2x3::from(1.);
+        assert!((from.inverse_lerp(to, from.lerp(to, 0.3)) - 0.3).abs() < EPSILON);
+    }
+    // Method remap(from, to)
+    #[test]
+    fn test1_f32remap() {
+        assert_eq!(5_f32.remap((0., 10.), (100., 200.)), 150.);
+        assert_eq!(15_f32.remap((0., 10.), (0., -1.)), -1.5);
+    }
+    #[test]
+    fn test1_vector3remap() {
+        let value = Vector3::new(1., 1., 1.);
+        let remapped = value.remap(
+            (Vector3::zero(), Vector3::new(2., 2., 2.)),
+            (Vector3::new(10., 0., 0.), Vector3::new(20., 0., 0.)),
+        );
+        assert_eq!(remapped, Vector3::new(15., 0., 0.));
+    }
+    // Methods smoothstep(to, t), smootherstep(to, t)
+    #[test]
+    fn test1_f32smoothstep() {
+        assert_eq!(0_f32.smoothstep(1., 0.5), 0.5);
+        assert_eq!(0_f32.smoothstep(1., 0.25), 0.15625);
+        assert_eq!(0_f32.smoothstep(1., -1.), 0.);
+        assert_eq!(0_f32.smoothstep(1., 2.), 1.);
+        assert_eq!(0_f32.smootherstep(1., 0.5), 0.5);
+        assert_eq!(0_f32.smootherstep(1., 0.25), 0.103515625);
+        assert_eq!(2_f32.smootherstep(4., 3.), 4.);
+    }
+    #[test]
+    fn test1_vector4smoothstep() {
+        let from = Vector4::zero();
+        let to = Vector4::new(4., 4., 0., -4.);
+        assert_eq!(from.smoothstep(to, 0.25), to * 0.15625);
+        assert_eq!(from.smootherstep(to, 1.), to);
+    }
+    // Method exp_decay(to, decay, dt)
+    #[test]
+    fn test1_f32exp_decay() {
+        // Two half frames end where one full frame does
+        let one_step = 0_f32.exp_decay(10., 4., 1. / 30.);
+        let two_steps = 0_f32
+            .exp_decay(10., 4., 1. / 60.)
+            .exp_decay(10., 4., 1. / 60.);
+        assert!((one_step - two_steps).abs() < 1e-5);
+        assert_eq!(0_f32.exp_decay(10., 4., 0.), 0.);
+        assert!((0_f32.exp_decay(10., 4., 100.) - 10.).abs() < 1e-5);
+    }
+    #[test]
+    fn test1_matrix3exp_decay() {
+        let from = Matrix3::zero();
+        let to = Matrix3::idenity();
+        let res = from.exp_decay(to, 2_f32.ln(), 1.);
+        assert_eq!(res, Matrix3::scalar(0.5));
+    }
+    // Method move_towards(to, max_delta)
+    #[test]
+    fn test1_f32move_towards() {
+        assert_eq!(0_f32.move_towards(10., 3.), 3.);
+        assert_eq!(0_f32.move_towards(-10., 3.), -3.);
+        assert_eq!(9_f32.move_towards(10., 3.), 10.);
+    }
+    #[test]
+    fn test1_vector2move_towards() {
+        let from = Vector2::zero();
+        let to = Vector2::new(3., 4.);
+        assert_eq!(from.move_towards(to, 2.5), Vector2::new(1.5, 2.));
+        assert_eq!(from.move_towards(to, 10.), to);
+        assert_eq!(to.move_towards(to, 1.), to);
+    }
+    #[test]
+    fn test1_vectornmove_towards() {
+        let from = VectorN::new([0., 0., 0., 0., 0.]);
+        let to = VectorN::new([2., 0., 0., 0., 0.]);
+        assert_eq!(
+            from.move_towards(to, 0.5),
+            VectorN::new([0.5, 0., 0., 0., 0.])
+        );
+    }
+}
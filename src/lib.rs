@@ -1,27 +1,80 @@
+pub(crate) mod animation;
+pub(crate) mod curves;
+pub(crate) mod easing;
 pub(crate) mod functions;
+pub(crate) mod geometry;
+pub(crate) mod integrators;
+pub(crate) mod interpolation;
 pub(crate) mod matrices;
+pub(crate) mod polynomial;
+pub(crate) mod spatial;
+pub(crate) mod springs;
 pub(crate) mod vectors;
 
 // pub (crate) mod quaternions;
 
 use std::ops::Mul;
 
+pub use cholesky::{Cholesky, Ldlt};
+pub use eigen::SymmetricEigen;
+pub use lu::Lu;
 pub use mat2::Matrix2;
 pub use mat3::Matrix3;
 pub use mat4::Matrix4;
+pub use matmxn::{
+    Matrix2x3, Matrix2x4, Matrix3x2, Matrix3x4, Matrix4x2, Matrix4x3, MatrixMxN, MatrixN,
+};
 pub use matrices::Matrix;
 use matrices::*;
+pub use qr::Qr;
+pub use svd::Svd;
 use transform_matrix::*;
 
 pub use vec2::Vector2;
 pub use vec3::Vector3;
 pub use vec4::Vector4;
+pub use vecn::VectorN;
 pub use vectors::Vector;
 use vectors::*;
 
-pub use functions::*;
-pub use constants;
+pub use aabb::Aabb3;
+pub use frustum::Frustum;
+use geometry::*;
+pub use ray::Ray3;
+pub use rect::Rect;
+
+pub use bvh::Bvh;
+pub use grid::{CellHit, CellTraversal, SpatialHash};
+pub use kdtree::KdTree;
+pub use loose::{LooseBounds, LooseTree};
+pub use octree::Octree;
+pub use quadtree::Quadtree;
+use spatial::*;
+
+pub use bezier::{CubicBezier, QuadraticBezier};
+pub use bspline::BSpline;
+use curves::*;
+pub use curves::{ArcLengthCurve, ArcLengthTable, Curve, Curve2, Curve3, Frame};
+pub use nurbs::{NurbsCurve, NurbsSurface, TriangleMesh};
+pub use spline::{CatmullRom, CubicHermite, NaturalCubicSpline};
+
+pub use interpolation::Interpolate;
+
+pub use animation::{Animatable, AnimationTrack, Interpolation, Keyframe, WrapMode};
 
+pub use easing::*;
+
+pub use integrators::{
+    explicit_euler, integrate_rotation, integrate_rotation_local, rk4, rk45, rk45_step,
+    semi_implicit_euler, velocity_verlet, AdaptiveStep, PhaseState,
+};
+
+pub use polynomial::{solve_cubic, solve_quadratic, solve_quartic, Polynomial};
+
+pub use springs::{smooth_damp, smooth_damp_rotation, RotationDynamics, SecondOrderDynamics};
+
+pub use constants;
+pub use functions::*;
 
 impl Mul<Vector2> for Matrix2 {
     type Output = Vector2;
@@ -0,0 +1,349 @@
+use super::{mat2::Matrix2, mat3::Matrix3, mat4::Matrix4, matmxn::MatrixN, Matrix};
+
+// Pivots up to this, relative to the largest diagonal entry, end the factorization
+fn tolerance<const N: usize>(matrix: &[[f32; N]; N]) -> f32 {
+    let largest = (0..N).map(|i| matrix[i][i].abs()).fold(0., f32::max);
+    f32::EPSILON * N as f32 * largest
+}
+
+fn forward<const N: usize>(l: &[[f32; N]; N], b: [f32; N], unit_diagonal: bool) -> [f32; N] {
+    let mut x = b;
+    for i in 0..N {
+        for j in 0..i {
+            x[i] -= l[i][j] * x[j];
+        }
+        if !unit_diagonal {
+            x[i] /= l[i][i];
+        }
+    }
+    x
+}
+
+// Solves L^T x = b
+fn backward<const N: usize>(l: &[[f32; N]; N], b: [f32; N], unit_diagonal: bool) -> [f32; N] {
+    let mut x = b;
+    for i in (0..N).rev() {
+        for j in i + 1..N {
+            x[i] -= l[j][i] * x[j];
+        }
+        if !unit_diagonal {
+            x[i] /= l[i][i];
+        }
+    }
+    x
+}
+
+// A = L * L^T for symmetric positive-definite A, only the lower triangle of A is read
+#[derive(Debug, Clone, Copy)]
+pub struct Cholesky<const N: usize> {
+    l: [[f32; N]; N],
+}
+impl<const N: usize> Cholesky<N> {
+    pub fn try_new(matrix: [[f32; N]; N]) -> Option<Self> {
+        let tolerance = tolerance(&matrix);
+        let mut l = [[0.; N]; N];
+        for j in 0..N {
+            let diagonal = matrix[j][j] - (0..j).map(|k| l[j][k] * l[j][k]).sum::<f32>();
+            if diagonal <= tolerance {
+                return None;
+            }
+            l[j][j] = diagonal.sqrt();
+            for i in j + 1..N {
+                let sum = (0..j).map(|k| l[i][k] * l[j][k]).sum::<f32>();
+                l[i][j] = (matrix[i][j] - sum) / l[j][j];
+            }
+        }
+        Some(Self { l })
+    }
+
+    pub fn new(matrix: [[f32; N]; N]) -> Self {
+        Self::try_new(matrix)
+            .expect("It is impossible to factorize a matrix that is not positive-definite")
+    }
+
+    pub fn l<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.l.into()
+    }
+
+    pub fn det(&self) -> f32 {
+        (0..N).map(|i| self.l[i][i] * self.l[i][i]).product()
+    }
+
+    pub fn solve<V>(&self, b: V) -> V
+    where
+        V: From<[f32; N]> + Into<[f32; N]>,
+    {
+        let y = forward(&self.l, b.into(), false);
+        backward(&self.l, y, false).into()
+    }
+
+    pub fn inverse<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        let columns: [[f32; N]; N] = std::array::from_fn(|j| {
+            let mut e = [0.; N];
+            e[j] = 1.;
+            self.solve(e)
+        });
+        let inverse: [[f32; N]; N] =
+            std::array::from_fn(|i| std::array::from_fn(|j| columns[j][i]));
+        inverse.into()
+    }
+}
+
+// A = L * D * L^T with unit lower triangular L, works for symmetric indefinite A as long as
+// no pivot vanishes. Only the lower triangle of A is read
+#[derive(Debug, Clone, Copy)]
+pub struct Ldlt<const N: usize> {
+    l: [[f32; N]; N],
+    d: [f32; N],
+}
+impl<const N: usize> Ldlt<N> {
+    pub fn try_new(matrix: [[f32; N]; N]) -> Option<Self> {
+        let tolerance = tolerance(&matrix);
+        let mut l = [[0.; N]; N];
+        let mut d = [0.; N];
+        for j in 0..N {
+            d[j] = matrix[j][j] - (0..j).map(|k| l[j][k] * l[j][k] * d[k]).sum::<f32>();
+            if d[j].abs() <= tolerance {
+                return None;
+            }
+            l[j][j] = 1.;
+            for i in j + 1..N {
+                let sum = (0..j).map(|k| l[i][k] * l[j][k] * d[k]).sum::<f32>();
+                l[i][j] = (matrix[i][j] - sum) / d[j];
+            }
+        }
+        Some(Self { l, d })
+    }
+
+    pub fn new(matrix: [[f32; N]; N]) -> Self {
+        Self::try_new(matrix)
+            .expect("It is impossible to factorize a matrix with a vanishing pivot")
+    }
+
+    pub fn l<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.l.into()
+    }
+
+    pub fn d<V>(&self) -> V
+    where
+        V: From<[f32; N]>,
+    {
+        self.d.into()
+    }
+
+    pub fn det(&self) -> f32 {
+        self.d.iter().product()
+    }
+
+    pub fn is_positive_definite(&self) -> bool {
+        self.d.iter().all(|&d| d > 0.)
+    }
+
+    pub fn solve<V>(&self, b: V) -> V
+    where
+        V: From<[f32; N]> + Into<[f32; N]>,
+    {
+        let mut y = forward(&self.l, b.into(), true);
+        for (y, d) in y.iter_mut().zip(self.d) {
+            *y /= d;
+        }
+        backward(&self.l, y, true).into()
+    }
+}
+
+impl Matrix2 {
+    pub fn try_cholesky(self) -> Option<Cholesky<2>> {
+        Cholesky::try_new(self.into())
+    }
+    pub fn cholesky(self) -> Cholesky<2> {
+        Cholesky::new(self.into())
+    }
+
+    pub fn try_ldlt(self) -> Option<Ldlt<2>> {
+        Ldlt::try_new(self.into())
+    }
+    pub fn ldlt(self) -> Ldlt<2> {
+        Ldlt::new(self.into())
+    }
+
+    pub fn is_positive_definite(self) -> bool {
+        self.is_symmetric() && self.try_cholesky().is_some()
+    }
+}
+impl Matrix3 {
+    pub fn try_cholesky(self) -> Option<Cholesky<3>> {
+        Cholesky::try_new(self.into())
+    }
+    pub fn cholesky(self) -> Cholesky<3> {
+        Cholesky::new(self.into())
+    }
+
+    pub fn try_ldlt(self) -> Option<Ldlt<3>> {
+        Ldlt::try_new(self.into())
+    }
+    pub fn ldlt(self) -> Ldlt<3> {
+        Ldlt::new(self.into())
+    }
+
+    pub fn is_positive_definite(self) -> bool {
+        self.is_symmetric() && self.try_cholesky().is_some()
+    }
+}
+impl Matrix4 {
+    pub fn try_cholesky(self) -> Option<Cholesky<4>> {
+        Cholesky::try_new(self.into())
+    }
+    pub fn cholesky(self) -> Cholesky<4> {
+        Cholesky::new(self.into())
+    }
+
+    pub fn try_ldlt(self) -> Option<Ldlt<4>> {
+        Ldlt::try_new(self.into())
+    }
+    pub fn ldlt(self) -> Ldlt<4> {
+        Ldlt::new(self.into())
+    }
+
+    pub fn is_positive_definite(self) -> bool {
+        self.is_symmetric() && self.try_cholesky().is_some()
+    }
+}
+impl<const N: usize> MatrixN<N> {
+    pub fn try_cholesky(self) -> Option<Cholesky<N>> {
+        Cholesky::try_new(self.into())
+    }
+    pub fn cholesky(self) -> Cholesky<N> {
+        Cholesky::new(self.into())
+    }
+
+    pub fn try_ldlt(self) -> Option<Ldlt<N>> {
+        Ldlt::try_new(self.into())
+    }
+    pub fn ldlt(self) -> Ldlt<N> {
+        Ldlt::new(self.into())
+    }
+
+    pub fn is_positive_definite(self) -> bool {
+        self.is_symmetric() && self.try_cholesky().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Vector2, Vector3, Vector4};
+
+    fn spd4() -> Matrix4 {
+        Matrix4::new([
+            [4., 1., 0., 2.],
+            [1., 3., 1., 0.],
+            [0., 1., 5., 1.],
+            [2., 0., 1., 6.],
+        ])
+    }
+
+    // Constructors
+    #[test]
+    fn test1_choleskynew() {
+        let mat = Matrix3::new([[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]]);
+        let l: Matrix3 = mat.cholesky().l();
+        assert_eq!(l, Matrix3::new([[2., 0., 0.], [6., 1., 0.], [-8., 5., 3.]]));
+        assert_eq!(l * l.transpose(), mat);
+    }
+    #[test]
+    fn test2_choleskynew() {
+        let mat = Matrix2::new([[1., 2.], [2., 1.]]);
+        assert!(mat.try_cholesky().is_none());
+        assert!(Matrix3::zero().try_cholesky().is_none());
+    }
+    #[test]
+    #[should_panic]
+    fn test3_choleskynew() {
+        Matrix2::new([[-1., 0.], [0., 1.]]).cholesky();
+    }
+    // Method det()
+    #[test]
+    fn test1_choleskydet() {
+        let mat = spd4();
+        assert!((mat.cholesky().det() - mat.det()).abs() < EPSILON * mat.det());
+    }
+    // Method solve(b)
+    #[test]
+    fn test1_choleskysolve() {
+        let mat = spd4();
+        let x = Vector4::new(1., -2., 0.5, 3.);
+        assert_eq!(mat.cholesky().solve(mat * x), x);
+    }
+    #[test]
+    fn test2_choleskysolve() {
+        let mat = Matrix2::new([[2., -1.], [-1., 2.]]);
+        assert_eq!(
+            mat.cholesky().solve(Vector2::new(1., 1.)),
+            Vector2::new(1., 1.)
+        );
+    }
+    // Method inverse()
+    #[test]
+    fn test1_choleskyinverse() {
+        let mat = spd4();
+        let inverse: Matrix4 = mat.cholesky().inverse();
+        assert_eq!(inverse * mat, Matrix4::idenity());
+    }
+    // Constructors
+    #[test]
+    fn test1_ldltnew() {
+        let mat = Matrix3::new([[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]]);
+        let ldlt = mat.ldlt();
+        let (l, d): (Matrix3, Vector3) = (ldlt.l(), ldlt.d());
+        let diagonal = Matrix3::new([[d.x, 0., 0.], [0., d.y, 0.], [0., 0., d.z]]);
+        assert_eq!(l * diagonal * l.transpose(), mat);
+        assert_eq!(d, Vector3::new(4., 1., 9.));
+        assert!(ldlt.is_positive_definite());
+    }
+    #[test]
+    fn test2_ldltnew() {
+        // Symmetric indefinite, Cholesky fails but LDL^T does not
+        let mat = Matrix3::new([[1., 2., 0.], [2., 1., 1.], [0., 1., -2.]]);
+        assert!(mat.try_cholesky().is_none());
+        let ldlt = mat.ldlt();
+        assert!(!ldlt.is_positive_definite());
+        assert!((ldlt.det() - mat.det()).abs() < EPSILON);
+        let x = Vector3::new(1., 2., 3.);
+        assert_eq!(ldlt.solve(mat * x), x);
+    }
+    #[test]
+    fn test3_ldltnew() {
+        assert!(Matrix2::new([[0., 1.], [1., 0.]]).try_ldlt().is_none());
+    }
+    // Method is_positive_definite()
+    #[test]
+    fn test1_matrix4is_positive_definite() {
+        assert!(spd4().is_positive_definite());
+        assert!(Matrix4::idenity().is_positive_definite());
+        assert!(!(-spd4()).is_positive_definite());
+        let mut not_symmetric = spd4();
+        not_symmetric[(0, 3)] = 0.;
+        assert!(!not_symmetric.is_positive_definite());
+    }
+    #[test]
+    fn test1_matrixncholesky() {
+        let mut mat = MatrixN::<6>::scalar(4.);
+        for i in 0..5 {
+            mat[(i, i + 1)] = -1.;
+            mat[(i + 1, i)] = -1.;
+        }
+        assert!(mat.is_positive_definite());
+        let b = crate::VectorN::new([1., 2., 3., 4., 5., 6.]);
+        assert_eq!(mat * mat.cholesky().solve(b), b);
+        assert_eq!(mat * mat.ldlt().solve(b), b);
+    }
+}
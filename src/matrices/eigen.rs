@@ -0,0 +1,180 @@
+use super::mat3::Matrix3;
+use crate::{Matrix, Vector, Vector3};
+
+const MAX_SWEEPS: usize = 32;
+
+// Eigenvalues in descending order, eigenvectors[i] belongs to eigenvalues[i].
+// The eigenvectors form a right-handed orthonormal basis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymmetricEigen {
+    pub eigenvalues: Vector3,
+    pub eigenvectors: [Vector3; 3],
+}
+impl SymmetricEigen {
+    // Cyclic Jacobi rotations, only the symmetric part of the matrix is used
+    pub fn new(matrix: Matrix3) -> Self {
+        let mut a: [[f32; 3]; 3] = ((matrix + matrix.transpose()) / 2.).into();
+        let mut v: [[f32; 3]; 3] = Matrix3::idenity().into();
+        let norm_squared = a.iter().flatten().map(|x| x * x).sum::<f32>();
+        for _ in 0..MAX_SWEEPS {
+            let off_squared = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+            if off_squared <= f32::EPSILON * f32::EPSILON * norm_squared {
+                break;
+            }
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q] == 0. {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+                // A = J^T * A * J and V = V * J with J the rotation in the (p, q) plane
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                for row in v.iter_mut() {
+                    let (vp, vq) = (row[p], row[q]);
+                    row[p] = c * vp - s * vq;
+                    row[q] = s * vp + c * vq;
+                }
+            }
+        }
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| a[j][j].total_cmp(&a[i][i]));
+        let column = |j: usize| Vector3::new(v[0][j], v[1][j], v[2][j]);
+        let mut eigenvectors = order.map(column);
+        if eigenvectors[0].cross(eigenvectors[1]).dot(eigenvectors[2]) < 0. {
+            eigenvectors[2] = -eigenvectors[2];
+        }
+        Self {
+            eigenvalues: Vector3::from(order.map(|i| a[i][i])),
+            eigenvectors,
+        }
+    }
+
+    // Columns are the eigenvectors, so matrix = rotation * diag(eigenvalues) * rotation^T
+    pub fn rotation(&self) -> Matrix3 {
+        let [vec1, vec2, vec3] = self.eigenvectors;
+        Matrix3::from_cols(vec1, vec2, vec3)
+    }
+}
+
+impl Matrix3 {
+    pub fn symmetric_eigen(self) -> SymmetricEigen {
+        SymmetricEigen::new(self)
+    }
+
+    // Population covariance of the points around their centroid, zero for an empty slice
+    pub fn covariance(points: &[Vector3]) -> Self {
+        if points.is_empty() {
+            return Self::zero();
+        }
+        let count = points.len() as f32;
+        let centroid = points
+            .iter()
+            .fold(Vector3::zero(), |sum, &point| sum + point)
+            / count;
+        let mut data = [[0.; 3]; 3];
+        for &point in points {
+            let offset = point - centroid;
+            for (i, row) in data.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value += offset[i] * offset[j];
+                }
+            }
+        }
+        Self::new(data) / count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, transform};
+
+    fn assert_decomposition(matrix: Matrix3, eigen: SymmetricEigen) {
+        for i in 0..3 {
+            let vector = eigen.eigenvectors[i];
+            assert!((vector.len() - 1.).abs() < EPSILON);
+            assert_eq!(matrix * vector, vector * eigen.eigenvalues[i]);
+        }
+        let rotation = eigen.rotation();
+        assert_eq!(rotation.transpose() * rotation, Matrix3::idenity());
+        assert!((rotation.det() - 1.).abs() < EPSILON);
+        let [x, y, z] = eigen.eigenvalues.into();
+        let diagonal = Matrix3::new([[x, 0., 0.], [0., y, 0.], [0., 0., z]]);
+        assert_eq!(rotation * diagonal * rotation.transpose(), matrix);
+        assert!(x >= y && y >= z);
+    }
+
+    // Constructors
+    #[test]
+    fn test1_symmetriceigennew() {
+        let mat = Matrix3::new([[1., 0., 0.], [0., 5., 0.], [0., 0., -2.]]);
+        let eigen = mat.symmetric_eigen();
+        assert_eq!(eigen.eigenvalues, Vector3::new(5., 1., -2.));
+        assert_decomposition(mat, eigen);
+    }
+    #[test]
+    fn test2_symmetriceigennew() {
+        let mat = Matrix3::new([[4., 1., -2.], [1., 2., 0.], [-2., 0., 3.]]);
+        assert_decomposition(mat, mat.symmetric_eigen());
+    }
+    #[test]
+    fn test3_symmetriceigennew() {
+        let mat = Matrix3::new([[2., 1., 0.], [1., 2., 0.], [0., 0., 3.]]);
+        let eigen = mat.symmetric_eigen();
+        assert_eq!(eigen.eigenvalues, Vector3::new(3., 3., 1.));
+        assert_decomposition(mat, eigen);
+    }
+    #[test]
+    fn test4_symmetriceigennew() {
+        let rotation = transform::rotate3(0.4, -0.7, 1.3);
+        let diagonal = Matrix3::new([[7., 0., 0.], [0., 0.5, 0.], [0., 0., 2.]]);
+        let mat = rotation * diagonal * rotation.transpose();
+        let eigen = mat.symmetric_eigen();
+        assert_eq!(eigen.eigenvalues, Vector3::new(7., 2., 0.5));
+        assert_decomposition(mat, eigen);
+    }
+    #[test]
+    fn test5_symmetriceigennew() {
+        let eigen = Matrix3::zero().symmetric_eigen();
+        assert_eq!(eigen.eigenvalues, Vector3::zero());
+        assert_eq!(eigen.rotation(), Matrix3::idenity());
+    }
+    // Method covariance(points)
+    #[test]
+    fn test1_matrix3covariance() {
+        let points = [
+            Vector3::new(1., 1., 1.),
+            Vector3::new(3., 1., 1.),
+            Vector3::new(1., 3., 1.),
+            Vector3::new(3., 3., 1.),
+        ];
+        assert_eq!(
+            Matrix3::covariance(&points),
+            Matrix3::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 0.]])
+        );
+        assert_eq!(Matrix3::covariance(&[]), Matrix3::zero());
+    }
+    #[test]
+    fn test2_matrix3covariance() {
+        let direction = Vector3::new(1., 2., -2.) / 3.;
+        let points: Vec<Vector3> = (0..20)
+            .map(|i| {
+                let t = i as f32 - 10.;
+                let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+                Vector3::new(2., -1., 4.) + direction * t + Vector3::new(noise, 0., noise)
+            })
+            .collect();
+        let eigen = Matrix3::covariance(&points).symmetric_eigen();
+        assert!(eigen.eigenvectors[0].dot(direction).abs() > 1. - EPSILON);
+        assert!(eigen.eigenvalues[1] < 0.01);
+    }
+}
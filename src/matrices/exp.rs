@@ -0,0 +1,412 @@
+use super::{mat2::Matrix2, mat3::Matrix3, mat4::Matrix4, matmxn::MatrixN};
+
+// Matrix functions are evaluated in f64, squaring and the series lose too much in f32
+type Array<const N: usize> = [[f64; N]; N];
+
+const PADE_ORDER: usize = 6;
+const MAX_SQRT_ITERATIONS: usize = 64;
+const MAX_LOG_SQUARE_ROOTS: i32 = 32;
+
+fn widen<const N: usize>(matrix: [[f32; N]; N]) -> Array<N> {
+    matrix.map(|row| row.map(f64::from))
+}
+
+fn narrow<const N: usize>(matrix: Array<N>) -> [[f32; N]; N] {
+    matrix.map(|row| row.map(|value| value as f32))
+}
+
+fn identity<const N: usize>() -> Array<N> {
+    std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1. } else { 0. }))
+}
+
+fn add<const N: usize>(a: &Array<N>, b: &Array<N>, b_factor: f64) -> Array<N> {
+    std::array::from_fn(|i| std::array::from_fn(|j| a[i][j] + b_factor * b[i][j]))
+}
+
+fn scale<const N: usize>(a: &Array<N>, factor: f64) -> Array<N> {
+    a.map(|row| row.map(|value| value * factor))
+}
+
+fn mul<const N: usize>(a: &Array<N>, b: &Array<N>) -> Array<N> {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..N).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+fn norm_1<const N: usize>(a: &Array<N>) -> f64 {
+    (0..N)
+        .map(|j| (0..N).map(|i| a[i][j].abs()).sum::<f64>())
+        .fold(0., f64::max)
+}
+
+// Gauss-Jordan with partial pivoting, None when a pivot vanishes relative to the matrix norm
+fn invert<const N: usize>(a: &Array<N>) -> Option<Array<N>> {
+    let tolerance = f64::EPSILON * N as f64 * norm_1(a);
+    let mut a = *a;
+    let mut inverse = identity::<N>();
+    for k in 0..N {
+        let pivot = (k..N).max_by(|&p, &q| a[p][k].abs().total_cmp(&a[q][k].abs()))?;
+        if a[pivot][k].abs() <= tolerance {
+            return None;
+        }
+        a.swap(pivot, k);
+        inverse.swap(pivot, k);
+        let factor = 1. / a[k][k];
+        a[k] = a[k].map(|value| value * factor);
+        inverse[k] = inverse[k].map(|value| value * factor);
+        let (pivot_row, inverse_row) = (a[k], inverse[k]);
+        for i in (0..N).filter(|&i| i != k) {
+            let factor = a[i][k];
+            for j in 0..N {
+                a[i][j] -= factor * pivot_row[j];
+                inverse[i][j] -= factor * inverse_row[j];
+            }
+        }
+    }
+    Some(inverse)
+}
+
+// Diagonal Pade approximant with scaling and squaring
+fn exp_array<const N: usize>(a: &Array<N>) -> Array<N> {
+    let norm = norm_1(a);
+    // An infinite or NaN entry would saturate the squaring count, the result is NaN anyway
+    if !norm.is_finite() {
+        return [[f64::NAN; N]; N];
+    }
+    let squarings = if norm > 0.5 {
+        (norm / 0.5).log2().ceil() as i32
+    } else {
+        0
+    };
+    let a = scale(a, 0.5_f64.powi(squarings));
+    let mut numerator = identity::<N>();
+    let mut denominator = identity::<N>();
+    let mut power = identity::<N>();
+    let mut coefficient = 1.;
+    for k in 1..=PADE_ORDER {
+        coefficient *= (PADE_ORDER - k + 1) as f64 / (k * (2 * PADE_ORDER - k + 1)) as f64;
+        power = mul(&power, &a);
+        numerator = add(&numerator, &power, coefficient);
+        let sign = if k % 2 == 0 { 1. } else { -1. };
+        denominator = add(&denominator, &power, sign * coefficient);
+    }
+    let denominator =
+        invert(&denominator).expect("Pade denominator of a scaled matrix is invertible");
+    let mut res = mul(&denominator, &numerator);
+    for _ in 0..squarings {
+        res = mul(&res, &res);
+    }
+    res
+}
+
+// Denman-Beavers iteration, converges to the principal square root when no eigenvalue lies
+// on the closed negative real axis
+fn sqrt_array<const N: usize>(a: &Array<N>) -> Option<Array<N>> {
+    let mut y = *a;
+    let mut z = identity::<N>();
+    for _ in 0..MAX_SQRT_ITERATIONS {
+        let (y_inverse, z_inverse) = (invert(&y)?, invert(&z)?);
+        let next_y = scale(&add(&y, &z_inverse, 1.), 0.5);
+        let next_z = scale(&add(&z, &y_inverse, 1.), 0.5);
+        let change = norm_1(&add(&next_y, &y, -1.));
+        y = next_y;
+        z = next_z;
+        if change <= 1e-12 * norm_1(&y) {
+            return Some(y);
+        }
+    }
+    None
+}
+
+// Inverse scaling and squaring: square roots bring A close to I, then the Gregory series
+// log(A) = 2 * sum(Z^(2k+1) / (2k+1)) with Z = (A - I)(A + I)^-1
+fn log_array<const N: usize>(a: &Array<N>) -> Option<Array<N>> {
+    let identity = identity::<N>();
+    let mut a = *a;
+    let mut roots = 0;
+    while norm_1(&add(&a, &identity, -1.)) > 0.25 {
+        if roots == MAX_LOG_SQUARE_ROOTS {
+            return None;
+        }
+        a = sqrt_array(&a)?;
+        roots += 1;
+    }
+    let z = mul(&add(&a, &identity, -1.), &invert(&add(&a, &identity, 1.))?);
+    let z_squared = mul(&z, &z);
+    let mut term = z;
+    let mut res = z;
+    for k in 1.. {
+        term = mul(&term, &z_squared);
+        let contribution = scale(&term, 1. / (2 * k + 1) as f64);
+        res = add(&res, &contribution, 1.);
+        if norm_1(&contribution) <= f64::EPSILON * norm_1(&res) {
+            break;
+        }
+    }
+    Some(scale(&res, 2_f64.powi(roots + 1)))
+}
+
+fn try_powf_array<const N: usize>(a: &Array<N>, exponent: f32) -> Option<Array<N>> {
+    Some(exp_array(&scale(&log_array(a)?, f64::from(exponent))))
+}
+
+impl Matrix2 {
+    pub fn exp(self) -> Self {
+        narrow(exp_array(&widen(self.into()))).into()
+    }
+
+    pub fn try_log(self) -> Option<Self> {
+        log_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn log(self) -> Self {
+        self.try_log()
+            .expect("It is impossible to take the principal logarithm of this matrix")
+    }
+
+    pub fn try_sqrt(self) -> Option<Self> {
+        sqrt_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn sqrt(self) -> Self {
+        self.try_sqrt()
+            .expect("It is impossible to take the principal square root of this matrix")
+    }
+
+    pub fn try_powf(self, exponent: f32) -> Option<Self> {
+        try_powf_array(&widen(self.into()), exponent).map(|res| narrow(res).into())
+    }
+
+    pub fn powf(self, exponent: f32) -> Self {
+        self.try_powf(exponent)
+            .expect("It is impossible to take a fractional power of this matrix")
+    }
+}
+impl Matrix3 {
+    pub fn exp(self) -> Self {
+        narrow(exp_array(&widen(self.into()))).into()
+    }
+
+    pub fn try_log(self) -> Option<Self> {
+        log_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn log(self) -> Self {
+        self.try_log()
+            .expect("It is impossible to take the principal logarithm of this matrix")
+    }
+
+    pub fn try_sqrt(self) -> Option<Self> {
+        sqrt_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn sqrt(self) -> Self {
+        self.try_sqrt()
+            .expect("It is impossible to take the principal square root of this matrix")
+    }
+
+    pub fn try_powf(self, exponent: f32) -> Option<Self> {
+        try_powf_array(&widen(self.into()), exponent).map(|res| narrow(res).into())
+    }
+
+    pub fn powf(self, exponent: f32) -> Self {
+        self.try_powf(exponent)
+            .expect("It is impossible to take a fractional power of this matrix")
+    }
+}
+impl Matrix4 {
+    pub fn exp(self) -> Self {
+        narrow(exp_array(&widen(self.into()))).into()
+    }
+
+    pub fn try_log(self) -> Option<Self> {
+        log_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn log(self) -> Self {
+        self.try_log()
+            .expect("It is impossible to take the principal logarithm of this matrix")
+    }
+
+    pub fn try_sqrt(self) -> Option<Self> {
+        sqrt_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn sqrt(self) -> Self {
+        self.try_sqrt()
+            .expect("It is impossible to take the principal square root of this matrix")
+    }
+
+    pub fn try_powf(self, exponent: f32) -> Option<Self> {
+        try_powf_array(&widen(self.into()), exponent).map(|res| narrow(res).into())
+    }
+
+    pub fn powf(self, exponent: f32) -> Self {
+        self.try_powf(exponent)
+            .expect("It is impossible to take a fractional power of this matrix")
+    }
+}
+impl<const N: usize> MatrixN<N> {
+    pub fn exp(self) -> Self {
+        narrow(exp_array(&widen(self.into()))).into()
+    }
+
+    pub fn try_log(self) -> Option<Self> {
+        log_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn log(self) -> Self {
+        self.try_log()
+            .expect("It is impossible to take the principal logarithm of this matrix")
+    }
+
+    pub fn try_sqrt(self) -> Option<Self> {
+        sqrt_array(&widen(self.into())).map(|res| narrow(res).into())
+    }
+
+    pub fn sqrt(self) -> Self {
+        self.try_sqrt()
+            .expect("It is impossible to take the principal square root of this matrix")
+    }
+
+    pub fn try_powf(self, exponent: f32) -> Option<Self> {
+        try_powf_array(&widen(self.into()), exponent).map(|res| narrow(res).into())
+    }
+
+    pub fn powf(self, exponent: f32) -> Self {
+        self.try_powf(exponent)
+            .expect("It is impossible to take a fractional power of this matrix")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        functions::constants::PI,
+        transform::{self, homogeneous},
+        Matrix, Vector, Vector3,
+    };
+
+    // Method exp()
+    #[test]
+    fn test1_matrix3exp() {
+        assert_eq!(Matrix3::zero().exp(), Matrix3::idenity());
+        let mat = Matrix3::new([[1., 0., 0.], [0., -2., 0.], [0., 0., 0.5]]);
+        let e = std::f32::consts::E;
+        assert_eq!(
+            mat.exp(),
+            Matrix3::new([[e, 0., 0.], [0., 1. / (e * e), 0.], [0., 0., e.sqrt()]])
+        );
+    }
+    #[test]
+    fn test2_matrix3exp() {
+        // Rodrigues: exp of a skew-symmetric matrix is the rotation about its axis
+        let axis = Vector3::new(1., 2., -2.).normalize();
+        let angle = 2.5;
+        let rotation = Matrix3::skew(axis * angle).exp();
+        let (sin, cos) = angle.sin_cos();
+        let expected =
+            Matrix3::idenity() * cos + Matrix3::skew(axis) * sin + axis.outer(axis) * (1. - cos);
+        assert_eq!(rotation, expected);
+        assert_eq!(rotation * axis, axis);
+    }
+    #[test]
+    fn test3_matrix3exp() {
+        // Integrating a constant angular velocity step by step matches one large step
+        let omega = Vector3::new(0.3, -1.2, 2.);
+        let step = Matrix3::skew(omega * 0.01).exp();
+        let mut rotation = Matrix3::idenity();
+        for _ in 0..100 {
+            rotation = step * rotation;
+        }
+        assert_eq!(rotation, Matrix3::skew(omega).exp());
+    }
+    #[test]
+    fn test4_matrix3exp() {
+        for value in [f32::INFINITY, f32::NAN] {
+            assert!(Matrix3::from(value).exp().iter().all(|comp| comp.is_nan()));
+        }
+    }
+    #[test]
+    fn test1_matrix4exp() {
+        let mat = Matrix4::new([
+            [0., 0., 0., 1.],
+            [0., 0., 0., 2.],
+            [0., 0., 0., 3.],
+            [0., 0., 0., 0.],
+        ]);
+        assert_eq!(mat.exp(), homogeneous::translate3(Vector3::new(1., 2., 3.)));
+        let large = Matrix4::scalar(5.).exp();
+        assert!((large[(0, 0)] - 5_f32.exp()).abs() < 5_f32.exp() * 1e-5);
+    }
+    // Method log()
+    #[test]
+    fn test1_matrix3log() {
+        assert_eq!(Matrix3::idenity().log(), Matrix3::zero());
+        let rotation = transform::rotate3(0.4, -1.1, 2.3);
+        let log = rotation.log();
+        assert_eq!(log.transpose(), -log);
+        assert_eq!(log.exp(), rotation);
+    }
+    #[test]
+    fn test2_matrix3log() {
+        let mat = Matrix3::new([[4., 1., 0.], [1., 3., 1.], [0., 1., 2.]]);
+        assert_eq!(mat.log().exp(), mat);
+        assert_eq!(
+            Matrix3::new([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]).try_log(),
+            None
+        );
+    }
+    #[test]
+    fn test1_matrix4log() {
+        let transform = homogeneous::translate3(Vector3::new(1., -2., 5.))
+            * homogeneous::rotate3(0.2, 0.7, -1.3)
+            * homogeneous::scale3(Vector3::new(2., 2., 2.));
+        assert_eq!(transform.log().exp(), transform);
+    }
+    // Method sqrt()
+    #[test]
+    fn test1_matrix3sqrt() {
+        let rotation = transform::rotate3z(PI / 2.);
+        assert_eq!(rotation.sqrt(), transform::rotate3z(PI / 4.));
+        let mat = Matrix3::new([[4., 1., 0.], [1., 3., 1.], [0., 1., 2.]]);
+        let root = mat.sqrt();
+        assert_eq!(root * root, mat);
+        assert_eq!(root.transpose(), root);
+    }
+    #[test]
+    fn test2_matrix3sqrt() {
+        let mat = Matrix3::new([[-1., 0., 0.], [0., -1., 0.], [0., 0., 1.]]);
+        assert_eq!(mat.try_sqrt(), None);
+        assert_eq!(Matrix3::zero().try_sqrt(), None);
+    }
+    #[test]
+    fn test1_matrix2sqrt() {
+        let mat = Matrix2::new([[33., 24.], [48., 57.]]);
+        assert_eq!(mat.sqrt(), Matrix2::new([[5., 2.], [4., 7.]]));
+    }
+    // Method powf(exponent)
+    #[test]
+    fn test1_matrix4powf() {
+        let transform = homogeneous::translate3(Vector3::new(4., 0., -2.))
+            * homogeneous::rotate3(0.5, -0.2, 1.);
+        let half = transform.powf(0.5);
+        assert_eq!(half * half, transform);
+        assert_eq!(transform.powf(1.), transform);
+        assert_eq!(transform.powf(0.), Matrix4::idenity());
+        assert_eq!(transform.powf(-1.), transform.invert());
+        assert_eq!(transform.powf(2.), transform * transform);
+    }
+    #[test]
+    fn test1_matrix3powf() {
+        let rotation = transform::rotate3x(1.2);
+        assert_eq!(rotation.powf(0.25), transform::rotate3x(0.3));
+        assert_eq!(Matrix3::zero().try_powf(0.5), None);
+    }
+    #[test]
+    fn test1_matrixnexp() {
+        let mat = MatrixN::<5>::scalar(0.5);
+        assert_eq!(mat.exp(), MatrixN::scalar(0.5_f32.exp()));
+        assert_eq!(mat.exp().log(), mat);
+        assert_eq!(MatrixN::<5>::scalar(4.).sqrt(), MatrixN::scalar(2.));
+    }
+}
@@ -0,0 +1,251 @@
+use super::{mat2::Matrix2, mat3::Matrix3, mat4::Matrix4, matmxn::MatrixN};
+
+// LU decomposition with partial pivoting, P * A = L * U. L (unit diagonal, implied) and U
+// share one array, row i of P * A is row pivots[i] of A.
+#[derive(Debug, Clone, Copy)]
+pub struct Lu<const N: usize> {
+    lu: [[f32; N]; N],
+    pivots: [usize; N],
+    sign: f32,
+    norm: f32,
+}
+impl<const N: usize> Lu<N> {
+    pub fn new(matrix: [[f32; N]; N]) -> Self {
+        let mut lu = matrix;
+        let mut pivots: [usize; N] = std::array::from_fn(|i| i);
+        let mut sign = 1.;
+        for k in 0..N {
+            let pivot = (k..N)
+                .max_by(|a, b| lu[*a][k].abs().total_cmp(&lu[*b][k].abs()))
+                .unwrap_or(k);
+            if pivot != k {
+                lu.swap(pivot, k);
+                pivots.swap(pivot, k);
+                sign = -sign;
+            }
+            if lu[k][k] == 0. {
+                continue;
+            }
+            let pivot_row = lu[k];
+            for row in lu.iter_mut().skip(k + 1) {
+                let factor = row[k] / pivot_row[k];
+                row[k] = factor;
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(k + 1) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+        Self {
+            lu,
+            pivots,
+            sign,
+            norm: norm_1(&matrix),
+        }
+    }
+
+    pub fn pivots(&self) -> [usize; N] {
+        self.pivots
+    }
+
+    pub fn det(&self) -> f32 {
+        (0..N).fold(self.sign, |det, i| det * self.lu[i][i])
+    }
+
+    // A pivot is treated as zero relative to the size of the matrix entries
+    pub fn is_singular(&self) -> bool {
+        let tolerance = f32::EPSILON * N as f32 * self.norm;
+        (0..N).any(|i| self.lu[i][i].abs() <= tolerance)
+    }
+
+    fn solve_array(&self, b: [f32; N]) -> [f32; N] {
+        let mut x: [f32; N] = std::array::from_fn(|i| b[self.pivots[i]]);
+        for i in 0..N {
+            for j in 0..i {
+                x[i] -= self.lu[i][j] * x[j];
+            }
+        }
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] -= self.lu[i][j] * x[j];
+            }
+            x[i] /= self.lu[i][i];
+        }
+        x
+    }
+
+    fn inverse_array(&self) -> [[f32; N]; N] {
+        let mut inverse = [[0.; N]; N];
+        for j in 0..N {
+            let mut e = [0.; N];
+            e[j] = 1.;
+            let column = self.solve_array(e);
+            for i in 0..N {
+                inverse[i][j] = column[i];
+            }
+        }
+        inverse
+    }
+
+    pub fn try_solve<V>(&self, b: V) -> Option<V>
+    where
+        V: From<[f32; N]> + Into<[f32; N]>,
+    {
+        if self.is_singular() {
+            return None;
+        }
+        Some(self.solve_array(b.into()).into())
+    }
+
+    pub fn solve<V>(&self, b: V) -> V
+    where
+        V: From<[f32; N]> + Into<[f32; N]>,
+    {
+        self.try_solve(b)
+            .expect("It is impossible to solve a system with a singular matrix")
+    }
+
+    pub fn try_inverse<M>(&self) -> Option<M>
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        if self.is_singular() {
+            return None;
+        }
+        Some(self.inverse_array().into())
+    }
+
+    pub fn inverse<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.try_inverse()
+            .expect("It is impossible to invert a singular matrix")
+    }
+
+    // 1-norm condition number ||A|| * ||A^-1||, infinite for singular matrices
+    pub fn condition_number(&self) -> f32 {
+        if self.is_singular() {
+            return f32::INFINITY;
+        }
+        self.norm * norm_1(&self.inverse_array())
+    }
+}
+
+fn norm_1<const N: usize>(matrix: &[[f32; N]; N]) -> f32 {
+    (0..N)
+        .map(|j| (0..N).map(|i| matrix[i][j].abs()).sum::<f32>())
+        .fold(0., f32::max)
+}
+
+impl Matrix2 {
+    pub fn lu(self) -> Lu<2> {
+        Lu::new(self.into())
+    }
+}
+impl Matrix3 {
+    pub fn lu(self) -> Lu<3> {
+        Lu::new(self.into())
+    }
+}
+impl Matrix4 {
+    pub fn lu(self) -> Lu<4> {
+        Lu::new(self.into())
+    }
+}
+impl<const N: usize> MatrixN<N> {
+    pub fn lu(self) -> Lu<N> {
+        Lu::new(self.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Matrix, Vector2, Vector3, Vector4};
+
+    // Method det()
+    #[test]
+    fn test1_ludet() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        assert!((mat.lu().det() - 1677.).abs() < EPSILON);
+    }
+    #[test]
+    fn test2_ludet() {
+        let mat = Matrix3::new([[0., 1., 0.], [1., 0., 0.], [0., 0., 1.]]);
+        assert!((mat.lu().det() + 1.).abs() < EPSILON);
+        assert_eq!(mat.lu().pivots(), [1, 0, 2]);
+    }
+    #[test]
+    fn test3_ludet() {
+        let mat = Matrix3::new([[2., 1., 11.], [7., 5., 0.], [-3., 1., 0.]]);
+        assert!((mat.lu().det() - mat.det()).abs() < EPSILON);
+    }
+    // Method solve(b)
+    #[test]
+    fn test1_lusolve() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        let x = Vector4::new(1., -2., 0.5, 3.);
+        assert_eq!(mat.lu().solve(mat * x), x);
+    }
+    #[test]
+    fn test2_lusolve() {
+        let mat = Matrix2::new([[0., 2.], [3., 1.]]);
+        assert_eq!(mat.lu().solve(Vector2::new(4., 5.)), Vector2::new(1., 2.));
+    }
+    #[test]
+    fn test3_lusolve() {
+        let mat = Matrix3::new([[1e-4, 2e-4, 0.], [0., 1e-4, 0.], [0., 0., 1e-4]]);
+        let x = Vector3::new(3., -1., 2.);
+        assert_eq!(mat.lu().solve(mat * x), x);
+    }
+    #[test]
+    #[should_panic]
+    fn test4_lusolve() {
+        let mat = Matrix3::new([[1., 2., 8.], [0., 2., 5.], [3., -4., -1.]]);
+        mat.lu().solve(Vector3::from(1.));
+    }
+    // Method inverse()
+    #[test]
+    fn test1_luinverse() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        let inverse: Matrix4 = mat.lu().inverse();
+        assert_eq!(inverse * mat, Matrix4::idenity());
+        assert_eq!(mat * inverse, Matrix4::idenity());
+    }
+    #[test]
+    fn test2_luinverse() {
+        let mat = Matrix3::new([[1., 2., 8.], [0., 2., 5.], [3., -4., -1.]]);
+        assert_eq!(mat.lu().try_inverse::<Matrix3>(), None);
+        assert!(mat.lu().is_singular());
+    }
+    // Method condition_number()
+    #[test]
+    fn test1_lucondition_number() {
+        assert!((Matrix4::idenity().lu().condition_number() - 1.).abs() < EPSILON);
+    }
+    #[test]
+    fn test2_lucondition_number() {
+        let mat = Matrix2::new([[1., 0.], [0., 1e-3]]);
+        assert!((mat.lu().condition_number() - 1e3).abs() < 1.);
+    }
+    #[test]
+    fn test3_lucondition_number() {
+        let mat = Matrix2::new([[1., 1.], [1., 1.]]);
+        assert_eq!(mat.lu().condition_number(), f32::INFINITY);
+    }
+}
@@ -1,11 +1,5 @@
-use super::Matrix;
-use crate::{
-    functions::{
-        constants::{EPSILON, PRECISION},
-        unpack,
-    },
-    Vector2,
-};
+use super::{mat3::Matrix3, matmxn::MatrixN, Matrix};
+use crate::{functions::constants::EPSILON, Vector2};
 use std::{
     fmt::Display,
     ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -31,13 +25,57 @@ impl Matrix2 {
         let Vector2 { x: x2, y: y2 } = vec2;
         Self::new([[x1, x2], [y1, y2]])
     }
+
+    // Upper-left block of a larger matrix
+    pub fn from_matrix3(matrix: Matrix3) -> Self {
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| matrix[(i, j)])
+        }))
+    }
+
+    pub fn row(self, i: usize) -> Vector2 {
+        self.data[i].into()
+    }
+    pub fn col(self, j: usize) -> Vector2 {
+        self.get_col(j).into()
+    }
+    pub fn set_row(&mut self, i: usize, row: Vector2) {
+        self.data[i] = row.into();
+    }
+    pub fn set_col(&mut self, j: usize, col: Vector2) {
+        for (row, value) in self.data.iter_mut().zip(<[f32; 2]>::from(col)) {
+            row[j] = value;
+        }
+    }
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+    }
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        for row in self.data.iter_mut() {
+            row.swap(a, b);
+        }
+    }
+
+    pub fn iter_rows(self) -> impl Iterator<Item = Vector2> {
+        self.data.into_iter().map(Vector2::from)
+    }
+    pub fn iter_cols(self) -> impl Iterator<Item = Vector2> {
+        (0..2).map(move |j| self.col(j))
+    }
+    // Elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+        self.data.iter().flatten()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.data.iter_mut().flatten()
+    }
 }
 impl Matrix for Matrix2 {
     type Row = [f32; 2];
     type Column = [f32; 2];
 
     fn scalar(value: f32) -> Self {
-        Self::new([[value, 0.], [0., value]])
+        MatrixN::<2>::scalar(value).into()
     }
 
     fn dim(self) -> usize {
@@ -54,7 +92,7 @@ impl Matrix for Matrix2 {
         [self[(0, j)], self[(1, j)]]
     }
     fn transpose(self) -> Self {
-        Self::new([[self[(0, 0)], self[(1, 0)]], [self[(0, 1)], self[(1, 1)]]])
+        MatrixN::from(self).transpose().into()
     }
 
     fn try_invert(self) -> Option<Self>
@@ -67,12 +105,26 @@ impl Matrix for Matrix2 {
         }
         Some(Self::new([[self[(1, 1)], -self[(0, 1)]], [-self[(1, 0)], self[(0, 0)]]]) / det)
     }
+
+    fn singular_values(self) -> Self::Row {
+        self.svd().singular_values()
+    }
 }
 impl From<f32> for Matrix2 {
     fn from(value: f32) -> Self {
         Self::new([[value; 2]; 2])
     }
 }
+impl From<[[f32; 2]; 2]> for Matrix2 {
+    fn from(value: [[f32; 2]; 2]) -> Self {
+        Self::new(value)
+    }
+}
+impl From<Matrix2> for [[f32; 2]; 2] {
+    fn from(value: Matrix2) -> Self {
+        value.data
+    }
+}
 impl Index<(usize, usize)> for Matrix2 {
     type Output = f32;
 
@@ -89,29 +141,7 @@ impl IndexMut<(usize, usize)> for Matrix2 {
 }
 impl Display for Matrix2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_len = unpack(self.data)
-            .iter()
-            .map(|comp| (*comp as i32).to_string().len())
-            .max()
-            .unwrap();
-        let space = " ".repeat(1 + (max_len + 1 + PRECISION + 1) * self.dim());
-        writeln!(f, "\n┌{space}┐").unwrap();
-        writeln!(
-            f,
-            "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |",
-            self[(0, 0)],
-            self[(0, 1)]
-        )
-        .unwrap();
-        writeln!(
-            f,
-            "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |",
-            self[(1, 0)],
-            self[(1, 1)]
-        )
-        .unwrap();
-        writeln!(f, "└{space}┘").unwrap();
-        Ok(())
+        MatrixN::from(*self).fmt(f)
     }
 }
 impl Neg for Matrix2 {
@@ -125,10 +155,7 @@ impl Add for Matrix2 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new([
-            [self[(0, 0)] + rhs[(0, 0)], self[(0, 1)] + rhs[(0, 1)]],
-            [self[(1, 0)] + rhs[(1, 0)], self[(1, 1)] + rhs[(1, 1)]],
-        ])
+        (MatrixN::from(self) + MatrixN::from(rhs)).into()
     }
 }
 impl AddAssign for Matrix2 {
@@ -152,15 +179,7 @@ impl Mul for Matrix2 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut res = Matrix2::default();
-        for i in 0..2 {
-            for j in 0..2 {
-                for k in 0..2 {
-                    res[(i, j)] += self[(i, k)] * rhs[(k, j)];
-                }
-            }
-        }
-        res
+        (MatrixN::from(self) * MatrixN::from(rhs)).into()
     }
 }
 impl MulAssign for Matrix2 {
@@ -172,10 +191,7 @@ impl Mul<f32> for Matrix2 {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::new([
-            [self[(0, 0)] * rhs, self[(0, 1)] * rhs],
-            [self[(1, 0)] * rhs, self[(1, 1)] * rhs],
-        ])
+        (MatrixN::from(self) * rhs).into()
     }
 }
 impl Mul<Matrix2> for f32 {
@@ -204,14 +220,7 @@ impl DivAssign<f32> for Matrix2 {
 }
 impl PartialEq for Matrix2 {
     fn eq(&self, other: &Self) -> bool {
-        let matrix: Matrix2 = *self - *other;
-        let mut sum = 0.;
-        for i in 0..2 {
-            for j in 0..2 {
-                sum += matrix[(i, j)] * matrix[(i, j)];
-            }
-        }
-        sum.sqrt() < EPSILON
+        MatrixN::from(*self) == MatrixN::from(*other)
     }
 }
 
@@ -238,6 +247,13 @@ mod tests {
         assert_eq!(mat, mat_new);
     }
     #[test]
+    fn test1_matrix2from_array() {
+        let data = [[1., 2.], [3., 4.]];
+        let mat = Matrix2::from(data);
+        assert_eq!(mat, Matrix2::new(data));
+        assert_eq!(<[[f32; 2]; 2]>::from(mat), data);
+    }
+    #[test]
     fn test1_matrix2idenity() {
         let mat = Matrix2 {
             data: [[1., 0.], [0., 1.]],
@@ -372,6 +388,77 @@ mod tests {
     fn test3_matrix2invert() {
         Matrix2::new([[3., 6.], [7., 14.]]).invert();
     }
+    // Methods row(i), col(j), set_row(i, vector), set_col(j, vector)
+    #[test]
+    fn test1_matrix2row() {
+        let mut mat = Matrix2::new([[1., 2.], [3., 4.]]);
+        assert_eq!(mat.row(1), Vector2::new(3., 4.));
+        assert_eq!(mat.col(1), Vector2::new(2., 4.));
+        mat.set_row(0, Vector2::new(-1., -2.));
+        mat.set_col(1, Vector2::new(5., 6.));
+        assert_eq!(mat, Matrix2::new([[-1., 5.], [3., 6.]]));
+    }
+    // Methods swap_rows(a, b), swap_cols(a, b)
+    #[test]
+    fn test1_matrix2swap_rows() {
+        let mut mat = Matrix2::new([[1., 2.], [3., 4.]]);
+        mat.swap_rows(0, 1);
+        assert_eq!(mat, Matrix2::new([[3., 4.], [1., 2.]]));
+        mat.swap_cols(1, 0);
+        assert_eq!(mat, Matrix2::new([[4., 3.], [2., 1.]]));
+    }
+    // Method from_matrix3(matrix)
+    #[test]
+    fn test1_matrix2from_matrix3() {
+        let mat = Matrix3::new([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
+        assert_eq!(
+            Matrix2::from_matrix3(mat),
+            Matrix2::new([[1., 2.], [4., 5.]])
+        );
+    }
+    // Methods iter_rows(), iter_cols(), iter(), iter_mut()
+    #[test]
+    fn test1_matrix2iter() {
+        let mut mat = Matrix2::new([[1., 2.], [3., 4.]]);
+        assert_eq!(
+            mat.iter().copied().collect::<Vec<_>>(),
+            vec![1., 2., 3., 4.]
+        );
+        assert_eq!(
+            mat.iter_cols().collect::<Vec<_>>(),
+            vec![Vector2::new(1., 3.), Vector2::new(2., 4.)]
+        );
+        for value in mat.iter_mut() {
+            *value *= 2.;
+        }
+        assert_eq!(
+            mat.iter_rows().collect::<Vec<_>>(),
+            vec![Vector2::new(2., 4.), Vector2::new(6., 8.)]
+        );
+    }
+    // Method trace()
+    #[test]
+    fn test1_matrix2trace() {
+        let mat = Matrix2::new([[3., 6.], [7., -14.]]);
+        assert_eq!(mat.trace(), -11.);
+    }
+    // Methods frobenius_norm(), one_norm(), infinity_norm()
+    #[test]
+    fn test1_matrix2norm() {
+        let mat = Matrix2::new([[1., -2.], [-3., 4.]]);
+        assert!((mat.frobenius_norm() - 30_f32.sqrt()).abs() < EPSILON);
+        assert_eq!(mat.one_norm(), 6.);
+        assert_eq!(mat.infinity_norm(), 7.);
+    }
+    // Methods is_orthogonal(), is_rotation()
+    #[test]
+    fn test1_matrix2is_rotation() {
+        let rotation = Matrix2::new([[0., -1.], [1., 0.]]);
+        let reflection = Matrix2::new([[0., 1.], [1., 0.]]);
+        assert!(rotation.is_orthogonal() && rotation.is_rotation());
+        assert!(reflection.is_orthogonal() && !reflection.is_rotation());
+        assert!(!(rotation * 2.).is_orthogonal());
+    }
     // Impl Index<(usize, usize)>
     #[test]
     fn test1_matrix2index_usize_usize() {
@@ -1,11 +1,5 @@
-use super::{mat2::Matrix2, Matrix};
-use crate::{
-    functions::{
-        constants::{EPSILON, PRECISION},
-        unpack,
-    },
-    Vector3,
-};
+use super::{mat2::Matrix2, mat4::Matrix4, matmxn::MatrixN, Matrix};
+use crate::{functions::constants::EPSILON, Vector3};
 use std::{
     fmt::Display,
     ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -57,13 +51,79 @@ impl Matrix3 {
         } = vec3;
         Self::new([[x1, x2, x3], [y1, y2, y3], [z1, z2, z3]])
     }
+
+    // Upper-left block is the given matrix, the rest is taken from the identity
+    pub fn from_matrix2(matrix: Matrix2) -> Self {
+        let mut res = Self::idenity();
+        for i in 0..2 {
+            for j in 0..2 {
+                res[(i, j)] = matrix[(i, j)];
+            }
+        }
+        res
+    }
+
+    // Upper-left block of a larger matrix
+    pub fn from_matrix4(matrix: Matrix4) -> Self {
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| matrix[(i, j)])
+        }))
+    }
+
+    pub fn row(self, i: usize) -> Vector3 {
+        self.data[i].into()
+    }
+    pub fn col(self, j: usize) -> Vector3 {
+        self.get_col(j).into()
+    }
+    pub fn set_row(&mut self, i: usize, row: Vector3) {
+        self.data[i] = row.into();
+    }
+    pub fn set_col(&mut self, j: usize, col: Vector3) {
+        for (row, value) in self.data.iter_mut().zip(<[f32; 3]>::from(col)) {
+            row[j] = value;
+        }
+    }
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+    }
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        for row in self.data.iter_mut() {
+            row.swap(a, b);
+        }
+    }
+
+    pub fn iter_rows(self) -> impl Iterator<Item = Vector3> {
+        self.data.into_iter().map(Vector3::from)
+    }
+    pub fn iter_cols(self) -> impl Iterator<Item = Vector3> {
+        (0..3).map(move |j| self.col(j))
+    }
+    // Elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+        self.data.iter().flatten()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.data.iter_mut().flatten()
+    }
+
+    // Copy without the given row and column
+    pub fn submatrix(self, row: usize, col: usize) -> Matrix2 {
+        Matrix2::new(std::array::from_fn(|i| {
+            let i = i + usize::from(i >= row);
+            std::array::from_fn(|j| self[(i, j + usize::from(j >= col))])
+        }))
+    }
+    pub fn minor(self, row: usize, col: usize) -> f32 {
+        self.submatrix(row, col).det()
+    }
 }
 impl Matrix for Matrix3 {
     type Row = [f32; 3];
     type Column = [f32; 3];
 
     fn scalar(value: f32) -> Self {
-        Self::new([[value, 0., 0.], [0., value, 0.], [0., 0., value]])
+        MatrixN::<3>::scalar(value).into()
     }
 
     fn dim(self) -> usize {
@@ -83,11 +143,7 @@ impl Matrix for Matrix3 {
     }
 
     fn transpose(self) -> Self {
-        Self::new([
-            [self[(0, 0)], self[(1, 0)], self[(2, 0)]],
-            [self[(0, 1)], self[(1, 1)], self[(2, 1)]],
-            [self[(0, 2)], self[(1, 2)], self[(2, 2)]],
-        ])
+        MatrixN::from(self).transpose().into()
     }
     fn try_invert(self) -> Option<Self>
     where
@@ -108,12 +164,26 @@ impl Matrix for Matrix3 {
         let a22 = Matrix2::new([[self[(0, 0)], self[(0, 1)]], [self[(1, 0)], self[(1, 1)]]]).det();
         Some(Self::new([[a00, -a10, a20], [-a01, a11, -a21], [a02, -a12, a22]]) / det)
     }
+
+    fn singular_values(self) -> Self::Row {
+        self.svd().singular_values()
+    }
 }
 impl From<f32> for Matrix3 {
     fn from(value: f32) -> Self {
         Self::new([[value; 3]; 3])
     }
 }
+impl From<[[f32; 3]; 3]> for Matrix3 {
+    fn from(value: [[f32; 3]; 3]) -> Self {
+        Self::new(value)
+    }
+}
+impl From<Matrix3> for [[f32; 3]; 3] {
+    fn from(value: Matrix3) -> Self {
+        value.data
+    }
+}
 impl Index<(usize, usize)> for Matrix3 {
     type Output = f32;
 
@@ -130,39 +200,7 @@ impl IndexMut<(usize, usize)> for Matrix3 {
 }
 impl Display for Matrix3 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_len = unpack(self.data)
-            .iter()
-            .map(|comp| (*comp as i32).to_string().len())
-            .max()
-            .unwrap();
-        let space = " ".repeat(1 + (max_len + 1 + PRECISION + 1) * self.dim());
-        writeln!(f, "\n┌{space}┐").unwrap();
-        writeln!(
-            f,
-            "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |",
-            self[(0, 0)],
-            self[(0, 1)],
-            self[(0, 2)]
-        )
-        .unwrap();
-        writeln!(
-            f,
-            "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |",
-            self[(1, 0)],
-            self[(1, 1)],
-            self[(1, 2)]
-        )
-        .unwrap();
-        writeln!(
-            f,
-            "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |",
-            self[(2, 0)],
-            self[(2, 1)],
-            self[(2, 2)]
-        )
-        .unwrap();
-        writeln!(f, "└{space}┘").unwrap();
-        Ok(())
+        MatrixN::from(*self).fmt(f)
     }
 }
 impl Neg for Matrix3 {
@@ -176,23 +214,7 @@ impl Add for Matrix3 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new([
-            [
-                self[(0, 0)] + rhs[(0, 0)],
-                self[(0, 1)] + rhs[(0, 1)],
-                self[(0, 2)] + rhs[(0, 2)],
-            ],
-            [
-                self[(1, 0)] + rhs[(1, 0)],
-                self[(1, 1)] + rhs[(1, 1)],
-                self[(1, 2)] + rhs[(1, 2)],
-            ],
-            [
-                self[(2, 0)] + rhs[(2, 0)],
-                self[(2, 1)] + rhs[(2, 1)],
-                self[(2, 2)] + rhs[(2, 2)],
-            ],
-        ])
+        (MatrixN::from(self) + MatrixN::from(rhs)).into()
     }
 }
 impl AddAssign for Matrix3 {
@@ -216,15 +238,7 @@ impl Mul for Matrix3 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut res = Matrix3::default();
-        for i in 0..3 {
-            for j in 0..3 {
-                for k in 0..3 {
-                    res[(i, j)] += self[(i, k)] * rhs[(k, j)];
-                }
-            }
-        }
-        res
+        (MatrixN::from(self) * MatrixN::from(rhs)).into()
     }
 }
 impl MulAssign for Matrix3 {
@@ -236,11 +250,7 @@ impl Mul<f32> for Matrix3 {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::new([
-            [self[(0, 0)] * rhs, self[(0, 1)] * rhs, self[(0, 2)] * rhs],
-            [self[(1, 0)] * rhs, self[(1, 1)] * rhs, self[(1, 2)] * rhs],
-            [self[(2, 0)] * rhs, self[(2, 1)] * rhs, self[(2, 2)] * rhs],
-        ])
+        (MatrixN::from(self) * rhs).into()
     }
 }
 impl Mul<Matrix3> for f32 {
@@ -269,14 +279,7 @@ impl DivAssign<f32> for Matrix3 {
 }
 impl PartialEq for Matrix3 {
     fn eq(&self, other: &Self) -> bool {
-        let matrix = *self - *other;
-        let mut sum = 0.;
-        for i in 0..3 {
-            for j in 0..3 {
-                sum += matrix[(i, j)] * matrix[(i, j)];
-            }
-        }
-        sum.sqrt() < EPSILON
+        MatrixN::from(*self) == MatrixN::from(*other)
     }
 }
 
@@ -303,6 +306,13 @@ mod tests {
         assert_eq!(mat, mat_new);
     }
     #[test]
+    fn test1_matrix3from_array() {
+        let data = [[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]];
+        let mat = Matrix3::from(data);
+        assert_eq!(mat, Matrix3::new(data));
+        assert_eq!(<[[f32; 3]; 3]>::from(mat), data);
+    }
+    #[test]
     fn test1_matrix3idenity() {
         let mat = Matrix3 {
             data: [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
@@ -441,6 +451,112 @@ mod tests {
     fn test3_matrix3invert() {
         Matrix3::new([[1., 2., 8.], [0., 2., 5.], [3., -4., -1.]]).invert();
     }
+    // Methods row(i), col(j), set_row(i, vector), set_col(j, vector)
+    #[test]
+    fn test1_matrix3row() {
+        let mat = Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]]);
+        assert_eq!(mat.row(2), Vector3::new(-8., -1., 0.));
+        assert_eq!(mat.col(1), Vector3::new(7., 4., -1.));
+        assert_eq!(<[f32; 3]>::from(mat.row(0)), mat.get_row(0));
+        assert_eq!(<[f32; 3]>::from(mat.col(2)), mat.get_col(2));
+    }
+    #[test]
+    fn test1_matrix3set_row() {
+        let mut mat = Matrix3::zero();
+        mat.set_row(1, Vector3::new(1., 2., 3.));
+        mat.set_col(2, Vector3::new(-1., -2., -3.));
+        assert_eq!(
+            mat,
+            Matrix3::new([[0., 0., -1.], [1., 2., -2.], [0., 0., -3.]])
+        );
+    }
+    // Methods swap_rows(a, b), swap_cols(a, b)
+    #[test]
+    fn test1_matrix3swap_rows() {
+        let mut mat = Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]]);
+        let det = mat.det();
+        mat.swap_rows(0, 2);
+        assert_eq!(mat.row(0), Vector3::new(-8., -1., 0.));
+        assert!((mat.det() + det).abs() < EPSILON);
+        mat.swap_cols(0, 1);
+        assert_eq!(mat.col(0), Vector3::new(-1., 4., 7.));
+        assert!((mat.det() - det).abs() < EPSILON);
+    }
+    // Methods submatrix(row, col), minor(row, col)
+    #[test]
+    fn test1_matrix3submatrix() {
+        let mat = Matrix3::new([[1., 2., 3.], [4., 5., 6.], [7., 8., 10.]]);
+        assert_eq!(mat.submatrix(1, 1), Matrix2::new([[1., 3.], [7., 10.]]));
+        assert_eq!(mat.submatrix(0, 2), Matrix2::new([[4., 5.], [7., 8.]]));
+        assert_eq!(mat.minor(2, 0), -3.);
+    }
+    // Methods from_matrix2(matrix), from_matrix4(matrix)
+    #[test]
+    fn test1_matrix3from_matrix2() {
+        let mat = Matrix2::new([[1., 2.], [3., 4.]]);
+        assert_eq!(
+            Matrix3::from_matrix2(mat),
+            Matrix3::new([[1., 2., 0.], [3., 4., 0.], [0., 0., 1.]])
+        );
+        let homogeneous = crate::transform::homogeneous::rotate3(0.3, -1.2, 2.);
+        assert_eq!(
+            Matrix3::from_matrix4(homogeneous),
+            crate::transform::rotate3(0.3, -1.2, 2.)
+        );
+    }
+    // Methods iter_rows(), iter_cols(), iter(), iter_mut()
+    #[test]
+    fn test1_matrix3iter() {
+        let mat = Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]]);
+        assert_eq!(mat.iter().sum::<f32>(), 1.);
+        let rows: Vec<Vector3> = mat.iter_rows().collect();
+        assert_eq!(Matrix3::from_rows(rows[0], rows[1], rows[2]), mat);
+        let cols: Vec<Vector3> = mat.iter_cols().collect();
+        assert_eq!(Matrix3::from_cols(cols[0], cols[1], cols[2]), mat);
+        let mut abs = mat;
+        abs.iter_mut().for_each(|value| *value = value.abs());
+        assert_eq!(abs.iter().sum::<f32>(), 25.);
+    }
+    // Method trace()
+    #[test]
+    fn test1_matrix3trace() {
+        let mat = Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]]);
+        assert_eq!(mat.trace(), 5.);
+        assert_eq!(Matrix3::idenity().trace(), 3.);
+    }
+    // Methods frobenius_norm(), one_norm(), infinity_norm()
+    #[test]
+    fn test1_matrix3norm() {
+        let mat = Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]]);
+        assert!((mat.frobenius_norm() - 141_f32.sqrt()).abs() < EPSILON);
+        assert_eq!(mat.one_norm(), 12.);
+        assert_eq!(mat.infinity_norm(), 9.);
+        assert_eq!(mat.transpose().infinity_norm(), mat.one_norm());
+    }
+    // Method is_symmetric()
+    #[test]
+    fn test1_matrix3is_symmetric() {
+        let mat = Matrix3::new([[4., 1., -2.], [1., 2., 0.], [-2., 0., 3.]]);
+        assert!(mat.is_symmetric());
+        assert!(!Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]]).is_symmetric());
+    }
+    // Methods is_orthogonal(), is_rotation()
+    #[test]
+    fn test1_matrix3is_rotation() {
+        let rotation = crate::transform::rotate3(0.3, -1.2, 2.);
+        assert!(rotation.is_orthogonal());
+        assert!(rotation.is_rotation());
+        assert!(!(-rotation).is_rotation());
+        assert!((-rotation).is_orthogonal());
+        assert!(!(rotation * 1.1).is_orthogonal());
+    }
+    // Method is_affine()
+    #[test]
+    fn test1_matrix3is_affine() {
+        let mat = Matrix3::new([[2., 1., 5.], [0., 3., -1.], [0., 0., 1.]]);
+        assert!(mat.is_affine());
+        assert!(!Matrix3::new([[2., 1., 5.], [0., 3., -1.], [0., 1., 1.]]).is_affine());
+    }
     // // Impl Index<(usize, usize)>
     //     #[test]
     //     fn test1_matrix3index_usize_usize() {
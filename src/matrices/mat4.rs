@@ -1,11 +1,5 @@
-use super::{mat3::Matrix3, Matrix};
-use crate::{
-    functions::{
-        constants::{EPSILON, PRECISION},
-        unpack,
-    },
-    Vector4,
-};
+use super::{mat3::Matrix3, matmxn::MatrixN, Matrix};
+use crate::Vector4;
 use std::{
     fmt::Display,
     ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -85,52 +79,79 @@ impl Matrix4 {
             [w1, w2, w3, w4],
         ])
     }
+
+    // Upper-left block is the given matrix, the rest is taken from the identity
+    pub fn from_matrix3(matrix: Matrix3) -> Self {
+        let mut res = Self::idenity();
+        for i in 0..3 {
+            for j in 0..3 {
+                res[(i, j)] = matrix[(i, j)];
+            }
+        }
+        res
+    }
+
+    pub fn row(self, i: usize) -> Vector4 {
+        self.data[i].into()
+    }
+    pub fn col(self, j: usize) -> Vector4 {
+        self.get_col(j).into()
+    }
+    pub fn set_row(&mut self, i: usize, row: Vector4) {
+        self.data[i] = row.into();
+    }
+    pub fn set_col(&mut self, j: usize, col: Vector4) {
+        for (row, value) in self.data.iter_mut().zip(<[f32; 4]>::from(col)) {
+            row[j] = value;
+        }
+    }
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+    }
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        for row in self.data.iter_mut() {
+            row.swap(a, b);
+        }
+    }
+
+    pub fn iter_rows(self) -> impl Iterator<Item = Vector4> {
+        self.data.into_iter().map(Vector4::from)
+    }
+    pub fn iter_cols(self) -> impl Iterator<Item = Vector4> {
+        (0..4).map(move |j| self.col(j))
+    }
+    // Elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+        self.data.iter().flatten()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.data.iter_mut().flatten()
+    }
+
+    // Copy without the given row and column
+    pub fn submatrix(self, row: usize, col: usize) -> Matrix3 {
+        Matrix3::new(std::array::from_fn(|i| {
+            let i = i + usize::from(i >= row);
+            std::array::from_fn(|j| self[(i, j + usize::from(j >= col))])
+        }))
+    }
+    pub fn minor(self, row: usize, col: usize) -> f32 {
+        self.submatrix(row, col).det()
+    }
 }
 impl Matrix for Matrix4 {
     type Row = [f32; 4];
     type Column = [f32; 4];
 
     fn scalar(value: f32) -> Self {
-        Self::new([
-            [value, 0., 0., 0.],
-            [0., value, 0., 0.],
-            [0., 0., value, 0.],
-            [0., 0., 0., value],
-        ])
+        MatrixN::<4>::scalar(value).into()
     }
 
     fn dim(self) -> usize {
         4
     }
     fn det(self) -> f32 {
-        self[(0, 0)]
-            * Matrix3::new([
-                [self[(1, 1)], self[(1, 2)], self[(1, 3)]],
-                [self[(2, 1)], self[(2, 2)], self[(2, 3)]],
-                [self[(3, 1)], self[(3, 2)], self[(3, 3)]],
-            ])
-            .det()
-            - self[(0, 1)]
-                * Matrix3::new([
-                    [self[(1, 0)], self[(1, 2)], self[(1, 3)]],
-                    [self[(2, 0)], self[(2, 2)], self[(2, 3)]],
-                    [self[(3, 0)], self[(3, 2)], self[(3, 3)]],
-                ])
-                .det()
-            + self[(0, 2)]
-                * Matrix3::new([
-                    [self[(1, 0)], self[(1, 1)], self[(1, 3)]],
-                    [self[(2, 0)], self[(2, 1)], self[(2, 3)]],
-                    [self[(3, 0)], self[(3, 1)], self[(3, 3)]],
-                ])
-                .det()
-            - self[(0, 3)]
-                * Matrix3::new([
-                    [self[(1, 0)], self[(1, 1)], self[(1, 2)]],
-                    [self[(2, 0)], self[(2, 1)], self[(2, 2)]],
-                    [self[(3, 0)], self[(3, 1)], self[(3, 2)]],
-                ])
-                .det()
+        self.lu().det()
     }
 
     fn get_row(self, i: usize) -> Self::Row {
@@ -141,126 +162,19 @@ impl Matrix for Matrix4 {
     }
 
     fn transpose(self) -> Self {
-        Self::new([
-            [self[(0, 0)], self[(1, 0)], self[(2, 0)], self[(3, 0)]],
-            [self[(0, 1)], self[(1, 1)], self[(2, 1)], self[(3, 1)]],
-            [self[(0, 2)], self[(1, 2)], self[(2, 2)], self[(3, 2)]],
-            [self[(0, 3)], self[(1, 3)], self[(2, 3)], self[(3, 3)]],
-        ])
+        MatrixN::from(self).transpose().into()
     }
 
     fn try_invert(self) -> Option<Self>
     where
         Self: Sized,
     {
-        let det = self.det();
-        if det.abs() < EPSILON {
-            return None;
-        }
-        let a00 = Matrix3::new([
-            [self[(1, 1)], self[(1, 2)], self[(1, 3)]],
-            [self[(2, 1)], self[(2, 2)], self[(2, 3)]],
-            [self[(3, 1)], self[(3, 2)], self[(3, 3)]],
-        ])
-        .det();
-        let a01 = Matrix3::new([
-            [self[(1, 0)], self[(1, 2)], self[(1, 3)]],
-            [self[(2, 0)], self[(2, 2)], self[(2, 3)]],
-            [self[(3, 0)], self[(3, 2)], self[(3, 3)]],
-        ])
-        .det();
-        let a02 = Matrix3::new([
-            [self[(1, 0)], self[(1, 1)], self[(1, 3)]],
-            [self[(2, 0)], self[(2, 1)], self[(2, 3)]],
-            [self[(3, 0)], self[(3, 1)], self[(3, 3)]],
-        ])
-        .det();
-        let a03 = Matrix3::new([
-            [self[(1, 0)], self[(1, 1)], self[(1, 2)]],
-            [self[(2, 0)], self[(2, 1)], self[(2, 2)]],
-            [self[(3, 0)], self[(3, 1)], self[(3, 2)]],
-        ])
-        .det();
-        let a10 = Matrix3::new([
-            [self[(0, 1)], self[(0, 2)], self[(0, 3)]],
-            [self[(2, 1)], self[(2, 2)], self[(2, 3)]],
-            [self[(3, 1)], self[(3, 2)], self[(3, 3)]],
-        ])
-        .det();
-        let a11 = Matrix3::new([
-            [self[(0, 0)], self[(0, 2)], self[(0, 3)]],
-            [self[(2, 0)], self[(2, 2)], self[(2, 3)]],
-            [self[(3, 0)], self[(3, 2)], self[(3, 3)]],
-        ])
-        .det();
-        let a12 = Matrix3::new([
-            [self[(0, 0)], self[(0, 1)], self[(0, 3)]],
-            [self[(2, 0)], self[(2, 1)], self[(2, 3)]],
-            [self[(3, 0)], self[(3, 1)], self[(3, 3)]],
-        ])
-        .det();
-        let a13 = Matrix3::new([
-            [self[(0, 0)], self[(0, 1)], self[(0, 2)]],
-            [self[(2, 0)], self[(2, 1)], self[(2, 2)]],
-            [self[(3, 0)], self[(3, 1)], self[(3, 2)]],
-        ])
-        .det();
-        let a20 = Matrix3::new([
-            [self[(0, 1)], self[(0, 2)], self[(0, 3)]],
-            [self[(1, 1)], self[(1, 2)], self[(1, 3)]],
-            [self[(3, 1)], self[(3, 2)], self[(3, 3)]],
-        ])
-        .det();
-        let a21 = Matrix3::new([
-            [self[(0, 0)], self[(0, 2)], self[(0, 3)]],
-            [self[(1, 0)], self[(1, 2)], self[(1, 3)]],
-            [self[(3, 0)], self[(3, 2)], self[(3, 3)]],
-        ])
-        .det();
-        let a22 = Matrix3::new([
-            [self[(0, 0)], self[(0, 1)], self[(0, 3)]],
-            [self[(1, 0)], self[(1, 1)], self[(1, 3)]],
-            [self[(3, 0)], self[(3, 1)], self[(3, 3)]],
-        ])
-        .det();
-        let a23 = Matrix3::new([
-            [self[(0, 0)], self[(0, 1)], self[(0, 2)]],
-            [self[(1, 0)], self[(1, 1)], self[(1, 2)]],
-            [self[(3, 0)], self[(3, 1)], self[(3, 2)]],
-        ])
-        .det();
-        let a30 = Matrix3::new([
-            [self[(0, 1)], self[(0, 2)], self[(0, 3)]],
-            [self[(1, 1)], self[(1, 2)], self[(1, 3)]],
-            [self[(2, 1)], self[(2, 2)], self[(2, 3)]],
-        ])
-        .det();
-        let a31 = Matrix3::new([
-            [self[(0, 0)], self[(0, 2)], self[(0, 3)]],
-            [self[(1, 0)], self[(1, 2)], self[(1, 3)]],
-            [self[(2, 0)], self[(2, 2)], self[(2, 3)]],
-        ])
-        .det();
-        let a32 = Matrix3::new([
-            [self[(0, 0)], self[(0, 1)], self[(0, 3)]],
-            [self[(1, 0)], self[(1, 1)], self[(1, 3)]],
-            [self[(2, 0)], self[(2, 1)], self[(2, 3)]],
-        ])
-        .det();
-        let a33 = Matrix3::new([
-            [self[(0, 0)], self[(0, 1)], self[(0, 2)]],
-            [self[(1, 0)], self[(1, 1)], self[(1, 2)]],
-            [self[(2, 0)], self[(2, 1)], self[(2, 2)]],
-        ])
-        .det();
-        Some(
-            Self::new([
-                [a00, -a10, a20, -a30],
-                [-a01, a11, -a21, a31],
-                [a02, -a12, a22, -a32],
-                [-a03, a13, -a23, a33],
-            ]) / det,
-        )
+        // Singularity is judged by the pivots relative to the matrix norm, not by an absolute det
+        self.lu().try_inverse()
+    }
+
+    fn singular_values(self) -> Self::Row {
+        self.svd().singular_values()
     }
 }
 impl From<f32> for Matrix4 {
@@ -268,6 +182,16 @@ impl From<f32> for Matrix4 {
         Self::new([[value; 4]; 4])
     }
 }
+impl From<[[f32; 4]; 4]> for Matrix4 {
+    fn from(value: [[f32; 4]; 4]) -> Self {
+        Self::new(value)
+    }
+}
+impl From<Matrix4> for [[f32; 4]; 4] {
+    fn from(value: Matrix4) -> Self {
+        value.data
+    }
+}
 impl Index<(usize, usize)> for Matrix4 {
     type Output = f32;
 
@@ -284,19 +208,7 @@ impl IndexMut<(usize, usize)> for Matrix4 {
 }
 impl Display for Matrix4 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_len = unpack(self.data)
-            .iter()
-            .map(|comp| (*comp as i32).to_string().len())
-            .max()
-            .unwrap();
-        let space = " ".repeat(1 + (max_len + 1 + PRECISION + 1) * self.dim());
-        writeln!(f, "\n┌{space}┐").unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |", self[(0, 0)], self[(0, 1)], self[(0, 2)], self[(0, 3)]).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |", self[(1, 0)], self[(1, 1)], self[(1, 2)], self[(1, 3)]).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |", self[(2, 0)], self[(2, 1)], self[(2, 2)], self[(2, 3)]).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} {:>max_len$.PRECISION$} |", self[(3, 0)], self[(3, 1)], self[(3, 2)], self[(3, 3)]).unwrap();
-        writeln!(f, "└{space}┘").unwrap();
-        Ok(())
+        MatrixN::from(*self).fmt(f)
     }
 }
 impl Neg for Matrix4 {
@@ -310,32 +222,7 @@ impl Add for Matrix4 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new([
-            [
-                self[(0, 0)] + rhs[(0, 0)],
-                self[(0, 1)] + rhs[(0, 1)],
-                self[(0, 2)] + rhs[(0, 2)],
-                self[(0, 3)] + rhs[(0, 3)],
-            ],
-            [
-                self[(1, 0)] + rhs[(1, 0)],
-                self[(1, 1)] + rhs[(1, 1)],
-                self[(1, 2)] + rhs[(1, 2)],
-                self[(1, 3)] + rhs[(1, 3)],
-            ],
-            [
-                self[(2, 0)] + rhs[(2, 0)],
-                self[(2, 1)] + rhs[(2, 1)],
-                self[(2, 2)] + rhs[(2, 2)],
-                self[(2, 3)] + rhs[(2, 3)],
-            ],
-            [
-                self[(3, 0)] + rhs[(3, 0)],
-                self[(3, 1)] + rhs[(3, 1)],
-                self[(3, 2)] + rhs[(3, 2)],
-                self[(3, 3)] + rhs[(3, 3)],
-            ],
-        ])
+        (MatrixN::from(self) + MatrixN::from(rhs)).into()
     }
 }
 impl AddAssign for Matrix4 {
@@ -359,15 +246,7 @@ impl Mul for Matrix4 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut res = Matrix4::default();
-        for i in 0..4 {
-            for j in 0..4 {
-                for k in 0..4 {
-                    res[(i, j)] += self[(i, k)] * rhs[(k, j)];
-                }
-            }
-        }
-        res
+        (MatrixN::from(self) * MatrixN::from(rhs)).into()
     }
 }
 impl MulAssign for Matrix4 {
@@ -379,32 +258,7 @@ impl Mul<f32> for Matrix4 {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::new([
-            [
-                self[(0, 0)] * rhs,
-                self[(0, 1)] * rhs,
-                self[(0, 2)] * rhs,
-                self[(0, 3)] * rhs,
-            ],
-            [
-                self[(1, 0)] * rhs,
-                self[(1, 1)] * rhs,
-                self[(1, 2)] * rhs,
-                self[(1, 3)] * rhs,
-            ],
-            [
-                self[(2, 0)] * rhs,
-                self[(2, 1)] * rhs,
-                self[(2, 2)] * rhs,
-                self[(2, 3)] * rhs,
-            ],
-            [
-                self[(3, 0)] * rhs,
-                self[(3, 1)] * rhs,
-                self[(3, 2)] * rhs,
-                self[(3, 3)] * rhs,
-            ],
-        ])
+        (MatrixN::from(self) * rhs).into()
     }
 }
 impl Mul<Matrix4> for f32 {
@@ -433,21 +287,14 @@ impl DivAssign<f32> for Matrix4 {
 }
 impl PartialEq for Matrix4 {
     fn eq(&self, other: &Self) -> bool {
-        let matrix = *self - *other;
-        let mut sum = 0.;
-        for i in 0..4 {
-            for j in 0..4 {
-                sum += matrix[(i, j)] * matrix[(i, j)];
-            }
-        }
-        sum.sqrt() < EPSILON
+        MatrixN::from(*self) == MatrixN::from(*other)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::functions::constants::PI;
+    use crate::functions::constants::{EPSILON, PI};
 
     // Constructors
     #[test]
@@ -482,6 +329,18 @@ mod tests {
         assert_eq!(mat, mat_new);
     }
     #[test]
+    fn test1_matrix4from_array() {
+        let data = [
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [9., 10., 11., 12.],
+            [13., 14., 15., 16.],
+        ];
+        let mat = Matrix4::from(data);
+        assert_eq!(mat, Matrix4::new(data));
+        assert_eq!(<[[f32; 4]; 4]>::from(mat), data);
+    }
+    #[test]
     fn test1_matrix4idenity() {
         let mat = Matrix4 {
             data: [
@@ -722,6 +581,107 @@ mod tests {
         ])
         .invert();
     }
+    #[test]
+    fn test4_matrix4invert() {
+        // A small determinant alone does not make a well-conditioned matrix singular
+        let mat = Matrix4::scalar(0.1);
+        assert_eq!(mat.try_invert(), Some(Matrix4::scalar(10.)));
+    }
+    // Methods row(i), col(j), set_row(i, vector), set_col(j, vector)
+    #[test]
+    fn test1_matrix4row() {
+        use crate::{transform::homogeneous, Vector3};
+        let mut mat = homogeneous::translate3(Vector3::new(1., 2., 3.));
+        assert_eq!(mat.col(3), Vector4::new(1., 2., 3., 1.));
+        assert_eq!(mat.row(3), Vector4::new(0., 0., 0., 1.));
+        mat.set_col(3, Vector4::new(0., 0., 0., 1.));
+        assert_eq!(mat, Matrix4::idenity());
+        mat.set_row(0, Vector4::new(2., 0., 0., 0.));
+        assert_eq!(mat, homogeneous::scale3(Vector3::new(2., 1., 1.)));
+    }
+    // Methods swap_rows(a, b), swap_cols(a, b)
+    #[test]
+    fn test1_matrix4swap_rows() {
+        let mut mat = Matrix4::idenity();
+        mat.swap_rows(0, 3);
+        mat.swap_cols(0, 3);
+        assert_eq!(mat, Matrix4::idenity());
+        mat.swap_rows(1, 2);
+        assert_eq!(mat.row(1), Vector4::new(0., 0., 1., 0.));
+    }
+    // Methods submatrix(row, col), minor(row, col), from_matrix3(matrix)
+    #[test]
+    fn test1_matrix4submatrix() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        assert_eq!(
+            mat.submatrix(3, 3),
+            Matrix3::new([[1., 7., 0.], [-3., 4., 1.], [-8., -1., 0.]])
+        );
+        assert_eq!(
+            mat.submatrix(0, 1),
+            Matrix3::new([[-3., 1., 0.], [-8., 0., 11.], [1., -3., 0.]])
+        );
+        let cofactor_expansion = (0..4)
+            .map(|j| if j % 2 == 0 { 1. } else { -1. } * mat[(0, j)] * mat.minor(0, j))
+            .sum::<f32>();
+        assert!((cofactor_expansion - mat.det()).abs() < EPSILON);
+        let upper_left = mat.submatrix(3, 3);
+        assert_eq!(
+            Matrix4::from_matrix3(upper_left).submatrix(3, 3),
+            upper_left
+        );
+        assert_eq!(
+            Matrix4::from_matrix3(Matrix3::idenity()),
+            Matrix4::idenity()
+        );
+    }
+    // Methods iter_rows(), iter_cols(), iter(), iter_mut()
+    #[test]
+    fn test1_matrix4iter() {
+        let mut mat = Matrix4::scalar(2.);
+        assert_eq!(mat.iter().filter(|value| **value != 0.).count(), 4);
+        mat.iter_mut().for_each(|value| *value += 1.);
+        assert!(mat
+            .iter_rows()
+            .all(|row| row.x + row.y + row.z + row.w == 6.));
+        assert!(mat
+            .iter_cols()
+            .all(|col| col.x + col.y + col.z + col.w == 6.));
+    }
+    // Method trace()
+    #[test]
+    fn test1_matrix4trace() {
+        assert_eq!(Matrix4::scalar(PI).trace(), 4. * PI);
+    }
+    // Methods frobenius_norm(), one_norm(), infinity_norm()
+    #[test]
+    fn test1_matrix4norm() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        assert!((mat.frobenius_norm() - 330_f32.sqrt()).abs() < EPSILON);
+        assert_eq!(mat.one_norm(), 18.);
+        assert_eq!(mat.infinity_norm(), 20.);
+    }
+    // Methods is_rotation(), is_affine()
+    #[test]
+    fn test1_matrix4is_affine() {
+        use crate::{transform::homogeneous, Vector3};
+        let rotation = homogeneous::rotate3(0.3, -1.2, 2.);
+        let transform = homogeneous::translate3(Vector3::new(1., 2., 3.)) * rotation;
+        assert!(rotation.is_rotation() && rotation.is_affine());
+        assert!(transform.is_affine() && !transform.is_orthogonal());
+        let projection = homogeneous::perspective3(100., 0.1, 1.5, PI / 3.);
+        assert!(!projection.is_affine());
+    }
     // // // Impl Index<(usize, usize)>
     // //     #[test]
     // //     fn test1_matrix4index_usize_usize() {
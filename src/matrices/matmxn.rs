@@ -0,0 +1,608 @@
+use super::{lu::Lu, mat2::Matrix2, mat3::Matrix3, mat4::Matrix4, Matrix};
+use crate::{
+    functions::constants::{EPSILON, PRECISION},
+    Vector2, Vector3, Vector4, VectorN,
+};
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+// Matrix with M rows and N columns, products are dimension checked at compile time
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixMxN<const M: usize, const N: usize> {
+    data: [[f32; N]; M],
+}
+pub type Matrix2x3 = MatrixMxN<2, 3>;
+pub type Matrix2x4 = MatrixMxN<2, 4>;
+pub type Matrix3x2 = MatrixMxN<3, 2>;
+pub type Matrix3x4 = MatrixMxN<3, 4>;
+pub type Matrix4x2 = MatrixMxN<4, 2>;
+pub type Matrix4x3 = MatrixMxN<4, 3>;
+// Square matrix of any size, Matrix2/3/4 convert losslessly to and from MatrixN<2/3/4>
+pub type MatrixN<const N: usize> = MatrixMxN<N, N>;
+
+impl<const M: usize, const N: usize> MatrixMxN<M, N> {
+    pub fn new(data: [[f32; N]; M]) -> Self {
+        Self { data }
+    }
+
+    pub fn zero() -> Self {
+        Self::new([[0.; N]; M])
+    }
+
+    pub fn from_rows<V>(rows: [V; M]) -> Self
+    where
+        V: Into<[f32; N]>,
+    {
+        Self::new(rows.map(Into::into))
+    }
+
+    pub fn from_cols<V>(cols: [V; N]) -> Self
+    where
+        V: Into<[f32; M]>,
+    {
+        let cols = cols.map(Into::into);
+        Self::new(std::array::from_fn(|i| std::array::from_fn(|j| cols[j][i])))
+    }
+
+    // Outer product a * b^T, e.g. Vector3 and Vector4 give a Matrix3x4
+    pub fn outer<A, B>(a: A, b: B) -> Self
+    where
+        A: Into<[f32; M]>,
+        B: Into<[f32; N]>,
+    {
+        let (a, b) = (a.into(), b.into());
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| a[i] * b[j])
+        }))
+    }
+
+    pub fn rows(self) -> usize {
+        M
+    }
+    pub fn cols(self) -> usize {
+        N
+    }
+
+    pub fn get_row(self, i: usize) -> [f32; N] {
+        self.data[i]
+    }
+    pub fn get_col(self, j: usize) -> [f32; M] {
+        self.data.map(|row| row[j])
+    }
+
+    pub fn transpose(self) -> MatrixMxN<N, M> {
+        MatrixMxN::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self[(j, i)])
+        }))
+    }
+}
+impl<const N: usize> Matrix for MatrixMxN<N, N> {
+    type Row = [f32; N];
+    type Column = [f32; N];
+
+    fn scalar(value: f32) -> Self {
+        let mut res = Self::zero();
+        for i in 0..N {
+            res[(i, i)] = value;
+        }
+        res
+    }
+
+    fn dim(self) -> usize {
+        N
+    }
+    fn det(self) -> f32 {
+        Lu::new(self.data).det()
+    }
+
+    fn get_row(self, i: usize) -> Self::Row {
+        self.data[i]
+    }
+    fn get_col(self, j: usize) -> Self::Column {
+        self.data.map(|row| row[j])
+    }
+
+    fn transpose(self) -> Self {
+        MatrixMxN::transpose(self)
+    }
+    fn try_invert(self) -> Option<Self> {
+        Lu::new(self.data).try_inverse()
+    }
+
+    fn singular_values(self) -> Self::Row {
+        self.svd().singular_values()
+    }
+}
+impl<const M: usize, const N: usize> Default for MatrixMxN<M, N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+impl<const M: usize, const N: usize> From<f32> for MatrixMxN<M, N> {
+    fn from(value: f32) -> Self {
+        Self::new([[value; N]; M])
+    }
+}
+impl<const M: usize, const N: usize> From<[[f32; N]; M]> for MatrixMxN<M, N> {
+    fn from(data: [[f32; N]; M]) -> Self {
+        Self::new(data)
+    }
+}
+impl<const M: usize, const N: usize> From<MatrixMxN<M, N>> for [[f32; N]; M] {
+    fn from(matrix: MatrixMxN<M, N>) -> Self {
+        matrix.data
+    }
+}
+impl From<Matrix2> for MatrixMxN<2, 2> {
+    fn from(matrix: Matrix2) -> Self {
+        Self::new(matrix.into())
+    }
+}
+impl From<MatrixMxN<2, 2>> for Matrix2 {
+    fn from(matrix: MatrixMxN<2, 2>) -> Self {
+        Self::new(matrix.data)
+    }
+}
+impl From<Matrix3> for MatrixMxN<3, 3> {
+    fn from(matrix: Matrix3) -> Self {
+        Self::new(matrix.into())
+    }
+}
+impl From<MatrixMxN<3, 3>> for Matrix3 {
+    fn from(matrix: MatrixMxN<3, 3>) -> Self {
+        Self::new(matrix.data)
+    }
+}
+impl From<Matrix4> for MatrixMxN<4, 4> {
+    fn from(matrix: Matrix4) -> Self {
+        Self::new(matrix.into())
+    }
+}
+impl From<MatrixMxN<4, 4>> for Matrix4 {
+    fn from(matrix: MatrixMxN<4, 4>) -> Self {
+        Self::new(matrix.data)
+    }
+}
+impl<const M: usize, const N: usize> Index<(usize, usize)> for MatrixMxN<M, N> {
+    type Output = f32;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.data[index.0][index.1]
+    }
+}
+impl<const M: usize, const N: usize> IndexMut<(usize, usize)> for MatrixMxN<M, N> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.data[index.0][index.1]
+    }
+}
+impl<const M: usize, const N: usize> Display for MatrixMxN<M, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let max_len = self
+            .data
+            .iter()
+            .flatten()
+            .map(|comp| (*comp as i32).to_string().len())
+            .max()
+            .unwrap_or(1);
+        let space = " ".repeat(1 + (max_len + 1 + PRECISION + 1) * N);
+        writeln!(f, "\n┌{space}┐")?;
+        for row in self.data {
+            write!(f, "|")?;
+            for comp in row {
+                write!(f, " {:>max_len$.PRECISION$}", comp)?;
+            }
+            writeln!(f, " |")?;
+        }
+        writeln!(f, "└{space}┘")
+    }
+}
+impl<const M: usize, const N: usize> Neg for MatrixMxN<M, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1.
+    }
+}
+impl<const M: usize, const N: usize> Add for MatrixMxN<M, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self[(i, j)] + rhs[(i, j)])
+        }))
+    }
+}
+impl<const M: usize, const N: usize> AddAssign for MatrixMxN<M, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<const M: usize, const N: usize> Sub for MatrixMxN<M, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+impl<const M: usize, const N: usize> SubAssign for MatrixMxN<M, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+impl<const M: usize, const N: usize, const P: usize> Mul<MatrixMxN<N, P>> for MatrixMxN<M, N> {
+    type Output = MatrixMxN<M, P>;
+
+    fn mul(self, rhs: MatrixMxN<N, P>) -> Self::Output {
+        let mut res = MatrixMxN::zero();
+        for i in 0..M {
+            for j in 0..P {
+                for k in 0..N {
+                    res[(i, j)] += self[(i, k)] * rhs[(k, j)];
+                }
+            }
+        }
+        res
+    }
+}
+impl<const M: usize> Mul<Matrix2> for MatrixMxN<M, 2> {
+    type Output = Self;
+
+    fn mul(self, rhs: Matrix2) -> Self::Output {
+        self * MatrixMxN::from(rhs)
+    }
+}
+impl<const M: usize> MulAssign<Matrix2> for MatrixMxN<M, 2> {
+    fn mul_assign(&mut self, rhs: Matrix2) {
+        *self = *self * rhs;
+    }
+}
+impl<const P: usize> Mul<MatrixMxN<2, P>> for Matrix2 {
+    type Output = MatrixMxN<2, P>;
+
+    fn mul(self, rhs: MatrixMxN<2, P>) -> Self::Output {
+        MatrixMxN::from(self) * rhs
+    }
+}
+impl<const M: usize> Mul<Matrix3> for MatrixMxN<M, 3> {
+    type Output = Self;
+
+    fn mul(self, rhs: Matrix3) -> Self::Output {
+        self * MatrixMxN::from(rhs)
+    }
+}
+impl<const M: usize> MulAssign<Matrix3> for MatrixMxN<M, 3> {
+    fn mul_assign(&mut self, rhs: Matrix3) {
+        *self = *self * rhs;
+    }
+}
+impl<const P: usize> Mul<MatrixMxN<3, P>> for Matrix3 {
+    type Output = MatrixMxN<3, P>;
+
+    fn mul(self, rhs: MatrixMxN<3, P>) -> Self::Output {
+        MatrixMxN::from(self) * rhs
+    }
+}
+impl<const M: usize> Mul<Matrix4> for MatrixMxN<M, 4> {
+    type Output = Self;
+
+    fn mul(self, rhs: Matrix4) -> Self::Output {
+        self * MatrixMxN::from(rhs)
+    }
+}
+impl<const M: usize> MulAssign<Matrix4> for MatrixMxN<M, 4> {
+    fn mul_assign(&mut self, rhs: Matrix4) {
+        *self = *self * rhs;
+    }
+}
+impl<const P: usize> Mul<MatrixMxN<4, P>> for Matrix4 {
+    type Output = MatrixMxN<4, P>;
+
+    fn mul(self, rhs: MatrixMxN<4, P>) -> Self::Output {
+        MatrixMxN::from(self) * rhs
+    }
+}
+impl<const M: usize, const N: usize> Mul<[f32; N]> for MatrixMxN<M, N> {
+    type Output = [f32; M];
+
+    fn mul(self, rhs: [f32; N]) -> Self::Output {
+        self.data
+            .map(|row| row.iter().zip(rhs).map(|(a, b)| a * b).sum())
+    }
+}
+impl<const M: usize, const N: usize> Mul<VectorN<N>> for MatrixMxN<M, N> {
+    type Output = VectorN<M>;
+
+    fn mul(self, rhs: VectorN<N>) -> Self::Output {
+        (self * <[f32; N]>::from(rhs)).into()
+    }
+}
+impl Mul<Vector3> for Matrix2x3 {
+    type Output = Vector2;
+
+    fn mul(self, rhs: Vector3) -> Self::Output {
+        (self * <[f32; 3]>::from(rhs)).into()
+    }
+}
+impl Mul<Vector4> for Matrix2x4 {
+    type Output = Vector2;
+
+    fn mul(self, rhs: Vector4) -> Self::Output {
+        (self * <[f32; 4]>::from(rhs)).into()
+    }
+}
+impl Mul<Vector2> for Matrix3x2 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Vector2) -> Self::Output {
+        (self * <[f32; 2]>::from(rhs)).into()
+    }
+}
+impl Mul<Vector4> for Matrix3x4 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Vector4) -> Self::Output {
+        (self * <[f32; 4]>::from(rhs)).into()
+    }
+}
+impl Mul<Vector2> for Matrix4x2 {
+    type Output = Vector4;
+
+    fn mul(self, rhs: Vector2) -> Self::Output {
+        (self * <[f32; 2]>::from(rhs)).into()
+    }
+}
+impl Mul<Vector3> for Matrix4x3 {
+    type Output = Vector4;
+
+    fn mul(self, rhs: Vector3) -> Self::Output {
+        (self * <[f32; 3]>::from(rhs)).into()
+    }
+}
+impl<const M: usize, const N: usize> Mul<f32> for MatrixMxN<M, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.data.map(|row| row.map(|comp| comp * rhs)))
+    }
+}
+impl<const M: usize, const N: usize> Mul<MatrixMxN<M, N>> for f32 {
+    type Output = MatrixMxN<M, N>;
+
+    fn mul(self, rhs: MatrixMxN<M, N>) -> Self::Output {
+        rhs * self
+    }
+}
+impl<const M: usize, const N: usize> MulAssign<f32> for MatrixMxN<M, N> {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+impl<const M: usize, const N: usize> Div<f32> for MatrixMxN<M, N> {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        self * (1. / rhs)
+    }
+}
+impl<const M: usize, const N: usize> DivAssign<f32> for MatrixMxN<M, N> {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+impl<const M: usize, const N: usize> PartialEq for MatrixMxN<M, N> {
+    fn eq(&self, other: &Self) -> bool {
+        let matrix = *self - *other;
+        let sum = matrix
+            .data
+            .iter()
+            .flatten()
+            .map(|comp| comp * comp)
+            .sum::<f32>();
+        sum.sqrt() < EPSILON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transform::homogeneous, Vector};
+
+    // Constructors
+    #[test]
+    fn test1_matrixmxnfrom_rows() {
+        let mat = Matrix2x3::from_rows([Vector3::new(1., 2., 3.), Vector3::new(4., 5., 6.)]);
+        assert_eq!(mat, Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]));
+    }
+    #[test]
+    fn test1_matrixmxnfrom_cols() {
+        let mat = Matrix2x3::from_cols([
+            Vector2::new(1., 4.),
+            Vector2::new(2., 5.),
+            Vector2::new(3., 6.),
+        ]);
+        assert_eq!(mat, Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]));
+    }
+    #[test]
+    fn test1_matrixmxnouter() {
+        let mat = Matrix3x4::outer(Vector3::new(1., 2., 3.), Vector4::new(1., 0., -1., 2.));
+        assert_eq!(
+            mat,
+            Matrix3x4::new([[1., 0., -1., 2.], [2., 0., -2., 4.], [3., 0., -3., 6.]])
+        );
+    }
+    // Method transpose()
+    #[test]
+    fn test1_matrixmxntranspose() {
+        let mat = Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]);
+        let transposed: Matrix3x2 = mat.transpose();
+        assert_eq!(transposed, Matrix3x2::new([[1., 4.], [2., 5.], [3., 6.]]));
+        assert_eq!(transposed.transpose(), mat);
+        assert_eq!((transposed.rows(), transposed.cols()), (3, 2));
+    }
+    // Method get_row() and get_col()
+    #[test]
+    fn test1_matrixmxnget_row() {
+        let mat = Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]);
+        assert_eq!(mat.get_row(1), [4., 5., 6.]);
+        assert_eq!(mat.get_col(2), [3., 6.]);
+    }
+    // Display
+    #[test]
+    fn test1_matrixmxndisplay() {
+        let mat = Matrix2x3::new([[1., -2., 3.], [4., 5., 6.]]);
+        println!("{mat}");
+    }
+    // Impl Add, Sub, Neg
+    #[test]
+    fn test1_matrixmxnadd() {
+        let a = Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]);
+        let b = Matrix2x3::from(1.);
+        assert_eq!(a + b, Matrix2x3::new([[2., 3., 4.], [5., 6., 7.]]));
+        assert_eq!(a - b - a, -b);
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+    // Impl Mul
+    #[test]
+    fn test1_matrixmxnmul() {
+        let a = Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]);
+        let b = Matrix3x2::new([[7., 8.], [9., 10.], [11., 12.]]);
+        let product: Matrix2 = (a * b).into();
+        assert_eq!(product, Matrix2::new([[58., 64.], [139., 154.]]));
+        let product: Matrix3 = (b * a).into();
+        assert_eq!(
+            product,
+            Matrix3::new([[39., 54., 69.], [49., 68., 87.], [59., 82., 105.]])
+        );
+    }
+    #[test]
+    fn test2_matrixmxnmul() {
+        let affine = Matrix3x4::new([[1., 0., 0., 5.], [0., 2., 0., -1.], [0., 0., 3., 2.]]);
+        let transform = homogeneous::translate3(Vector3::new(1., 1., 1.));
+        assert_eq!(
+            affine * transform,
+            Matrix3x4::new([[1., 0., 0., 6.], [0., 2., 0., 1.], [0., 0., 3., 5.]])
+        );
+        assert_eq!(
+            affine * Vector4::new(1., 1., 1., 1.),
+            Vector3::new(6., 1., 5.)
+        );
+        let square = Matrix3::scalar(2.);
+        assert_eq!(square * affine, affine * 2.);
+        let mut scaled = affine;
+        scaled *= Matrix4::scalar(0.5);
+        assert_eq!(scaled, affine / 2.);
+    }
+    #[test]
+    fn test3_matrixmxnmul() {
+        let mat = Matrix4x2::new([[1., 0.], [0., 1.], [1., 1.], [2., -1.]]);
+        assert_eq!(mat * Vector2::new(3., 4.), Vector4::new(3., 4., 7., 2.));
+        assert_eq!(mat * [3., 4.], [3., 4., 7., 2.]);
+        assert_eq!(2. * mat, mat + mat);
+        let mut halved = mat;
+        halved /= 2.;
+        halved *= 4.;
+        assert_eq!(halved, mat * 2.);
+    }
+    #[test]
+    fn test4_matrixmxnmul() {
+        let a = Matrix2x3::new([[1., 2., 3.], [4., 5., 6.]]);
+        let b = Matrix4x3::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.], [1., 1., 1.]]);
+        assert_eq!(a * Vector3::new(1., 0., -1.), Vector2::new(-2., -2.));
+        assert_eq!(b * Vector3::new(1., 2., 3.), Vector4::new(1., 2., 3., 6.));
+        assert_eq!(
+            Matrix2x4::from(1.) * Vector4::new(1., 2., 3., 4.),
+            Vector2::new(10., 10.)
+        );
+        assert_eq!(
+            Matrix3x2::from(1.) * Vector2::new(1., 2.),
+            Vector3::new(3., 3., 3.)
+        );
+    }
+    // MatrixN
+    #[test]
+    fn test1_matrixnscalar() {
+        let mat = MatrixN::<5>::idenity();
+        assert_eq!(
+            mat * VectorN::new([1., 2., 3., 4., 5.]),
+            VectorN::new([1., 2., 3., 4., 5.])
+        );
+        assert_eq!(mat.dim(), 5);
+        assert_eq!(mat.det(), 1.);
+        assert_eq!(MatrixN::<5>::scalar(2.).det(), 32.);
+    }
+    #[test]
+    fn test1_matrixninvert() {
+        let mat = MatrixN::new([
+            [4., 1., 0., 0., 2.],
+            [1., 3., 1., 0., 0.],
+            [0., 1., 5., 1., 0.],
+            [0., 0., 1., 2., 1.],
+            [2., 0., 0., 1., 6.],
+        ]);
+        assert_eq!(mat * mat.invert(), MatrixN::idenity());
+        assert_eq!(mat.invert() * mat, MatrixN::idenity());
+        assert_eq!(mat.transpose(), mat);
+        assert_eq!(mat.get_col(4), mat.get_row(4));
+        let singular = MatrixN::new([[1., 2., 3., 4., 5.]; 5]);
+        assert!(singular.is_singular());
+        assert_eq!(singular.try_invert(), None);
+    }
+    #[test]
+    fn test1_matrixnfrom_matrix() {
+        let mat = Matrix3::new([[2., 1., 11.], [7., 5., 0.], [-3., 1., 0.]]);
+        let mat_n = MatrixN::from(mat);
+        let vec = Vector3::new(1., -2., 4.);
+        assert_eq!(Matrix3::from(mat_n), mat);
+        assert!((mat_n.det() - mat.det()).abs() < EPSILON);
+        assert_eq!(Matrix3::from(mat_n.invert()), mat.invert());
+        assert_eq!(Vector3::from(mat_n * VectorN::from(vec)), mat * vec);
+        assert_eq!(
+            Matrix4::from(MatrixN::from(Matrix4::scalar(3.))),
+            Matrix4::scalar(3.)
+        );
+        assert_eq!(
+            Matrix2::from(MatrixN::from(Matrix2::scalar(3.))),
+            Matrix2::scalar(3.)
+        );
+    }
+    #[test]
+    fn test1_matrixnmul() {
+        // Kalman style covariance update P' = F * P * F^T with a 6-dimensional state
+        let mut transition = MatrixN::<6>::idenity();
+        for i in 0..3 {
+            transition[(i, i + 3)] = 0.5;
+        }
+        let covariance = MatrixN::<6>::scalar(2.);
+        let predicted = transition * covariance * transition.transpose();
+        assert_eq!(predicted.transpose(), predicted);
+        assert_eq!(predicted[(0, 0)], 2.5);
+        assert_eq!(predicted[(0, 3)], 1.);
+        let state = VectorN::new([0., 0., 0., 1., 2., 3.]);
+        assert_eq!(transition * state, VectorN::new([0.5, 1., 1.5, 1., 2., 3.]));
+        assert_eq!(state.len(), 14_f32.sqrt());
+    }
+    #[test]
+    fn test1_matrixndecompositions() {
+        let mat = MatrixN::new([
+            [4., 1., 0., 0., 2.],
+            [1., 3., 1., 0., 0.],
+            [0., 1., 5., 1., 0.],
+            [0., 0., 1., 2., 1.],
+            [2., 0., 0., 1., 6.],
+        ]);
+        let b = VectorN::new([1., 2., 3., 4., 5.]);
+        assert_eq!(mat * mat.lu().solve(b), b);
+        assert_eq!(mat * mat.qr().solve(b), b);
+        assert_eq!(mat.pseudo_inverse(), mat.invert());
+        let (q, r): (MatrixN<5>, MatrixN<5>) = (mat.qr().q(), mat.qr().r());
+        assert_eq!(q * r, mat);
+        assert_eq!(q, (q * 1.01).orthonormalize());
+        let (rotation, stretch) = mat.polar();
+        assert_eq!(rotation * stretch, mat);
+    }
+}
@@ -1,17 +1,27 @@
+pub mod cholesky;
+pub mod eigen;
+pub mod exp;
+pub mod lu;
 pub mod mat2;
 pub mod mat3;
 pub mod mat4;
+pub mod matmxn;
+pub mod qr;
+pub mod rotation;
+pub mod svd;
 use mat2::Matrix2;
 use mat3::Matrix3;
 use mat4::Matrix4;
 
 use crate::constants::EPSILON;
+use std::ops::Index;
 
 pub trait Matrix
 where
     Self: Sized,
     Self: Copy,
     Self: Clone,
+    Self: Index<(usize, usize), Output = f32>,
 {
     type Row;
     type Column;
@@ -45,6 +55,73 @@ where
             return false;
         }
     }
+
+    // Descending, see Svd
+    fn singular_values(self) -> Self::Row;
+    // Number of singular values above the tolerance
+    fn rank(self, tolerance: f32) -> usize
+    where
+        Self::Row: AsRef<[f32]>,
+    {
+        svd::rank(self.singular_values().as_ref(), tolerance)
+    }
+    // Ratio of the largest to the smallest singular value, infinite for singular matrices
+    fn condition_number(self) -> f32
+    where
+        Self::Row: AsRef<[f32]>,
+    {
+        svd::condition_number(self.singular_values().as_ref())
+    }
+
+    fn trace(self) -> f32 {
+        (0..self.dim()).map(|i| self[(i, i)]).sum()
+    }
+
+    fn frobenius_norm(self) -> f32 {
+        let dim = self.dim();
+        (0..dim)
+            .flat_map(|i| (0..dim).map(move |j| (i, j)))
+            .map(|index| self[index] * self[index])
+            .sum::<f32>()
+            .sqrt()
+    }
+    // Maximum absolute column sum
+    fn one_norm(self) -> f32 {
+        let dim = self.dim();
+        (0..dim)
+            .map(|j| (0..dim).map(|i| self[(i, j)].abs()).sum::<f32>())
+            .fold(0., f32::max)
+    }
+    // Maximum absolute row sum
+    fn infinity_norm(self) -> f32 {
+        self.transpose().one_norm()
+    }
+
+    fn is_symmetric(self) -> bool {
+        let dim = self.dim();
+        (0..dim).all(|i| (0..i).all(|j| (self[(i, j)] - self[(j, i)]).abs() < EPSILON))
+    }
+    // Columns are orthonormal, so the transpose is the inverse
+    fn is_orthogonal(self) -> bool {
+        let dim = self.dim();
+        (0..dim).all(|i| {
+            (0..dim).all(|j| {
+                let dot = (0..dim).map(|k| self[(k, i)] * self[(k, j)]).sum::<f32>();
+                let expected = if i == j { 1. } else { 0. };
+                (dot - expected).abs() < EPSILON
+            })
+        })
+    }
+    // Orthogonal without a reflection
+    fn is_rotation(self) -> bool {
+        self.is_orthogonal() && self.det() > 0.
+    }
+    // Homogeneous transform, the last row is (0, ..., 0, 1)
+    fn is_affine(self) -> bool {
+        let last = self.dim() - 1;
+        (0..last).all(|j| self[(last, j)].abs() < EPSILON)
+            && (self[(last, last)] - 1.).abs() < EPSILON
+    }
 }
 
 #[allow(non_snake_case)]
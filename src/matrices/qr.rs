@@ -0,0 +1,327 @@
+use super::{mat2::Matrix2, mat3::Matrix3, mat4::Matrix4, matmxn::MatrixN};
+
+// Householder QR decomposition A = Q * R with the diagonal of R made non-negative,
+// which makes the factors unique for invertible A
+#[derive(Debug, Clone, Copy)]
+pub struct Qr<const N: usize> {
+    q: [[f32; N]; N],
+    r: [[f32; N]; N],
+}
+impl<const N: usize> Qr<N> {
+    pub fn new(matrix: [[f32; N]; N]) -> Self {
+        let mut q = identity();
+        let mut r = matrix;
+        for k in 0..N.saturating_sub(1) {
+            let norm = (k..N).map(|i| r[i][k] * r[i][k]).sum::<f32>().sqrt();
+            if norm == 0. {
+                continue;
+            }
+            let alpha = if r[k][k] > 0. { -norm } else { norm };
+            let mut v: [f32; N] = std::array::from_fn(|i| if i < k { 0. } else { r[i][k] });
+            v[k] -= alpha;
+            let v_norm_squared = v.iter().map(|x| x * x).sum::<f32>();
+            if v_norm_squared == 0. {
+                continue;
+            }
+            // R = H * R and Q = Q * H with H = I - 2 v v^T / (v^T v)
+            for j in 0..N {
+                let dot = (k..N).map(|i| v[i] * r[i][j]).sum::<f32>();
+                let factor = 2. * dot / v_norm_squared;
+                for (row, v) in r.iter_mut().zip(v).skip(k) {
+                    row[j] -= factor * v;
+                }
+            }
+            for row in q.iter_mut() {
+                let dot = (k..N).map(|j| row[j] * v[j]).sum::<f32>();
+                let factor = 2. * dot / v_norm_squared;
+                for j in k..N {
+                    row[j] -= factor * v[j];
+                }
+            }
+        }
+        for i in 0..N {
+            if r[i][i] < 0. {
+                for value in r[i].iter_mut() {
+                    *value = -*value;
+                }
+                for row in q.iter_mut() {
+                    row[i] = -row[i];
+                }
+            }
+            r[i][..i].fill(0.);
+        }
+        Self { q, r }
+    }
+
+    pub fn q<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.q.into()
+    }
+
+    pub fn r<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.r.into()
+    }
+
+    // Solves A x = b as R x = Q^T b, None if R has a (numerically) zero diagonal entry
+    pub fn try_solve<V>(&self, b: V) -> Option<V>
+    where
+        V: From<[f32; N]> + Into<[f32; N]>,
+    {
+        let b: [f32; N] = b.into();
+        let scale = self
+            .r
+            .iter()
+            .flatten()
+            .fold(0_f32, |max, x| max.max(x.abs()));
+        if (0..N).any(|i| self.r[i][i] <= f32::EPSILON * N as f32 * scale) {
+            return None;
+        }
+        let mut x: [f32; N] = std::array::from_fn(|i| (0..N).map(|k| self.q[k][i] * b[k]).sum());
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] -= self.r[i][j] * x[j];
+            }
+            x[i] /= self.r[i][i];
+        }
+        Some(x.into())
+    }
+
+    pub fn solve<V>(&self, b: V) -> V
+    where
+        V: From<[f32; N]> + Into<[f32; N]>,
+    {
+        self.try_solve(b)
+            .expect("It is impossible to solve a system with a singular matrix")
+    }
+}
+
+fn identity<const N: usize>() -> [[f32; N]; N] {
+    let mut identity = [[0.; N]; N];
+    for (i, row) in identity.iter_mut().enumerate() {
+        row[i] = 1.;
+    }
+    identity
+}
+
+// Modified Gram-Schmidt over the columns, the first column keeps its direction
+fn gram_schmidt<const N: usize>(matrix: [[f32; N]; N]) -> Option<[[f32; N]; N]> {
+    let mut columns: [[f32; N]; N] = std::array::from_fn(|j| std::array::from_fn(|i| matrix[i][j]));
+    for j in 0..N {
+        let original_norm = columns[j].iter().map(|x| x * x).sum::<f32>().sqrt();
+        for k in 0..j {
+            let basis = columns[k];
+            let dot = (0..N).map(|i| columns[j][i] * basis[i]).sum::<f32>();
+            for (value, basis) in columns[j].iter_mut().zip(basis) {
+                *value -= dot * basis;
+            }
+        }
+        let norm = columns[j].iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm <= f32::EPSILON.sqrt() * original_norm {
+            return None;
+        }
+        for value in columns[j].iter_mut() {
+            *value /= norm;
+        }
+    }
+    Some(std::array::from_fn(|i| {
+        std::array::from_fn(|j| columns[j][i])
+    }))
+}
+
+impl Matrix2 {
+    pub fn qr(self) -> Qr<2> {
+        Qr::new(self.into())
+    }
+
+    pub fn try_orthonormalize(self) -> Option<Self> {
+        gram_schmidt(self.into()).map(Self::new)
+    }
+
+    pub fn orthonormalize(self) -> Self {
+        self.try_orthonormalize()
+            .expect("It is impossible to orthonormalize linearly dependent columns")
+    }
+}
+impl Matrix3 {
+    pub fn qr(self) -> Qr<3> {
+        Qr::new(self.into())
+    }
+
+    pub fn try_orthonormalize(self) -> Option<Self> {
+        gram_schmidt(self.into()).map(Self::new)
+    }
+
+    pub fn orthonormalize(self) -> Self {
+        self.try_orthonormalize()
+            .expect("It is impossible to orthonormalize linearly dependent columns")
+    }
+}
+impl Matrix4 {
+    pub fn qr(self) -> Qr<4> {
+        Qr::new(self.into())
+    }
+
+    // Treats all four columns as a basis, use orthonormalize_rotation() for affine transforms
+    pub fn try_orthonormalize(self) -> Option<Self> {
+        gram_schmidt(self.into()).map(Self::new)
+    }
+
+    pub fn orthonormalize(self) -> Self {
+        self.try_orthonormalize()
+            .expect("It is impossible to orthonormalize linearly dependent columns")
+    }
+
+    // Repairs the upper-left 3x3 block of a homogeneous transform, keeping the translation
+    pub fn try_orthonormalize_rotation(self) -> Option<Self> {
+        let rotation = Matrix3::from_matrix4(self).try_orthonormalize()?;
+        let mut res = Self::from_matrix3(rotation);
+        res.set_col(3, self.col(3));
+        res.set_row(3, self.row(3));
+        Some(res)
+    }
+
+    pub fn orthonormalize_rotation(self) -> Self {
+        self.try_orthonormalize_rotation()
+            .expect("It is impossible to orthonormalize linearly dependent columns")
+    }
+}
+impl<const N: usize> MatrixN<N> {
+    pub fn qr(self) -> Qr<N> {
+        Qr::new(self.into())
+    }
+
+    pub fn try_orthonormalize(self) -> Option<Self> {
+        gram_schmidt(self.into()).map(Self::new)
+    }
+
+    pub fn orthonormalize(self) -> Self {
+        self.try_orthonormalize()
+            .expect("It is impossible to orthonormalize linearly dependent columns")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        functions::constants::EPSILON,
+        transform::{self, homogeneous},
+        Matrix, Vector3, Vector4,
+    };
+
+    // Constructors
+    #[test]
+    fn test1_qrnew() {
+        let mat = Matrix3::new([[12., -51., 4.], [6., 167., -68.], [-4., 24., -41.]]);
+        let qr = mat.qr();
+        let (q, r): (Matrix3, Matrix3) = (qr.q(), qr.r());
+        assert_eq!(q * r, mat);
+        assert_eq!(q.transpose() * q, Matrix3::idenity());
+        assert_eq!(
+            r,
+            Matrix3::new([[14., 21., -14.], [0., 175., -70.], [0., 0., 35.]])
+        );
+    }
+    #[test]
+    fn test2_qrnew() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        let qr = mat.qr();
+        let (q, r): (Matrix4, Matrix4) = (qr.q(), qr.r());
+        assert_eq!(q * r, mat);
+        assert_eq!(q * q.transpose(), Matrix4::idenity());
+        for i in 0..4 {
+            assert!(r[(i, i)] >= 0.);
+            for j in 0..i {
+                assert_eq!(r[(i, j)], 0.);
+            }
+        }
+    }
+    #[test]
+    fn test3_qrnew() {
+        let mat = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [0., 0., 1.]]);
+        let qr = mat.qr();
+        let (q, r): (Matrix3, Matrix3) = (qr.q(), qr.r());
+        assert_eq!(q * r, mat);
+        assert_eq!(q.transpose() * q, Matrix3::idenity());
+    }
+    // Method solve(b)
+    #[test]
+    fn test1_qrsolve() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        let x = Vector4::new(2., -1., 0.5, 1.);
+        assert_eq!(mat.qr().solve(mat * x), x);
+    }
+    #[test]
+    fn test2_qrsolve() {
+        let mat = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [0., 0., 1.]]);
+        assert_eq!(mat.qr().try_solve(Vector3::from(1.)), None);
+    }
+    // Method orthonormalize()
+    #[test]
+    fn test1_matrix3orthonormalize() {
+        let step = transform::rotate3x(0.01) * transform::rotate3y(0.02);
+        let mut mat = Matrix3::idenity();
+        for _ in 0..10000 {
+            mat = Matrix3::new([
+                [mat[(0, 0)] * 1.0001, mat[(0, 1)], mat[(0, 2)]],
+                [mat[(1, 0)], mat[(1, 1)], mat[(1, 2)] + 0.0001],
+                [mat[(2, 0)], mat[(2, 1)], mat[(2, 2)]],
+            ]) * step;
+        }
+        assert_ne!(mat.transpose() * mat, Matrix3::idenity());
+        let repaired = mat.orthonormalize();
+        assert_eq!(repaired.transpose() * repaired, Matrix3::idenity());
+        assert!((repaired.det() - 1.).abs() < EPSILON);
+    }
+    #[test]
+    fn test2_matrix3orthonormalize() {
+        let mat = transform::rotate3(0.3, -1.2, 2.);
+        assert_eq!(mat.orthonormalize(), mat);
+    }
+    #[test]
+    #[should_panic]
+    fn test3_matrix3orthonormalize() {
+        Matrix3::new([[1., 2., 0.], [1., 2., 0.], [1., 2., 1.]]).orthonormalize();
+    }
+    #[test]
+    fn test1_matrix2orthonormalize() {
+        let mat = Matrix2::new([[2., 1.], [0., 1.]]);
+        assert_eq!(mat.orthonormalize(), Matrix2::idenity());
+    }
+    #[test]
+    fn test1_matrix4orthonormalize() {
+        let mat = homogeneous::rotate3(0.3, -1.2, 2.) * 1.01;
+        let repaired = mat.orthonormalize();
+        assert_eq!(repaired.transpose() * repaired, Matrix4::idenity());
+    }
+    #[test]
+    fn test1_matrix4orthonormalize_rotation() {
+        let rotation = homogeneous::rotate3(0.3, -1.2, 2.);
+        let translation = homogeneous::translate3(Vector3::new(5., -2., 1.));
+        let mut drifted = translation * rotation;
+        drifted[(0, 1)] += 0.01;
+        drifted[(2, 0)] -= 0.02;
+        let repaired = drifted.orthonormalize_rotation();
+        assert_eq!(repaired.get_col(3), [5., -2., 1., 1.]);
+        assert_eq!(repaired.get_row(3), [0., 0., 0., 1.]);
+        assert_eq!(
+            repaired,
+            translation * (translation.invert() * repaired).orthonormalize()
+        );
+    }
+}
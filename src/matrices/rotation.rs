@@ -0,0 +1,99 @@
+use super::{mat3::Matrix3, Matrix};
+use crate::{Vector, Vector3};
+
+// Closed forms of exp and log restricted to rotations, with rotation vectors as axis * angle
+impl Matrix3 {
+    // Cross product matrix, skew(a) * b == a.cross(b)
+    pub fn skew(vec: Vector3) -> Self {
+        Self::new([
+            [0., -vec.z, vec.y],
+            [vec.z, 0., -vec.x],
+            [-vec.y, vec.x, 0.],
+        ])
+    }
+
+    // Rodrigues' formula
+    pub fn from_rotation_vector(vec: Vector3) -> Self {
+        let angle = vec.len();
+        if angle <= f32::EPSILON {
+            return Self::idenity() + Self::skew(vec);
+        }
+        let axis = Self::skew(vec / angle);
+        Self::idenity() + axis * angle.sin() + axis * axis * (1. - angle.cos())
+    }
+
+    // Inverse of from_rotation_vector for rotation matrices, angle in [0, pi]
+    pub fn rotation_vector(self) -> Vector3 {
+        let cos = ((self.trace() - 1.) / 2.).clamp(-1., 1.);
+        let angle = cos.acos();
+        // Twice the sine times the axis
+        let vee = Vector3::new(
+            self[(2, 1)] - self[(1, 2)],
+            self[(0, 2)] - self[(2, 0)],
+            self[(1, 0)] - self[(0, 1)],
+        );
+        if angle <= f32::EPSILON.sqrt() {
+            return vee / 2.;
+        }
+        if cos > -0.9 {
+            return vee * (angle / (2. * angle.sin()));
+        }
+        // Near a half turn the sine vanishes, read the axis off the symmetric part instead,
+        // (R + R^T) / 2 - cos I = (1 - cos) a a^T
+        let k = (0..3)
+            .max_by(|&a, &b| self[(a, a)].total_cmp(&self[(b, b)]))
+            .unwrap_or(0);
+        let symmetric = |i: usize| {
+            let delta = if i == k { cos } else { 0. };
+            (self[(i, k)] + self[(k, i)]) / 2. - delta
+        };
+        let column = Vector3::new(symmetric(0), symmetric(1), symmetric(2));
+        let axis = column.normalize();
+        let axis = if axis.dot(vee) < 0. { -axis } else { axis };
+        axis * angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::PI, transform};
+
+    // Methods from_rotation_vector(), rotation_vector()
+    #[test]
+    fn test1_matrix3from_rotation_vector() {
+        assert_eq!(
+            Matrix3::from_rotation_vector(Vector3::new(0., 0., PI / 2.)),
+            transform::rotate3z(PI / 2.)
+        );
+        let vec = Vector3::new(0.3, -1.2, 0.5);
+        assert_eq!(Matrix3::from_rotation_vector(vec), Matrix3::skew(vec).exp());
+        assert_eq!(
+            Matrix3::skew(vec) * Vector3::new(1., 2., 3.),
+            vec.cross(Vector3::new(1., 2., 3.))
+        );
+        assert_eq!(
+            Matrix3::from_rotation_vector(Vector3::zero()),
+            Matrix3::idenity()
+        );
+    }
+    #[test]
+    fn test1_matrix3rotation_vector() {
+        for vec in [
+            Vector3::new(0.3, -1.2, 0.5),
+            Vector3::new(1e-4, 0., 2e-4),
+            Vector3::new(0., 0., 0.),
+            Vector3::new(2., 2., -1.).normalize() * 3.,
+        ] {
+            let rotation = Matrix3::from_rotation_vector(vec);
+            assert_eq!(rotation.rotation_vector(), vec);
+        }
+        // A half turn about -y is also one about +y
+        let rotation = transform::rotate3y(PI);
+        assert_eq!(rotation.rotation_vector().len(), PI);
+        assert_eq!(
+            Matrix3::from_rotation_vector(rotation.rotation_vector()),
+            rotation
+        );
+    }
+}
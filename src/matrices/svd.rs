@@ -0,0 +1,442 @@
+use super::{lu::Lu, mat2::Matrix2, mat3::Matrix3, mat4::Matrix4, matmxn::MatrixN};
+
+const MAX_SWEEPS: usize = 32;
+
+// Singular value decomposition A = U * diag(singular values) * V^T computed with one-sided
+// Jacobi rotations. Singular values are non-negative and sorted in descending order
+#[derive(Debug, Clone, Copy)]
+pub struct Svd<const N: usize> {
+    u: [[f32; N]; N],
+    singular_values: [f32; N],
+    v: [[f32; N]; N],
+}
+impl<const N: usize> Svd<N> {
+    pub fn new(matrix: [[f32; N]; N]) -> Self {
+        // Columns of A * V are orthogonalized in place, so they are stored as rows here
+        let mut columns: [[f32; N]; N] =
+            std::array::from_fn(|j| std::array::from_fn(|i| matrix[i][j]));
+        let mut v_columns = identity::<N>();
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..N {
+                for q in p + 1..N {
+                    let alpha = dot(&columns[p], &columns[p]);
+                    let beta = dot(&columns[q], &columns[q]);
+                    let gamma = dot(&columns[p], &columns[q]);
+                    if gamma.abs() <= f32::EPSILON * (alpha * beta).sqrt() || gamma == 0. {
+                        continue;
+                    }
+                    rotated = true;
+                    let zeta = (beta - alpha) / (2. * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (zeta * zeta + 1.).sqrt());
+                    let c = 1. / (t * t + 1.).sqrt();
+                    let s = t * c;
+                    rotate(&mut columns, p, q, c, s);
+                    rotate(&mut v_columns, p, q, c, s);
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+        let norms = columns.map(|column| dot(&column, &column).sqrt());
+        let mut order: [usize; N] = std::array::from_fn(|i| i);
+        order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+        let singular_values = order.map(|i| norms[i]);
+        let largest = singular_values.first().copied().unwrap_or(0.);
+        let mut u_columns = [[0.; N]; N];
+        let mut complete = [true; N];
+        for (k, &i) in order.iter().enumerate() {
+            if norms[i] > f32::EPSILON * largest && norms[i] > 0. {
+                u_columns[k] = columns[i].map(|x| x / norms[i]);
+            } else {
+                complete[k] = false;
+            }
+        }
+        // Null space directions of U are filled up to an orthonormal basis
+        for k in 0..N {
+            if complete[k] {
+                continue;
+            }
+            u_columns[k] = (0..N)
+                .map(|e| {
+                    let mut candidate = [0.; N];
+                    candidate[e] = 1.;
+                    for (basis, _) in u_columns.iter().zip(complete).filter(|(_, c)| *c) {
+                        let projection = dot(&candidate, basis);
+                        for (value, basis) in candidate.iter_mut().zip(basis) {
+                            *value -= projection * basis;
+                        }
+                    }
+                    candidate
+                })
+                .max_by(|a, b| dot(a, a).total_cmp(&dot(b, b)))
+                .map(|candidate| candidate.map(|x| x / dot(&candidate, &candidate).sqrt()))
+                .unwrap_or([0.; N]);
+            complete[k] = true;
+        }
+        Self {
+            u: transpose(u_columns),
+            singular_values,
+            v: transpose(order.map(|i| v_columns[i])),
+        }
+    }
+
+    pub fn u<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.u.into()
+    }
+
+    pub fn v<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        self.v.into()
+    }
+
+    pub fn singular_values<V>(&self) -> V
+    where
+        V: From<[f32; N]>,
+    {
+        self.singular_values.into()
+    }
+
+    // Singular values up to this are treated as zero by rank() and pseudo_inverse()
+    pub fn tolerance(&self) -> f32 {
+        tolerance(&self.singular_values)
+    }
+
+    pub fn rank(&self, tolerance: f32) -> usize {
+        rank(&self.singular_values, tolerance)
+    }
+
+    // 2-norm condition number, infinite for singular matrices
+    pub fn condition_number(&self) -> f32 {
+        condition_number(&self.singular_values)
+    }
+
+    // Moore-Penrose pseudo-inverse V * diag(1 / singular values) * U^T
+    pub fn pseudo_inverse<M>(&self) -> M
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        let tolerance = self.tolerance();
+        let inverted = self
+            .singular_values
+            .map(|value| if value > tolerance { 1. / value } else { 0. });
+        let res: [[f32; N]; N] = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                (0..N)
+                    .map(|k| self.v[i][k] * inverted[k] * self.u[j][k])
+                    .sum()
+            })
+        });
+        res.into()
+    }
+
+    // Polar decomposition A = R * S with R a proper rotation (det = +1) and S symmetric.
+    // S is positive semi-definite unless A itself contains a reflection
+    pub fn polar<M>(&self) -> (M, M)
+    where
+        M: From<[[f32; N]; N]>,
+    {
+        let mut u = self.u;
+        let mut singular_values = self.singular_values;
+        if N > 0 && Lu::new(multiply_transposed(&u, &self.v)).det() < 0. {
+            for row in u.iter_mut() {
+                row[N - 1] = -row[N - 1];
+            }
+            singular_values[N - 1] = -singular_values[N - 1];
+        }
+        let rotation = multiply_transposed(&u, &self.v);
+        let stretch: [[f32; N]; N] = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                (0..N)
+                    .map(|k| self.v[i][k] * singular_values[k] * self.v[j][k])
+                    .sum()
+            })
+        });
+        (rotation.into(), stretch.into())
+    }
+}
+
+fn identity<const N: usize>() -> [[f32; N]; N] {
+    let mut identity = [[0.; N]; N];
+    for (i, row) in identity.iter_mut().enumerate() {
+        row[i] = 1.;
+    }
+    identity
+}
+
+fn dot<const N: usize>(a: &[f32; N], b: &[f32; N]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn transpose<const N: usize>(matrix: [[f32; N]; N]) -> [[f32; N]; N] {
+    std::array::from_fn(|i| std::array::from_fn(|j| matrix[j][i]))
+}
+
+// a * b^T
+fn multiply_transposed<const N: usize>(a: &[[f32; N]; N], b: &[[f32; N]; N]) -> [[f32; N]; N] {
+    std::array::from_fn(|i| std::array::from_fn(|j| dot(&a[i], &b[j])))
+}
+
+fn rotate<const N: usize>(columns: &mut [[f32; N]; N], p: usize, q: usize, c: f32, s: f32) {
+    let (column_p, column_q) = (columns[p], columns[q]);
+    columns[p] = std::array::from_fn(|k| c * column_p[k] - s * column_q[k]);
+    columns[q] = std::array::from_fn(|k| s * column_p[k] + c * column_q[k]);
+}
+
+// Shared by Svd and the Matrix trait, singular values are sorted in descending order
+pub(crate) fn tolerance(singular_values: &[f32]) -> f32 {
+    let largest = singular_values.first().copied().unwrap_or(0.);
+    f32::EPSILON * singular_values.len() as f32 * largest
+}
+
+pub(crate) fn rank(singular_values: &[f32], tolerance: f32) -> usize {
+    singular_values
+        .iter()
+        .filter(|&&value| value > tolerance)
+        .count()
+}
+
+pub(crate) fn condition_number(singular_values: &[f32]) -> f32 {
+    match (singular_values.first(), singular_values.last()) {
+        (Some(&largest), Some(&smallest)) if smallest > tolerance(singular_values) => {
+            largest / smallest
+        }
+        (Some(_), Some(_)) => f32::INFINITY,
+        _ => 1.,
+    }
+}
+
+impl Matrix2 {
+    pub fn svd(self) -> Svd<2> {
+        Svd::new(self.into())
+    }
+
+    pub fn pseudo_inverse(self) -> Self {
+        self.svd().pseudo_inverse()
+    }
+
+    pub fn polar(self) -> (Self, Self) {
+        self.svd().polar()
+    }
+}
+impl Matrix3 {
+    pub fn svd(self) -> Svd<3> {
+        Svd::new(self.into())
+    }
+
+    pub fn pseudo_inverse(self) -> Self {
+        self.svd().pseudo_inverse()
+    }
+
+    pub fn polar(self) -> (Self, Self) {
+        self.svd().polar()
+    }
+}
+impl Matrix4 {
+    pub fn svd(self) -> Svd<4> {
+        Svd::new(self.into())
+    }
+
+    pub fn pseudo_inverse(self) -> Self {
+        self.svd().pseudo_inverse()
+    }
+
+    pub fn polar(self) -> (Self, Self) {
+        self.svd().polar()
+    }
+}
+impl<const N: usize> MatrixN<N> {
+    pub fn svd(self) -> Svd<N> {
+        Svd::new(self.into())
+    }
+
+    pub fn pseudo_inverse(self) -> Self {
+        self.svd().pseudo_inverse()
+    }
+
+    pub fn polar(self) -> (Self, Self) {
+        self.svd().polar()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        functions::constants::EPSILON,
+        transform::{self, homogeneous},
+        Matrix, Vector2, Vector3, Vector4,
+    };
+
+    // Constructors
+    #[test]
+    fn test1_svdnew() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        let svd = mat.svd();
+        let (u, v): (Matrix4, Matrix4) = (svd.u(), svd.v());
+        let Vector4 { x, y, z, w } = svd.singular_values();
+        let sigma = Matrix4::new([
+            [x, 0., 0., 0.],
+            [0., y, 0., 0.],
+            [0., 0., z, 0.],
+            [0., 0., 0., w],
+        ]);
+        assert_eq!(u.transpose() * u, Matrix4::idenity());
+        assert_eq!(v.transpose() * v, Matrix4::idenity());
+        assert_eq!(u * sigma * v.transpose(), mat);
+        assert!(x >= y && y >= z && z >= w && w > 0.);
+    }
+    #[test]
+    fn test2_svdnew() {
+        let mat = Matrix2::new([[3., 0.], [4., 5.]]);
+        let svd = mat.svd();
+        let values: Vector2 = svd.singular_values();
+        assert_eq!(values, Vector2::new(45_f32.sqrt(), 5_f32.sqrt()));
+    }
+    #[test]
+    fn test3_svdnew() {
+        let mat = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]);
+        let svd = mat.svd();
+        let (u, v): (Matrix3, Matrix3) = (svd.u(), svd.v());
+        let Vector3 { x, y, z } = svd.singular_values();
+        let sigma = Matrix3::new([[x, 0., 0.], [0., y, 0.], [0., 0., z]]);
+        assert_eq!(u.transpose() * u, Matrix3::idenity());
+        assert_eq!(v.transpose() * v, Matrix3::idenity());
+        assert_eq!(u * sigma * v.transpose(), mat);
+        assert!(z.abs() < EPSILON);
+    }
+    #[test]
+    fn test4_svdnew() {
+        let svd = Matrix3::zero().svd();
+        let (u, v): (Matrix3, Matrix3) = (svd.u(), svd.v());
+        assert_eq!(svd.singular_values::<Vector3>(), Vector3::from(0.));
+        assert_eq!(u.transpose() * u, Matrix3::idenity());
+        assert_eq!(v, Matrix3::idenity());
+    }
+    // Method rank(tolerance)
+    #[test]
+    fn test1_svdrank() {
+        let svd = Matrix4::idenity().svd();
+        assert_eq!(svd.rank(svd.tolerance()), 4);
+    }
+    #[test]
+    fn test2_svdrank() {
+        let svd = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]).svd();
+        assert_eq!(svd.rank(svd.tolerance()), 2);
+        let svd = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [-1., -2., -3.]]).svd();
+        assert_eq!(svd.rank(svd.tolerance()), 1);
+        assert_eq!(Matrix2::zero().svd().rank(0.), 0);
+    }
+    #[test]
+    fn test3_svdrank() {
+        let svd = Matrix3::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 1e-4]]).svd();
+        assert_eq!(svd.rank(svd.tolerance()), 3);
+        assert_eq!(svd.rank(1e-3), 2);
+    }
+    // Method condition_number()
+    #[test]
+    fn test1_svdcondition_number() {
+        let mat = transform::rotate3(0.2, 0.4, -1.)
+            * Matrix3::new([[4., 0., 0.], [0., 2., 0.], [0., 0., 0.5]]);
+        assert!((mat.svd().condition_number() - 8.).abs() < EPSILON);
+        assert_eq!(
+            Matrix2::new([[1., 2.], [2., 4.]]).svd().condition_number(),
+            f32::INFINITY
+        );
+    }
+    #[test]
+    fn test1_matrix3rank() {
+        let mat = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]);
+        assert_eq!(mat.rank(1e-4), 2);
+        assert_eq!(Matrix4::idenity().rank(1e-4), 4);
+        // det is below EPSILON, but the matrix is perfectly conditioned
+        let tiny = Matrix3::scalar(0.05);
+        assert!(tiny.is_singular());
+        assert_eq!(tiny.rank(1e-4), 3);
+        assert!((tiny.condition_number() - 1.).abs() < EPSILON);
+    }
+    // Method pseudo_inverse()
+    #[test]
+    fn test1_matrix4pseudo_inverse() {
+        let mat = Matrix4::new([
+            [1., 7., 0., 7.],
+            [-3., 4., 1., 0.],
+            [-8., -1., 0., 11.],
+            [1., 3., -3., 0.],
+        ]);
+        assert_eq!(mat.pseudo_inverse(), mat.invert());
+    }
+    #[test]
+    fn test2_matrix3pseudo_inverse() {
+        let mat = Matrix3::new([[1., 2., 3.], [2., 4., 6.], [1., 1., 1.]]);
+        let pinv = mat.pseudo_inverse();
+        assert_eq!(mat * pinv * mat, mat);
+        assert_eq!(pinv * mat * pinv, pinv);
+        assert_eq!((mat * pinv).transpose(), mat * pinv);
+        assert_eq!((pinv * mat).transpose(), pinv * mat);
+    }
+    #[test]
+    fn test3_matrix2pseudo_inverse() {
+        let mat = Matrix2::new([[2., 0.], [0., 0.]]);
+        assert_eq!(mat.pseudo_inverse(), Matrix2::new([[0.5, 0.], [0., 0.]]));
+    }
+    // Method polar()
+    #[test]
+    fn test1_matrix3polar() {
+        let rotation = transform::rotate3(0.7, -0.3, 1.1);
+        let stretch = Matrix3::new([[2., 0.5, 0.], [0.5, 1., 0.2], [0., 0.2, 3.]]);
+        let (r, s) = (rotation * stretch).polar();
+        assert_eq!(r, rotation);
+        assert_eq!(s, stretch);
+    }
+    #[test]
+    fn test2_matrix3polar() {
+        let mat = Matrix3::new([[-1., 0., 0.], [0., 2., 0.], [0., 0., 3.]]);
+        let (r, s) = mat.polar();
+        assert!((r.det() - 1.).abs() < EPSILON);
+        assert_eq!(r.transpose() * r, Matrix3::idenity());
+        assert_eq!(s.transpose(), s);
+        assert_eq!(r * s, mat);
+    }
+    #[test]
+    fn test3_matrix4polar() {
+        let mat =
+            homogeneous::rotate3(0.2, 1.4, -0.5) * homogeneous::scale3(Vector3::new(2., 2., 0.));
+        let (r, s) = mat.polar();
+        assert!((r.det() - 1.).abs() < EPSILON);
+        assert_eq!(r.transpose() * r, Matrix4::idenity());
+        assert_eq!(r * s, mat);
+    }
+    #[test]
+    fn test4_matrix3polar() {
+        // Kabsch: the rotation aligning centred point sets is the polar factor of their covariance
+        let rotation = transform::rotate3(-0.4, 0.9, 0.25);
+        let points = [
+            Vector3::new(1., 0., 0.),
+            Vector3::new(0., 2., 0.),
+            Vector3::new(0., 0., 3.),
+            Vector3::new(-1., -2., -3.),
+        ];
+        let mut covariance = Matrix3::zero();
+        for point in points {
+            let target = rotation * point;
+            covariance += Matrix3::new([
+                [target.x * point.x, target.x * point.y, target.x * point.z],
+                [target.y * point.x, target.y * point.y, target.y * point.z],
+                [target.z * point.x, target.z * point.y, target.z * point.z],
+            ]);
+        }
+        assert_eq!(covariance.polar().0, rotation);
+    }
+}
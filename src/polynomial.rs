@@ -0,0 +1,446 @@
+use crate::functions::constants::PI;
+use std::fmt::{self, Display};
+
+const NEWTON_POLISH_ITERATIONS: usize = 2;
+const MAX_REFINE_ITERATIONS: usize = 100;
+
+// Closed form solvers run in f64, cancellation in the discriminants costs too much in f32
+
+fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0., |sum, c| sum * x + c)
+}
+
+fn derivative(coefficients: &[f64], x: f64) -> f64 {
+    coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .rev()
+        .fold(0., |sum, (i, c)| sum * x + c * i as f64)
+}
+
+// Newton steps on the original polynomial to undo the error of the closed forms.
+// Coefficients are in ascending order
+fn polish(coefficients: &[f64], roots: Vec<f64>) -> Vec<f32> {
+    let mut roots: Vec<f32> = roots
+        .into_iter()
+        .map(|mut root| {
+            for _ in 0..NEWTON_POLISH_ITERATIONS {
+                let slope = derivative(coefficients, root);
+                let next = root - evaluate(coefficients, root) / slope;
+                if slope == 0. || !next.is_finite() || next.abs() > 2. * root.abs().max(1.) {
+                    break;
+                }
+                root = next;
+            }
+            root as f32
+        })
+        .collect();
+    roots.sort_by(f32::total_cmp);
+    roots.dedup();
+    roots
+}
+
+fn quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a == 0. {
+        return if b == 0. { vec![] } else { vec![-c / b] };
+    }
+    let discriminant = b * b - 4. * a * c;
+    if discriminant < 0. {
+        return vec![];
+    }
+    if discriminant == 0. {
+        return vec![-b / (2. * a)];
+    }
+    // Citardauq form, never subtracts nearly equal numbers
+    let q = -(b + discriminant.sqrt().copysign(b)) / 2.;
+    if q == 0. {
+        return vec![0.];
+    }
+    vec![q / a, c / q]
+}
+
+fn cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a == 0. {
+        return quadratic(b, c, d);
+    }
+    let (b, c, d) = (b / a, c / a, d / a);
+    // x = t - b / 3 gives t^3 + p t + q
+    let p = c - b * b / 3.;
+    let q = 2. * b * b * b / 27. - b * c / 3. + d;
+    let shift = -b / 3.;
+    let (half_q, third_p) = (q / 2., p / 3.);
+    let discriminant = half_q * half_q + third_p * third_p * third_p;
+    let scale = half_q * half_q + (third_p * third_p * third_p).abs();
+    if discriminant.abs() <= 16. * f64::EPSILON * scale {
+        if p == 0. {
+            return vec![shift];
+        }
+        // Simple root and double root
+        return vec![3. * q / p + shift, -3. * q / (2. * p) + shift];
+    }
+    if discriminant > 0. {
+        let u = (-half_q - discriminant.sqrt().copysign(half_q)).cbrt();
+        return vec![u - third_p / u + shift];
+    }
+    // Three real roots, trigonometric form
+    let radius = 2. * (-third_p).sqrt();
+    let angle = (3. * q / (2. * p) * (-3. / p).sqrt()).clamp(-1., 1.).acos() / 3.;
+    (0..3)
+        .map(|k| radius * (angle - 2. * f64::from(PI) * k as f64 / 3.).cos() + shift)
+        .collect()
+}
+
+fn quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a == 0. {
+        return cubic(b, c, d, e);
+    }
+    let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+    // x = y - b / 4 gives y^4 + p y^2 + q y + r
+    let p = c - 3. * b * b / 8.;
+    let q = b * b * b / 8. - b * c / 2. + d;
+    let r = -3. * b * b * b * b / 256. + b * b * c / 16. - b * d / 4. + e;
+    let shift = -b / 4.;
+    let roots = if q.abs() <= 16. * f64::EPSILON * (p * p + r.abs()).sqrt().max(1.) {
+        // Biquadratic
+        quadratic(1., p, r)
+            .into_iter()
+            .filter(|z| *z >= 0.)
+            .flat_map(|z| [z.sqrt(), -z.sqrt()])
+            .collect()
+    } else {
+        // Ferrari, any positive root m of the resolvent splits it into two quadratics
+        let m = cubic(8., 8. * p, 2. * p * p - 8. * r, -q * q)
+            .into_iter()
+            .fold(0_f64, f64::max);
+        let s = (2. * m).sqrt();
+        let mut roots = quadratic(1., s, p / 2. + m - q / (2. * s));
+        roots.extend(quadratic(1., -s, p / 2. + m + q / (2. * s)));
+        roots
+    };
+    roots.into_iter().map(|y| y + shift).collect()
+}
+
+// Real roots of a x^2 + b x + c in ascending order, repeated roots appear once
+pub fn solve_quadratic(a: f32, b: f32, c: f32) -> Vec<f32> {
+    let coefficients = [c, b, a].map(f64::from);
+    polish(&coefficients, quadratic(a.into(), b.into(), c.into()))
+}
+
+// Real roots of a x^3 + b x^2 + c x + d in ascending order, repeated roots appear once
+pub fn solve_cubic(a: f32, b: f32, c: f32, d: f32) -> Vec<f32> {
+    let coefficients = [d, c, b, a].map(f64::from);
+    polish(&coefficients, cubic(a.into(), b.into(), c.into(), d.into()))
+}
+
+// Real roots of a x^4 + b x^3 + c x^2 + d x + e in ascending order, repeated roots appear once
+pub fn solve_quartic(a: f32, b: f32, c: f32, d: f32, e: f32) -> Vec<f32> {
+    let coefficients = [e, d, c, b, a].map(f64::from);
+    polish(
+        &coefficients,
+        quartic(a.into(), b.into(), c.into(), d.into(), e.into()),
+    )
+}
+
+// Polynomial with coefficients in ascending order, c0 + c1 x + c2 x^2 + ...
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<f32>,
+}
+impl Polynomial {
+    // Trailing zero coefficients are dropped
+    pub fn new(coefficients: Vec<f32>) -> Self {
+        let mut coefficients = coefficients;
+        while coefficients.last() == Some(&0.) {
+            coefficients.pop();
+        }
+        Self { coefficients }
+    }
+
+    pub fn coefficients(&self) -> &[f32] {
+        &self.coefficients
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    // Horner's scheme in f64
+    pub fn evaluate(&self, x: f32) -> f32 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0_f64, |sum, &c| sum * f64::from(x) + f64::from(c)) as f32
+    }
+
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| c * i as f32)
+                .collect(),
+        )
+    }
+
+    // Bound on the rounding error of evaluate(x), values below it count as zero
+    fn evaluation_tolerance(&self, x: f32) -> f32 {
+        let magnitude = self
+            .coefficients
+            .iter()
+            .rev()
+            .fold(0., |sum, c| sum * x.abs() + c.abs());
+        4. * f32::EPSILON * magnitude * self.coefficients.len() as f32
+    }
+
+    // Root inside [lower, upper] when the values at the ends differ in sign, Newton steps
+    // that leave the shrinking bracket fall back to bisection
+    pub fn refine_root(&self, lower: f32, upper: f32) -> Option<f32> {
+        let (mut lower, mut upper) = (lower.min(upper), lower.max(upper));
+        let (lower_value, upper_value) = (self.evaluate(lower), self.evaluate(upper));
+        if lower_value == 0. {
+            return Some(lower);
+        }
+        if upper_value == 0. {
+            return Some(upper);
+        }
+        if lower_value.signum() == upper_value.signum() {
+            return None;
+        }
+        let rising = upper_value > 0.;
+        let derivative = self.derivative();
+        let mut x = (lower + upper) / 2.;
+        for _ in 0..MAX_REFINE_ITERATIONS {
+            let value = self.evaluate(x);
+            if value == 0. {
+                return Some(x);
+            }
+            if (value > 0.) == rising {
+                upper = x;
+            } else {
+                lower = x;
+            }
+            let newton = x - value / derivative.evaluate(x);
+            let next = if newton > lower && newton < upper {
+                newton
+            } else {
+                (lower + upper) / 2.
+            };
+            if next == x || upper - lower <= f32::EPSILON * x.abs().max(1.) {
+                return Some(next);
+            }
+            x = next;
+        }
+        Some(x)
+    }
+
+    // Real roots in [min, max] ascending. Roots of the derivative split the interval into
+    // monotonic pieces, each bracketing at most one root
+    pub fn roots_in(&self, min: f32, max: f32) -> Vec<f32> {
+        let Some(degree) = self.degree() else {
+            return Vec::new();
+        };
+        if degree == 0 {
+            return Vec::new();
+        }
+        let mut splits = vec![min];
+        splits.extend(self.derivative().roots_in(min, max));
+        splits.push(max);
+        let mut roots = Vec::new();
+        for pair in splits.windows(2) {
+            if let Some(root) = self.refine_root(pair[0], pair[1]) {
+                roots.push(root);
+            }
+        }
+        // Roots of even multiplicity only touch zero at a critical point
+        for &x in &splits {
+            if self.evaluate(x).abs() <= self.evaluation_tolerance(x) {
+                roots.push(x);
+            }
+        }
+        roots.sort_by(f32::total_cmp);
+        roots.dedup_by(|a, b| (*a - *b).abs() <= 8. * f32::EPSILON * a.abs().max(1.));
+        roots
+    }
+
+    // All real roots ascending, closed forms up to degree four
+    pub fn roots(&self) -> Vec<f32> {
+        match self.coefficients[..] {
+            [] | [_] => Vec::new(),
+            [b, a] => vec![-b / a],
+            [c, b, a] => solve_quadratic(a, b, c),
+            [d, c, b, a] => solve_cubic(a, b, c, d),
+            [e, d, c, b, a] => solve_quartic(a, b, c, d, e),
+            _ => {
+                // Cauchy's bound contains every root
+                let leading = self.coefficients[self.coefficients.len() - 1].abs();
+                let bound = 1.
+                    + self
+                        .coefficients
+                        .iter()
+                        .map(|c| c.abs() / leading)
+                        .fold(0., f32::max);
+                self.roots_in(-bound, bound)
+            }
+        }
+    }
+}
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c != 0.)
+            .map(|(i, c)| match i {
+                0 => format!("{c}"),
+                1 => format!("{c}x"),
+                _ => format!("{c}x^{i}"),
+            })
+            .collect();
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constants::EPSILON;
+
+    fn assert_roots(roots: Vec<f32>, expected: &[f32]) {
+        assert_eq!(roots.len(), expected.len(), "{roots:?} != {expected:?}");
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!(
+                (root - expected).abs() < EPSILON,
+                "{roots:?} != {expected:?}"
+            );
+        }
+    }
+
+    // Function solve_quadratic()
+    #[test]
+    fn test1_solve_quadratic() {
+        assert_roots(solve_quadratic(1., -3., 2.), &[1., 2.]);
+        assert_roots(solve_quadratic(2., 0., -8.), &[-2., 2.]);
+        assert_roots(solve_quadratic(1., -2., 1.), &[1.]);
+        assert_roots(solve_quadratic(1., 0., 1.), &[]);
+        assert_roots(solve_quadratic(0., 2., -1.), &[0.5]);
+        assert_roots(solve_quadratic(0., 0., 1.), &[]);
+    }
+    #[test]
+    fn test2_solve_quadratic() {
+        // The textbook formula loses the small root to cancellation
+        let roots = solve_quadratic(1., -1e4, 1.);
+        assert!((roots[0] - 1e-4).abs() < 1e-9);
+        assert!((roots[1] - 1e4).abs() < 1e-1);
+    }
+    // Function solve_cubic()
+    #[test]
+    fn test1_solve_cubic() {
+        assert_roots(solve_cubic(1., -6., 11., -6.), &[1., 2., 3.]);
+        assert_roots(solve_cubic(2., 0., 0., -16.), &[2.]);
+        assert_roots(solve_cubic(1., -4., 5., -2.), &[1., 2.]);
+        assert_roots(solve_cubic(1., -3., 3., -1.), &[1.]);
+        assert_roots(solve_cubic(1., 0., 1., 0.), &[0.]);
+        assert_roots(solve_cubic(0., 1., -3., 2.), &[1., 2.]);
+    }
+    #[test]
+    fn test2_solve_cubic() {
+        // Widely spread roots -1000, 0.001, 2
+        let (a, b, c) = (-1000_f64, 0.001, 2.);
+        let coefficients = [1., -(a + b + c), a * b + a * c + b * c, -a * b * c].map(|c| c as f32);
+        let roots = solve_cubic(
+            coefficients[0],
+            coefficients[1],
+            coefficients[2],
+            coefficients[3],
+        );
+        assert_eq!(roots.len(), 3);
+        assert!((roots[0] + 1000.).abs() < 1e-2);
+        assert!((roots[1] - 0.001).abs() < 1e-5);
+        assert!((roots[2] - 2.).abs() < 1e-4);
+    }
+    // Function solve_quartic()
+    #[test]
+    fn test1_solve_quartic() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        assert_roots(solve_quartic(1., -10., 35., -50., 24.), &[1., 2., 3., 4.]);
+        // (x^2 - 1)(x^2 - 4), biquadratic
+        assert_roots(solve_quartic(1., 0., -5., 0., 4.), &[-2., -1., 1., 2.]);
+        // (x^2 + 1)(x - 1)(x + 3)
+        assert_roots(solve_quartic(1., 2., -2., 2., -3.), &[-3., 1.]);
+        assert_roots(solve_quartic(1., 0., 0., 0., 1.), &[]);
+        // (x - 1)^2 (x + 2)^2
+        assert_roots(solve_quartic(1., 2., -3., -4., 4.), &[-2., 1.]);
+        assert_roots(solve_quartic(0., 1., -6., 11., -6.), &[1., 2., 3.]);
+    }
+    #[test]
+    fn test2_solve_quartic() {
+        // Ray (t, 0, 0.5) against a torus of radii 2 and 1 around the z axis,
+        // (t^2 + 0.25 + 3)^2 - 16 t^2 = 0
+        let roots = solve_quartic(1., 0., 6.5 - 16., 0., 3.25 * 3.25);
+        let inner = (2. - 0.75_f32.sqrt(), 2. + 0.75_f32.sqrt());
+        assert_roots(roots, &[-inner.1, -inner.0, inner.0, inner.1]);
+    }
+    // Polynomial
+    #[test]
+    fn test1_polynomialnew() {
+        let poly = Polynomial::new(vec![1., 2., 0., 0.]);
+        assert_eq!(poly.coefficients(), &[1., 2.]);
+        assert_eq!(poly.degree(), Some(1));
+        assert_eq!(Polynomial::new(vec![0.]).degree(), None);
+        assert_eq!(
+            format!("{}", Polynomial::new(vec![1., 0., -3.])),
+            "1 + -3x^2"
+        );
+    }
+    #[test]
+    fn test1_polynomialevaluate() {
+        let poly = Polynomial::new(vec![-6., 11., -6., 1.]);
+        assert_eq!(poly.evaluate(0.), -6.);
+        assert_eq!(poly.evaluate(4.), 6.);
+        assert_eq!(poly.derivative(), Polynomial::new(vec![11., -12., 3.]));
+        assert_eq!(
+            poly.derivative().derivative().derivative().derivative(),
+            Polynomial::default()
+        );
+    }
+    #[test]
+    fn test1_polynomialrefine_root() {
+        let poly = Polynomial::new(vec![-2., 0., 1.]);
+        let root = poly.refine_root(0., 5.).unwrap();
+        assert!((root - 2_f32.sqrt()).abs() < 1e-6);
+        assert_eq!(poly.refine_root(-1., 1.), None);
+        assert_eq!(poly.refine_root(2., -2.), None);
+        assert_eq!(Polynomial::new(vec![-1., 1.]).refine_root(1., 3.), Some(1.));
+    }
+    #[test]
+    fn test1_polynomialroots_in() {
+        // (x + 2)(x - 0.5)(x - 1)^2 (x - 3)
+        let factors = [-2., 0.5, 1., 1., 3.];
+        let poly = factors
+            .iter()
+            .fold(Polynomial::new(vec![1.]), |poly, root| {
+                let mut coefficients = vec![0.; poly.coefficients().len() + 1];
+                for (i, c) in poly.coefficients().iter().enumerate() {
+                    coefficients[i + 1] += c;
+                    coefficients[i] -= c * root;
+                }
+                Polynomial::new(coefficients)
+            });
+        assert_eq!(poly.degree(), Some(5));
+        assert_roots(poly.roots(), &[-2., 0.5, 1., 3.]);
+        assert_roots(poly.roots_in(0., 2.), &[0.5, 1.]);
+        assert_roots(poly.roots_in(1.5, 2.5), &[]);
+        assert_roots(
+            Polynomial::new(vec![-6., 11., -6., 1.]).roots(),
+            &[1., 2., 3.],
+        );
+        assert_roots(Polynomial::new(vec![4.]).roots(), &[]);
+    }
+}
@@ -0,0 +1,691 @@
+use crate::{
+    geometry::{aabb::Aabb3, frustum::Frustum, ray::Ray3},
+    Vector3,
+};
+
+const SAH_BINS: usize = 12;
+
+#[derive(Debug, Clone, Copy)]
+enum NodeKind {
+    Leaf(usize),
+    Branch(usize, usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    bounds: Aabb3,
+    parent: Option<usize>,
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone)]
+struct Item<T> {
+    value: T,
+    bounds: Aabb3,
+    leaf: usize,
+}
+
+// Bounding volume hierarchy with one primitive per leaf. Primitives are addressed by the
+// id returned from insert() (or their position in the input of build()), which stays
+// valid until the primitive is removed.
+#[derive(Debug, Clone)]
+pub struct Bvh<T> {
+    nodes: Vec<Node>,
+    free_nodes: Vec<usize>,
+    items: Vec<Option<Item<T>>>,
+    free_items: Vec<usize>,
+    root: Option<usize>,
+}
+impl<T> Default for Bvh<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            items: Vec::new(),
+            free_items: Vec::new(),
+            root: None,
+        }
+    }
+}
+impl<T> Bvh<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build<I>(primitives: I) -> Self
+    where
+        I: IntoIterator<Item = (T, Aabb3)>,
+    {
+        let mut bvh = Self::new();
+        for (value, bounds) in primitives {
+            bvh.items.push(Some(Item {
+                value,
+                bounds,
+                leaf: 0,
+            }));
+        }
+        bvh.rebuild();
+        bvh
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() - self.free_items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn root_bounds(&self) -> Option<Aabb3> {
+        Some(self.nodes[self.root?].bounds)
+    }
+
+    pub fn get(&self, id: usize) -> Option<&T> {
+        Some(&self.items.get(id)?.as_ref()?.value)
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut T> {
+        Some(&mut self.items.get_mut(id)?.as_mut()?.value)
+    }
+
+    pub fn bounds(&self, id: usize) -> Option<Aabb3> {
+        Some(self.items.get(id)?.as_ref()?.bounds)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(id, item)| Some((id, &item.as_ref()?.value)))
+    }
+
+    // Full SAH rebuild over the current primitives, ids are preserved
+    pub fn rebuild(&mut self) {
+        self.nodes.clear();
+        self.free_nodes.clear();
+        let mut refs: Vec<(usize, Aabb3, Vector3)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(id, item)| {
+                let bounds = item.as_ref()?.bounds;
+                Some((id, bounds, bounds.center()))
+            })
+            .collect();
+        self.root = if refs.is_empty() {
+            None
+        } else {
+            Some(self.build_node(&mut refs, None))
+        };
+    }
+
+    fn build_node(&mut self, refs: &mut [(usize, Aabb3, Vector3)], parent: Option<usize>) -> usize {
+        if let [(id, bounds, _)] = refs {
+            let node = self.allocate_node(Node {
+                bounds: *bounds,
+                parent,
+                kind: NodeKind::Leaf(*id),
+            });
+            if let Some(item) = self.items[*id].as_mut() {
+                item.leaf = node;
+            }
+            return node;
+        }
+        let mid = sah_split(refs);
+        let node = self.allocate_node(Node {
+            bounds: Aabb3::empty(),
+            parent,
+            kind: NodeKind::Leaf(0),
+        });
+        let (left_refs, right_refs) = refs.split_at_mut(mid);
+        let left = self.build_node(left_refs, Some(node));
+        let right = self.build_node(right_refs, Some(node));
+        self.nodes[node].kind = NodeKind::Branch(left, right);
+        self.nodes[node].bounds = self.nodes[left].bounds.union(self.nodes[right].bounds);
+        node
+    }
+
+    fn allocate_node(&mut self, node: Node) -> usize {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    pub fn insert(&mut self, value: T, bounds: Aabb3) -> usize {
+        let item = Item {
+            value,
+            bounds,
+            leaf: 0,
+        };
+        let id = match self.free_items.pop() {
+            Some(id) => {
+                self.items[id] = Some(item);
+                id
+            }
+            None => {
+                self.items.push(Some(item));
+                self.items.len() - 1
+            }
+        };
+        let leaf = self.allocate_node(Node {
+            bounds,
+            parent: None,
+            kind: NodeKind::Leaf(id),
+        });
+        if let Some(item) = self.items[id].as_mut() {
+            item.leaf = leaf;
+        }
+        self.insert_leaf(leaf);
+        id
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let root = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(leaf);
+                self.nodes[leaf].parent = None;
+                return;
+            }
+        };
+        let bounds = self.nodes[leaf].bounds;
+
+        // Greedy descent towards the sibling whose pairing increases the total area least
+        let mut sibling = root;
+        while let NodeKind::Branch(left, right) = self.nodes[sibling].kind {
+            let area = self.nodes[sibling].bounds.surface_area();
+            let combined_area = self.nodes[sibling].bounds.union(bounds).surface_area();
+            let cost = 2. * combined_area;
+            let inheritance_cost = 2. * (combined_area - area);
+            let child_cost = |child: usize| {
+                let child_bounds = self.nodes[child].bounds;
+                let enlarged = child_bounds.union(bounds).surface_area();
+                match self.nodes[child].kind {
+                    NodeKind::Leaf(_) => enlarged + inheritance_cost,
+                    NodeKind::Branch(..) => {
+                        enlarged - child_bounds.surface_area() + inheritance_cost
+                    }
+                }
+            };
+            let (left_cost, right_cost) = (child_cost(left), child_cost(right));
+            if cost < left_cost && cost < right_cost {
+                break;
+            }
+            sibling = if left_cost < right_cost { left } else { right };
+        }
+
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate_node(Node {
+            bounds: self.nodes[sibling].bounds.union(bounds),
+            parent: old_parent,
+            kind: NodeKind::Branch(sibling, leaf),
+        });
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+        match old_parent {
+            Some(parent) => {
+                self.replace_child(parent, sibling, new_parent);
+                self.refit_ancestors(parent);
+            }
+            None => self.root = Some(new_parent),
+        }
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<T> {
+        let item = self.items.get_mut(id)?.take()?;
+        self.free_items.push(id);
+        self.remove_leaf(item.leaf);
+        self.free_nodes.push(item.leaf);
+        Some(item.value)
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        let parent = match self.nodes[leaf].parent {
+            Some(parent) => parent,
+            None => {
+                self.root = None;
+                return;
+            }
+        };
+        let sibling = match self.nodes[parent].kind {
+            NodeKind::Branch(left, right) if left == leaf => right,
+            NodeKind::Branch(left, _) => left,
+            NodeKind::Leaf(_) => unreachable!("Parent of a BVH node must be a branch"),
+        };
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.free_nodes.push(parent);
+        match grandparent {
+            Some(grandparent) => {
+                self.replace_child(grandparent, parent, sibling);
+                self.refit_ancestors(grandparent);
+            }
+            None => self.root = Some(sibling),
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old_child: usize, new_child: usize) {
+        if let NodeKind::Branch(left, right) = self.nodes[parent].kind {
+            self.nodes[parent].kind = if left == old_child {
+                NodeKind::Branch(new_child, right)
+            } else {
+                NodeKind::Branch(left, new_child)
+            };
+        }
+    }
+
+    fn refit_ancestors(&mut self, mut node: usize) {
+        loop {
+            if let NodeKind::Branch(left, right) = self.nodes[node].kind {
+                self.nodes[node].bounds = self.nodes[left].bounds.union(self.nodes[right].bounds);
+            }
+            match self.nodes[node].parent {
+                Some(parent) => node = parent,
+                None => return,
+            }
+        }
+    }
+
+    // Moves a primitive by reinserting its leaf, keeping the tree quality close to a fresh insert
+    pub fn update(&mut self, id: usize, bounds: Aabb3) -> bool {
+        let leaf = match self.items.get_mut(id).and_then(Option::as_mut) {
+            Some(item) => {
+                item.bounds = bounds;
+                item.leaf
+            }
+            None => return false,
+        };
+        self.remove_leaf(leaf);
+        self.nodes[leaf].bounds = bounds;
+        self.insert_leaf(leaf);
+        true
+    }
+
+    // Changes the bounds of a primitive without touching the tree, call refit() afterwards
+    pub fn set_bounds(&mut self, id: usize, bounds: Aabb3) -> bool {
+        match self.items.get_mut(id).and_then(Option::as_mut) {
+            Some(item) => {
+                item.bounds = bounds;
+                self.nodes[item.leaf].bounds = bounds;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Recomputes all branch bounds bottom-up while keeping the topology
+    pub fn refit(&mut self) {
+        let root = match self.root {
+            Some(root) => root,
+            None => return,
+        };
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            if let NodeKind::Branch(left, right) = self.nodes[node].kind {
+                stack.push(left);
+                stack.push(right);
+            }
+        }
+        for &node in order.iter().rev() {
+            if let NodeKind::Branch(left, right) = self.nodes[node].kind {
+                self.nodes[node].bounds = self.nodes[left].bounds.union(self.nodes[right].bounds);
+            }
+        }
+    }
+
+    fn traverse<P, F>(&self, mut overlaps: P, mut visitor: F)
+    where
+        P: FnMut(Aabb3) -> bool,
+        F: FnMut(usize, &T),
+    {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let Node { bounds, kind, .. } = self.nodes[node];
+            if !overlaps(bounds) {
+                continue;
+            }
+            match kind {
+                NodeKind::Leaf(id) => {
+                    if let Some(item) = self.items[id].as_ref() {
+                        visitor(id, &item.value);
+                    }
+                }
+                NodeKind::Branch(left, right) => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+    }
+
+    pub fn query_aabb<F>(&self, aabb: Aabb3, visitor: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        self.traverse(|bounds| bounds.intersects(aabb), visitor);
+    }
+
+    pub fn query_frustum<F>(&self, frustum: Frustum, visitor: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        self.traverse(|bounds| frustum.intersects_aabb(bounds), visitor);
+    }
+
+    // Visits every primitive whose bounds are hit by the ray within max_distance
+    pub fn query_ray<F>(&self, ray: Ray3, max_distance: f32, visitor: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        self.traverse(
+            |bounds| ray.intersect_aabb(bounds, max_distance).is_some(),
+            visitor,
+        );
+    }
+
+    // Finds the closest hit. The callback performs the exact primitive test and returns the
+    // hit distance along the ray, nodes farther than the best hit so far are skipped.
+    pub fn raycast<F>(&self, ray: Ray3, max_distance: f32, mut hit: F) -> Option<(usize, f32)>
+    where
+        F: FnMut(usize, &T) -> Option<f32>,
+    {
+        let mut closest: Option<(usize, f32)> = None;
+        let mut max_distance = max_distance;
+        let mut stack: Vec<(usize, f32)> = Vec::new();
+        if let Some(root) = self.root {
+            if let Some((t_enter, _)) = ray.intersect_aabb(self.nodes[root].bounds, max_distance) {
+                stack.push((root, t_enter));
+            }
+        }
+        while let Some((node, t_enter)) = stack.pop() {
+            if t_enter > max_distance {
+                continue;
+            }
+            match self.nodes[node].kind {
+                NodeKind::Leaf(id) => {
+                    let item = match self.items[id].as_ref() {
+                        Some(item) => item,
+                        None => continue,
+                    };
+                    if let Some(distance) = hit(id, &item.value) {
+                        if (0. ..=max_distance).contains(&distance) {
+                            max_distance = distance;
+                            closest = Some((id, distance));
+                        }
+                    }
+                }
+                NodeKind::Branch(left, right) => {
+                    let enter = |child: usize| {
+                        ray.intersect_aabb(self.nodes[child].bounds, max_distance)
+                            .map(|(t_enter, _)| (child, t_enter))
+                    };
+                    // Push the farther child first so the nearer one is processed next
+                    match (enter(left), enter(right)) {
+                        (Some(a), Some(b)) if a.1 <= b.1 => stack.extend([b, a]),
+                        (Some(a), Some(b)) => stack.extend([a, b]),
+                        (Some(a), None) | (None, Some(a)) => stack.push(a),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        closest
+    }
+}
+
+// Binned surface area heuristic, reorders refs and returns the split position
+fn sah_split(refs: &mut [(usize, Aabb3, Vector3)]) -> usize {
+    let centroid_bounds = refs.iter().fold(Aabb3::empty(), |aabb, (_, _, centroid)| {
+        aabb.expand_to(*centroid)
+    });
+    let extent = centroid_bounds.size();
+    let axis = if extent.x >= extent.y && extent.x >= extent.z {
+        0
+    } else if extent.y >= extent.z {
+        1
+    } else {
+        2
+    };
+    let (axis_min, axis_extent) = (centroid_bounds.min[axis], extent[axis]);
+    if axis_extent <= f32::EPSILON {
+        return refs.len() / 2;
+    }
+
+    let bin_of = |centroid: Vector3| {
+        let bin = ((centroid[axis] - axis_min) / axis_extent * SAH_BINS as f32) as usize;
+        bin.min(SAH_BINS - 1)
+    };
+    let mut bin_bounds = [Aabb3::empty(); SAH_BINS];
+    let mut bin_counts = [0_usize; SAH_BINS];
+    for (_, bounds, centroid) in refs.iter() {
+        let bin = bin_of(*centroid);
+        bin_bounds[bin] = bin_bounds[bin].union(*bounds);
+        bin_counts[bin] += 1;
+    }
+
+    // Sweep from the right to get the cost of every split plane between bins
+    let mut right_area = [0_f32; SAH_BINS];
+    let mut right_count = [0_usize; SAH_BINS];
+    let (mut bounds, mut count) = (Aabb3::empty(), 0);
+    for bin in (1..SAH_BINS).rev() {
+        bounds = bounds.union(bin_bounds[bin]);
+        count += bin_counts[bin];
+        right_area[bin] = bounds.surface_area();
+        right_count[bin] = count;
+    }
+    let (mut best_split, mut best_cost) = (0, f32::INFINITY);
+    let (mut bounds, mut count) = (Aabb3::empty(), 0);
+    for split in 1..SAH_BINS {
+        bounds = bounds.union(bin_bounds[split - 1]);
+        count += bin_counts[split - 1];
+        if count == 0 || right_count[split] == 0 {
+            continue;
+        }
+        let cost =
+            bounds.surface_area() * count as f32 + right_area[split] * right_count[split] as f32;
+        if cost < best_cost {
+            best_cost = cost;
+            best_split = split;
+        }
+    }
+    if best_split == 0 {
+        return refs.len() / 2;
+    }
+
+    let mut mid = 0;
+    for i in 0..refs.len() {
+        if bin_of(refs[i].2) < best_split {
+            refs.swap(i, mid);
+            mid += 1;
+        }
+    }
+    mid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::homogeneous::ortho3;
+    use crate::Vector;
+
+    fn grid_boxes(n: usize) -> Vec<(usize, Aabb3)> {
+        let mut boxes = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    let min = Vector3::new(i as f32, j as f32, k as f32) * 2.;
+                    boxes.push((boxes.len(), Aabb3::new(min, min + Vector3::from(1.))));
+                }
+            }
+        }
+        boxes
+    }
+
+    fn collect_aabb(bvh: &Bvh<usize>, aabb: Aabb3) -> Vec<usize> {
+        let mut found = Vec::new();
+        bvh.query_aabb(aabb, |_, value| found.push(*value));
+        found.sort();
+        found
+    }
+
+    fn brute_force_aabb(boxes: &[(usize, Aabb3)], aabb: Aabb3) -> Vec<usize> {
+        boxes
+            .iter()
+            .filter(|(_, bounds)| bounds.intersects(aabb))
+            .map(|(value, _)| *value)
+            .collect()
+    }
+
+    // Constructors
+    #[test]
+    fn test1_bvhbuild() {
+        let boxes = grid_boxes(4);
+        let bvh = Bvh::build(boxes.clone());
+        assert_eq!(bvh.len(), 64);
+        assert_eq!(
+            bvh.root_bounds(),
+            Some(Aabb3::new(Vector3::zero(), Vector3::from(7.)))
+        );
+        for (id, bounds) in boxes {
+            assert_eq!(bvh.get(id), Some(&id));
+            assert_eq!(bvh.bounds(id), Some(bounds));
+        }
+    }
+    #[test]
+    fn test2_bvhbuild() {
+        let bvh = Bvh::<usize>::build(Vec::new());
+        assert!(bvh.is_empty());
+        assert_eq!(bvh.root_bounds(), None);
+    }
+    // Method query_aabb(aabb, visitor)
+    #[test]
+    fn test1_bvhquery_aabb() {
+        let boxes = grid_boxes(5);
+        let bvh = Bvh::build(boxes.clone());
+        let query = Aabb3::new(Vector3::new(1.5, 0., 2.5), Vector3::new(4.5, 3., 6.));
+        assert_eq!(collect_aabb(&bvh, query), brute_force_aabb(&boxes, query));
+    }
+    #[test]
+    fn test2_bvhquery_aabb() {
+        let bvh = Bvh::build(grid_boxes(3));
+        let query = Aabb3::new(Vector3::from(20.), Vector3::from(21.));
+        assert!(collect_aabb(&bvh, query).is_empty());
+    }
+    // Method query_frustum(frustum, visitor)
+    #[test]
+    fn test1_bvhquery_frustum() {
+        let bvh = Bvh::build(grid_boxes(4));
+        let frustum = Frustum::from_matrix(ortho3(-0.5, 1.5, -0.5, 1.5, -1.5, 0.5));
+        let mut found = Vec::new();
+        bvh.query_frustum(frustum, |id, _| found.push(id));
+        assert_eq!(found, vec![0]);
+    }
+    // Methods query_ray(ray, max_distance, visitor), raycast(ray, max_distance, hit)
+    #[test]
+    fn test1_bvhquery_ray() {
+        let boxes = grid_boxes(4);
+        let bvh = Bvh::build(boxes.clone());
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, -1.), Vector3::new(0., 0., 1.));
+        let mut found = Vec::new();
+        bvh.query_ray(ray, f32::INFINITY, |_, value| found.push(*value));
+        found.sort();
+        assert_eq!(found, vec![0, 1, 2, 3]);
+    }
+    #[test]
+    fn test1_bvhraycast() {
+        let boxes = grid_boxes(4);
+        let bvh = Bvh::build(boxes.clone());
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, 10.), Vector3::new(0., 0., -1.));
+        let hit = bvh.raycast(ray, f32::INFINITY, |id, _| {
+            ray.intersect_aabb(boxes[id].1, f32::INFINITY)
+                .map(|(t, _)| t)
+        });
+        assert_eq!(hit, Some((3, 3.)));
+    }
+    #[test]
+    fn test2_bvhraycast() {
+        let boxes = grid_boxes(4);
+        let bvh = Bvh::build(boxes.clone());
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, 10.), Vector3::new(0., 0., -1.));
+        let hit = bvh.raycast(ray, 2., |id, _| {
+            ray.intersect_aabb(boxes[id].1, f32::INFINITY)
+                .map(|(t, _)| t)
+        });
+        assert_eq!(hit, None);
+    }
+    // Methods insert(value, bounds), remove(id)
+    #[test]
+    fn test1_bvhinsert() {
+        let boxes = grid_boxes(4);
+        let mut bvh = Bvh::new();
+        for (value, bounds) in boxes.iter() {
+            assert_eq!(bvh.insert(*value, *bounds), *value);
+        }
+        assert_eq!(bvh.len(), boxes.len());
+        let query = Aabb3::new(Vector3::new(1.5, 1.5, 0.), Vector3::new(3., 5., 2.));
+        assert_eq!(collect_aabb(&bvh, query), brute_force_aabb(&boxes, query));
+    }
+    #[test]
+    fn test1_bvhremove() {
+        let boxes = grid_boxes(4);
+        let mut bvh = Bvh::build(boxes.clone());
+        for id in (0..boxes.len()).step_by(3) {
+            assert_eq!(bvh.remove(id), Some(id));
+        }
+        assert_eq!(bvh.remove(0), None);
+        let remaining: Vec<_> = boxes.into_iter().filter(|(id, _)| id % 3 != 0).collect();
+        assert_eq!(bvh.len(), remaining.len());
+        let query = Aabb3::new(Vector3::zero(), Vector3::from(7.));
+        assert_eq!(
+            collect_aabb(&bvh, query),
+            brute_force_aabb(&remaining, query)
+        );
+    }
+    #[test]
+    fn test2_bvhremove() {
+        let mut bvh = Bvh::new();
+        let id = bvh.insert("a", Aabb3::new(Vector3::zero(), Vector3::from(1.)));
+        assert_eq!(bvh.remove(id), Some("a"));
+        assert!(bvh.is_empty());
+        assert_eq!(bvh.root_bounds(), None);
+        let id = bvh.insert("b", Aabb3::new(Vector3::zero(), Vector3::from(2.)));
+        assert_eq!(bvh.get(id), Some(&"b"));
+    }
+    // Methods update(id, bounds), set_bounds(id, bounds), refit()
+    #[test]
+    fn test1_bvhupdate() {
+        let mut boxes = grid_boxes(3);
+        let mut bvh = Bvh::build(boxes.clone());
+        let moved = Aabb3::new(Vector3::from(10.), Vector3::from(11.));
+        assert!(bvh.update(4, moved));
+        boxes[4].1 = moved;
+        let query = Aabb3::new(Vector3::from(0.), Vector3::from(12.));
+        assert_eq!(collect_aabb(&bvh, query), brute_force_aabb(&boxes, query));
+        assert_eq!(collect_aabb(&bvh, moved), vec![4]);
+    }
+    #[test]
+    fn test1_bvhrefit() {
+        let mut boxes = grid_boxes(3);
+        let mut bvh = Bvh::build(boxes.clone());
+        for (id, bounds) in boxes.iter_mut() {
+            *bounds = bounds.outset(Vector3::from(0.5 * (*id % 2) as f32));
+            bvh.set_bounds(*id, *bounds);
+        }
+        bvh.refit();
+        for query in [
+            Aabb3::new(Vector3::from(1.2), Vector3::from(1.4)),
+            Aabb3::new(Vector3::new(-0.4, 0., 0.), Vector3::new(-0.3, 1., 1.)),
+        ] {
+            assert_eq!(collect_aabb(&bvh, query), brute_force_aabb(&boxes, query));
+        }
+    }
+}
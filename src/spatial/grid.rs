@@ -0,0 +1,365 @@
+use crate::{geometry::ray::Ray3, Vector, Vector3};
+
+// Spatial hash over an unbounded uniform grid. Points are bucketed with a counting sort into
+// a table sized from the point count, so rebuild() is two linear passes and reuses memory.
+#[derive(Debug, Clone, Default)]
+pub struct SpatialHash {
+    cell_size: f32,
+    positions: Vec<Vector3>,
+    cells: Vec<[i32; 3]>,
+    bucket_start: Vec<usize>,
+    // Write cursor per bucket while filling entries, kept to reuse its memory
+    bucket_next: Vec<usize>,
+    entries: Vec<usize>,
+}
+impl SpatialHash {
+    pub fn try_new(cell_size: f32) -> Option<Self> {
+        if !cell_size.is_finite() || cell_size <= 0. {
+            return None;
+        }
+        Some(Self {
+            cell_size,
+            ..Default::default()
+        })
+    }
+
+    pub fn new(cell_size: f32) -> Self {
+        Self::try_new(cell_size)
+            .expect("It is impossible to build a spatial hash with a non-positive cell size")
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn positions(&self) -> &[Vector3] {
+        &self.positions
+    }
+
+    pub fn cell_of(&self, point: Vector3) -> [i32; 3] {
+        cell_of(point, self.cell_size)
+    }
+
+    fn bucket_of(&self, cell: [i32; 3]) -> usize {
+        let [x, y, z] = cell;
+        let hash =
+            x.wrapping_mul(92_837_111) ^ y.wrapping_mul(689_287_499) ^ z.wrapping_mul(283_923_481);
+        hash as u32 as usize % (self.bucket_start.len() - 1)
+    }
+
+    pub fn rebuild(&mut self, positions: &[Vector3]) {
+        self.positions.clear();
+        self.positions.extend_from_slice(positions);
+        self.cells.clear();
+        self.cells.extend(
+            positions
+                .iter()
+                .map(|point| cell_of(*point, self.cell_size)),
+        );
+
+        let table_size = 2 * positions.len().max(1);
+        self.bucket_start.clear();
+        self.bucket_start.resize(table_size + 1, 0);
+        for i in 0..self.cells.len() {
+            let bucket = self.bucket_of(self.cells[i]);
+            self.bucket_start[bucket + 1] += 1;
+        }
+        for bucket in 0..table_size {
+            self.bucket_start[bucket + 1] += self.bucket_start[bucket];
+        }
+        self.entries.clear();
+        self.entries.resize(positions.len(), 0);
+        self.bucket_next.clear();
+        self.bucket_next.extend_from_slice(&self.bucket_start);
+        for i in 0..self.cells.len() {
+            let bucket = self.bucket_of(self.cells[i]);
+            self.entries[self.bucket_next[bucket]] = i;
+            self.bucket_next[bucket] += 1;
+        }
+    }
+
+    // Indices of the points stored in one cell
+    pub fn cell(&self, cell: [i32; 3]) -> impl Iterator<Item = usize> + '_ {
+        let bucket = if self.entries.is_empty() {
+            0..0
+        } else {
+            let bucket = self.bucket_of(cell);
+            self.bucket_start[bucket]..self.bucket_start[bucket + 1]
+        };
+        self.entries[bucket]
+            .iter()
+            .copied()
+            .filter(move |i| self.cells[*i] == cell)
+    }
+
+    // Calls the visitor with the index and squared distance of every point within the radius
+    pub fn for_each_neighbour<F>(&self, point: Vector3, radius: f32, mut visitor: F)
+    where
+        F: FnMut(usize, f32),
+    {
+        let radius_squared = radius * radius;
+        let mut visit = |i: usize| {
+            let offset = self.positions[i] - point;
+            let distance_squared = offset.dot(offset);
+            if distance_squared <= radius_squared {
+                visitor(i, distance_squared);
+            }
+        };
+        let min = self.cell_of(point - Vector3::from(radius));
+        let max = self.cell_of(point + Vector3::from(radius));
+        // A radius spanning more cells than there are points is cheaper to answer by a scan
+        let cells = (0..3)
+            .map(|axis| (max[axis] as i64 - min[axis] as i64 + 1).max(0) as usize)
+            .fold(1_usize, usize::saturating_mul);
+        if cells > self.positions.len() {
+            (0..self.positions.len()).for_each(visit);
+            return;
+        }
+        for x in min[0]..=max[0] {
+            for y in min[1]..=max[1] {
+                for z in min[2]..=max[2] {
+                    self.cell([x, y, z]).for_each(&mut visit);
+                }
+            }
+        }
+    }
+
+    pub fn neighbours(&self, point: Vector3, radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.for_each_neighbour(point, radius, |i, _| found.push(i));
+        found
+    }
+
+    pub fn traverse(&self, ray: Ray3, max_distance: f32) -> CellTraversal {
+        CellTraversal::new(ray, self.cell_size, max_distance)
+    }
+}
+
+fn cell_of(point: Vector3, cell_size: f32) -> [i32; 3] {
+    [
+        (point.x / cell_size).floor() as i32,
+        (point.y / cell_size).floor() as i32,
+        (point.z / cell_size).floor() as i32,
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellHit {
+    pub cell: [i32; 3],
+    pub distance: f32,
+}
+
+// Amanatides-Woo traversal of the grid cells pierced by a ray, in order along the ray.
+// Distances are in units of the ray parameter, so they are metric for a unit direction.
+#[derive(Debug, Clone)]
+pub struct CellTraversal {
+    cell: [i32; 3],
+    step: [i32; 3],
+    t_max: [f32; 3],
+    t_delta: [f32; 3],
+    distance: f32,
+    max_distance: f32,
+}
+impl CellTraversal {
+    pub fn new(ray: Ray3, cell_size: f32, max_distance: f32) -> Self {
+        let cell = cell_of(ray.origin, cell_size);
+        let (mut step, mut t_max, mut t_delta) = ([0; 3], [f32::INFINITY; 3], [f32::INFINITY; 3]);
+        for axis in 0..3 {
+            let (origin, direction) = (ray.origin[axis], ray.direction[axis]);
+            if direction > 0. {
+                step[axis] = 1;
+                t_max[axis] = ((cell[axis] as f32 + 1.) * cell_size - origin) / direction;
+                t_delta[axis] = cell_size / direction;
+            } else if direction < 0. {
+                step[axis] = -1;
+                t_max[axis] = (cell[axis] as f32 * cell_size - origin) / direction;
+                t_delta[axis] = -cell_size / direction;
+            }
+        }
+        Self {
+            cell,
+            step,
+            t_max,
+            t_delta,
+            distance: 0.,
+            max_distance,
+        }
+    }
+}
+impl Iterator for CellTraversal {
+    type Item = CellHit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A zero or NaN direction never leaves its cell, its next boundary is at infinity
+        if self.distance > self.max_distance || !self.distance.is_finite() {
+            return None;
+        }
+        let hit = CellHit {
+            cell: self.cell,
+            distance: self.distance,
+        };
+        let axis = if self.t_max[0] <= self.t_max[1] && self.t_max[0] <= self.t_max[2] {
+            0
+        } else if self.t_max[1] <= self.t_max[2] {
+            1
+        } else {
+            2
+        };
+        self.distance = self.t_max[axis];
+        self.t_max[axis] += self.t_delta[axis];
+        match self.cell[axis].checked_add(self.step[axis]) {
+            Some(cell) => self.cell[axis] = cell,
+            // Cell coordinates are exhausted, end the traversal here
+            None => self.distance = f32::INFINITY,
+        }
+        Some(hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constants::EPSILON;
+
+    fn particles() -> Vec<Vector3> {
+        (0..400)
+            .map(|i| {
+                let t = i as f32;
+                Vector3::new((t * 0.731).sin(), (t * 0.317).cos(), (t * 0.193).sin()) * 5.
+            })
+            .collect()
+    }
+
+    // Function try_new(cell_size)
+    #[test]
+    fn test1_spatialhashtry_new() {
+        for cell_size in [0., -1., f32::NAN, f32::INFINITY] {
+            assert!(SpatialHash::try_new(cell_size).is_none());
+        }
+        assert_eq!(SpatialHash::try_new(0.5).unwrap().cell_size(), 0.5);
+    }
+    // Method rebuild(positions)
+    #[test]
+    fn test1_spatialhashrebuild() {
+        let points = particles();
+        let mut hash = SpatialHash::new(0.5);
+        hash.rebuild(&points);
+        assert_eq!(hash.len(), points.len());
+        for (i, point) in points.iter().enumerate() {
+            assert!(hash.cell(hash.cell_of(*point)).any(|j| j == i));
+        }
+    }
+    #[test]
+    fn test2_spatialhashrebuild() {
+        let mut hash = SpatialHash::new(1.);
+        hash.rebuild(&particles());
+        hash.rebuild(&[]);
+        assert!(hash.is_empty());
+        assert!(hash.neighbours(Vector3::zero(), 10.).is_empty());
+    }
+    // Method cell_of(point)
+    #[test]
+    fn test1_spatialhashcell_of() {
+        let hash = SpatialHash::new(2.);
+        assert_eq!(hash.cell_of(Vector3::new(0.5, 3.9, -0.1)), [0, 1, -1]);
+        assert_eq!(hash.cell_of(Vector3::new(-4., 4., -4.1)), [-2, 2, -3]);
+    }
+    // Methods neighbours(point, radius), for_each_neighbour(point, radius, visitor)
+    #[test]
+    fn test1_spatialhashneighbours() {
+        let points = particles();
+        let mut hash = SpatialHash::new(0.75);
+        hash.rebuild(&points);
+        for query in [Vector3::zero(), Vector3::new(2., -3., 1.), points[17]] {
+            let mut found = hash.neighbours(query, 1.3);
+            found.sort();
+            let exact: Vec<usize> = (0..points.len())
+                .filter(|i| (points[*i] - query).len() <= 1.3)
+                .collect();
+            assert!(!exact.is_empty());
+            assert_eq!(found, exact);
+        }
+    }
+    #[test]
+    fn test1_spatialhashfor_each_neighbour() {
+        let points = [
+            Vector3::zero(),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(0., 3., 0.),
+        ];
+        let mut hash = SpatialHash::new(1.);
+        hash.rebuild(&points);
+        let mut found = Vec::new();
+        hash.for_each_neighbour(Vector3::new(0.5, 0., 0.), 1., |i, d| found.push((i, d)));
+        found.sort_by_key(|(i, _)| *i);
+        assert_eq!(found, vec![(0, 0.25), (1, 0.25)]);
+    }
+    #[test]
+    fn test2_spatialhashfor_each_neighbour() {
+        // Radii far beyond the grid fall back to a scan instead of visiting every cell
+        let points = particles();
+        let mut hash = SpatialHash::new(0.01);
+        hash.rebuild(&points);
+        for radius in [1e4, 1e30, f32::INFINITY] {
+            assert_eq!(hash.neighbours(Vector3::zero(), radius).len(), points.len());
+        }
+    }
+    // Method traverse(ray, max_distance)
+    #[test]
+    fn test1_celltraversalnext() {
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, 0.5), Vector3::new(1., 0., 0.));
+        let cells: Vec<[i32; 3]> = CellTraversal::new(ray, 1., 3.)
+            .map(|hit| hit.cell)
+            .collect();
+        assert_eq!(cells, vec![[0, 0, 0], [1, 0, 0], [2, 0, 0], [3, 0, 0]]);
+    }
+    #[test]
+    fn test2_celltraversalnext() {
+        let ray = Ray3::new(
+            Vector3::new(0.5, 0.5, 0.),
+            Vector3::new(-1., -2., 0.).normalize(),
+        );
+        let hits: Vec<CellHit> = SpatialHash::new(1.).traverse(ray, 2.).collect();
+        let cells: Vec<[i32; 3]> = hits.iter().map(|hit| hit.cell).collect();
+        assert_eq!(cells, vec![[0, 0, 0], [0, -1, 0], [-1, -1, 0], [-1, -2, 0]]);
+        for pair in hits.windows(2) {
+            assert!(pair[0].distance <= pair[1].distance);
+        }
+        let hit = hits[2];
+        let entry = ray.at(hit.distance);
+        assert!(entry.x.abs() < EPSILON && entry.y <= -0.5);
+    }
+    #[test]
+    fn test3_celltraversalnext() {
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, 0.5), Vector3::new(1., 1., 1.));
+        let mut traversal = CellTraversal::new(ray, 1., 10.);
+        for _ in 0..30 {
+            let hit = traversal.next().unwrap();
+            let center = Vector3::new(
+                hit.cell[0] as f32 + 0.5,
+                hit.cell[1] as f32 + 0.5,
+                hit.cell[2] as f32 + 0.5,
+            );
+            assert!((center - ray.at(hit.distance)).len() <= 3_f32.sqrt());
+        }
+    }
+    #[test]
+    fn test4_celltraversalnext() {
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, 0.5), Vector3::zero());
+        let cells: Vec<[i32; 3]> = CellTraversal::new(ray, 1., f32::INFINITY)
+            .map(|hit| hit.cell)
+            .collect();
+        assert_eq!(cells, vec![[0, 0, 0]]);
+        let ray = Ray3::new(Vector3::new(0.5, 0.5, 0.5), Vector3::new(1., 0., 0.));
+        let mut traversal = CellTraversal::new(ray, 1e-30, f32::INFINITY);
+        assert!(traversal.nth(1 << 16).is_none());
+    }
+}
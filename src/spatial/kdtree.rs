@@ -0,0 +1,275 @@
+use super::Ranked;
+use crate::Vector;
+use std::{collections::BinaryHeap, ops::Index};
+
+// Static k-d tree stored implicitly: the point splitting the range [lo, hi) sits at its
+// midpoint, so no node structure is needed beyond the reordered indices.
+#[derive(Debug, Clone)]
+pub struct KdTree<V> {
+    points: Vec<V>,
+    order: Vec<usize>,
+    axes: Vec<usize>,
+}
+impl<V> KdTree<V>
+where
+    V: Vector + Index<usize, Output = f32>,
+{
+    pub fn new(points: &[V]) -> Self {
+        let mut tree = Self {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+            axes: vec![0; points.len()],
+        };
+        tree.build(0, points.len());
+        tree
+    }
+
+    fn build(&mut self, lo: usize, hi: usize) {
+        if hi - lo <= 1 {
+            return;
+        }
+        let axis = self.widest_axis(lo, hi);
+        let mid = (lo + hi) / 2;
+        let points = &self.points;
+        self.order[lo..hi].select_nth_unstable_by(mid - lo, |a, b| {
+            points[*a][axis].total_cmp(&points[*b][axis])
+        });
+        self.axes[mid] = axis;
+        self.build(lo, mid);
+        self.build(mid + 1, hi);
+    }
+
+    fn widest_axis(&self, lo: usize, hi: usize) -> usize {
+        let dim = self.points[self.order[lo]].dim();
+        (0..dim)
+            .map(|axis| {
+                let (min, max) = self.order[lo..hi].iter().fold(
+                    (f32::INFINITY, f32::NEG_INFINITY),
+                    |(min, max), i| {
+                        let value = self.points[*i][axis];
+                        (min.min(value), max.max(value))
+                    },
+                );
+                (axis, max - min)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(axis, _)| axis)
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn nearest(&self, query: V) -> Option<(usize, f32)> {
+        self.k_nearest(query, 1).pop()
+    }
+
+    // Indices into the original slice with distances, closest first
+    pub fn k_nearest(&self, query: V, k: usize) -> Vec<(usize, f32)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        self.search_k(query, k, 0, self.len(), &mut heap);
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Ranked { distance, value }| (value, distance.sqrt()))
+            .collect()
+    }
+
+    fn search_k(
+        &self,
+        query: V,
+        k: usize,
+        lo: usize,
+        hi: usize,
+        heap: &mut BinaryHeap<Ranked<usize>>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.order[mid];
+        let point = self.points[index];
+        let distance = distance_squared(query, point);
+        if heap.len() < k {
+            heap.push(Ranked {
+                distance,
+                value: index,
+            });
+        } else if heap.peek().is_some_and(|worst| distance < worst.distance) {
+            heap.pop();
+            heap.push(Ranked {
+                distance,
+                value: index,
+            });
+        }
+        let axis = self.axes[mid];
+        let offset = query[axis] - point[axis];
+        let (near, far) = if offset < 0. {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search_k(query, k, near.0, near.1, heap);
+        if heap.len() < k
+            || heap
+                .peek()
+                .is_none_or(|worst| offset * offset < worst.distance)
+        {
+            self.search_k(query, k, far.0, far.1, heap);
+        }
+    }
+
+    // Indices into the original slice with distances, closest first
+    pub fn within_radius(&self, query: V, radius: f32) -> Vec<(usize, f32)> {
+        let mut found = Vec::new();
+        self.search_radius(query, radius * radius, 0, self.len(), &mut found);
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found
+            .into_iter()
+            .map(|(index, distance)| (index, distance.sqrt()))
+            .collect()
+    }
+
+    fn search_radius(
+        &self,
+        query: V,
+        radius_squared: f32,
+        lo: usize,
+        hi: usize,
+        found: &mut Vec<(usize, f32)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.order[mid];
+        let point = self.points[index];
+        let distance = distance_squared(query, point);
+        if distance <= radius_squared {
+            found.push((index, distance));
+        }
+        let axis = self.axes[mid];
+        let offset = query[axis] - point[axis];
+        if offset <= 0. || offset * offset <= radius_squared {
+            self.search_radius(query, radius_squared, lo, mid, found);
+        }
+        if offset >= 0. || offset * offset <= radius_squared {
+            self.search_radius(query, radius_squared, mid + 1, hi, found);
+        }
+    }
+}
+
+fn distance_squared<V: Vector>(a: V, b: V) -> f32 {
+    let offset = V::from(a - b);
+    offset.dot(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Vector2, Vector3};
+
+    fn cloud3(n: usize) -> Vec<Vector3> {
+        (0..n)
+            .map(|i| {
+                let t = i as f32;
+                Vector3::new((t * 12.9898).sin(), (t * 78.233).sin(), (t * 37.719).cos()) * 10.
+            })
+            .collect()
+    }
+
+    fn brute_force<V: Vector>(points: &[V], query: V) -> Vec<(usize, f32)> {
+        let mut all: Vec<(usize, f32)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| (i, distance_squared(query, *point).sqrt()))
+            .collect();
+        all.sort_by(|a, b| a.1.total_cmp(&b.1));
+        all
+    }
+
+    // Constructors
+    #[test]
+    fn test1_kdtreenew() {
+        let points = cloud3(100);
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.len(), 100);
+        assert_eq!(tree.points()[42], points[42]);
+    }
+    #[test]
+    fn test2_kdtreenew() {
+        let tree = KdTree::<Vector3>::new(&[]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest(Vector3::zero()), None);
+        assert!(tree.within_radius(Vector3::zero(), 1.).is_empty());
+    }
+    // Method nearest(query)
+    #[test]
+    fn test1_kdtreenearest() {
+        let points = cloud3(500);
+        let tree = KdTree::new(&points);
+        for query in cloud3(520).into_iter().skip(500) {
+            let (index, distance) = tree.nearest(query * 1.1).unwrap();
+            let exact = brute_force(&points, query * 1.1)[0];
+            assert_eq!(index, exact.0);
+            assert!((distance - exact.1).abs() < EPSILON);
+        }
+    }
+    #[test]
+    fn test2_kdtreenearest() {
+        let points = [
+            Vector2::new(0., 0.),
+            Vector2::new(5., 5.),
+            Vector2::new(-3., 4.),
+            Vector2::new(2., -1.),
+        ];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest(Vector2::new(-2., 3.)).unwrap().0, 2);
+        assert_eq!(tree.nearest(Vector2::new(5., 5.)), Some((1, 0.)));
+    }
+    // Method k_nearest(query, k)
+    #[test]
+    fn test1_kdtreek_nearest() {
+        let points = cloud3(300);
+        let tree = KdTree::new(&points);
+        let query = Vector3::new(1., -2., 0.5);
+        let found = tree.k_nearest(query, 12);
+        let exact = brute_force(&points, query);
+        assert_eq!(found.len(), 12);
+        for (found, exact) in found.iter().zip(exact) {
+            assert!((found.1 - exact.1).abs() < EPSILON);
+        }
+    }
+    #[test]
+    fn test2_kdtreek_nearest() {
+        let points = cloud3(5);
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.k_nearest(Vector3::zero(), 10).len(), 5);
+        assert!(tree.k_nearest(Vector3::zero(), 0).is_empty());
+    }
+    // Method within_radius(query, radius)
+    #[test]
+    fn test1_kdtreewithin_radius() {
+        let points = cloud3(400);
+        let tree = KdTree::new(&points);
+        let query = Vector3::new(-3., 4., 1.);
+        let found: Vec<usize> = tree.within_radius(query, 4.).iter().map(|p| p.0).collect();
+        let exact: Vec<usize> = brute_force(&points, query)
+            .into_iter()
+            .filter(|p| p.1 <= 4.)
+            .map(|p| p.0)
+            .collect();
+        assert!(!exact.is_empty());
+        assert_eq!(found, exact);
+    }
+}
@@ -0,0 +1,287 @@
+use super::Ranked;
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Debug, ops::Add};
+
+// Axis aligned bounds a LooseTree can be built over, one implementation per dimension
+pub trait LooseBounds: Copy + Debug {
+    type Point: Copy + Debug + Add<Output = Self::Point>;
+
+    // Children of a split node, one per orthant around its center
+    const CHILDREN: usize;
+
+    // Square or cubic cell around the center
+    fn cell(center: Self::Point, half_size: f32) -> Self;
+    fn center(self) -> Self::Point;
+    // Largest half size over the axes
+    fn extent(self) -> f32;
+    fn contains(self, point: Self::Point) -> bool;
+    fn intersects(self, other: Self) -> bool;
+    fn intersects_ball(self, center: Self::Point, radius: f32) -> bool;
+    fn distance_squared_to(self, point: Self::Point) -> f32;
+    // Orthant of the point around the center, bit i is set on the positive side of axis i
+    fn orthant(center: Self::Point, point: Self::Point) -> usize;
+    // Offset from a node center to the center of its child in that orthant
+    fn orthant_offset(orthant: usize, quarter: f32) -> Self::Point;
+}
+
+#[derive(Debug, Clone)]
+struct LooseNode<P> {
+    center: P,
+    half_size: f32,
+    children: Option<usize>,
+    items: Vec<usize>,
+}
+impl<P> LooseNode<P> {
+    fn new(center: P, half_size: f32) -> Self {
+        Self {
+            center,
+            half_size,
+            children: None,
+            items: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry<B, T> {
+    value: T,
+    bounds: B,
+    node: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Candidate {
+    Node(usize),
+    Item(usize),
+}
+
+// Loose quadtree or octree storing payloads with bounds. Every payload lives in exactly one
+// node, chosen from the size and center of its bounds, so insert/remove/update never split or
+// merge nodes. Payloads outside the world bounds are kept in the root and are still found by
+// queries.
+#[derive(Debug, Clone)]
+pub struct LooseTree<B: LooseBounds, T> {
+    nodes: Vec<LooseNode<B::Point>>,
+    items: Vec<Option<Entry<B, T>>>,
+    free_items: Vec<usize>,
+    max_depth: usize,
+}
+impl<B: LooseBounds, T> LooseTree<B, T> {
+    pub fn new(bounds: B, max_depth: usize) -> Self {
+        Self {
+            nodes: vec![LooseNode::new(bounds.center(), bounds.extent())],
+            items: Vec::new(),
+            free_items: Vec::new(),
+            max_depth,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() - self.free_items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[0].children = None;
+        self.nodes[0].items.clear();
+        self.items.clear();
+        self.free_items.clear();
+    }
+
+    pub fn get(&self, id: usize) -> Option<&T> {
+        Some(&self.items.get(id)?.as_ref()?.value)
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut T> {
+        Some(&mut self.items.get_mut(id)?.as_mut()?.value)
+    }
+
+    pub fn bounds(&self, id: usize) -> Option<B> {
+        Some(self.items.get(id)?.as_ref()?.bounds)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(id, entry)| Some((id, &entry.as_ref()?.value)))
+    }
+
+    pub fn insert(&mut self, value: T, bounds: B) -> usize {
+        let node = self.find_node(bounds);
+        let entry = Entry {
+            value,
+            bounds,
+            node,
+        };
+        let id = match self.free_items.pop() {
+            Some(id) => {
+                self.items[id] = Some(entry);
+                id
+            }
+            None => {
+                self.items.push(Some(entry));
+                self.items.len() - 1
+            }
+        };
+        self.nodes[node].items.push(id);
+        id
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<T> {
+        let entry = self.items.get_mut(id)?.take()?;
+        self.detach(id, entry.node);
+        self.free_items.push(id);
+        Some(entry.value)
+    }
+
+    pub fn update(&mut self, id: usize, bounds: B) -> bool {
+        let old_node = match self.items.get(id).and_then(Option::as_ref) {
+            Some(entry) => entry.node,
+            None => return false,
+        };
+        let new_node = self.find_node(bounds);
+        if let Some(entry) = self.items[id].as_mut() {
+            entry.bounds = bounds;
+            entry.node = new_node;
+        }
+        if new_node != old_node {
+            self.detach(id, old_node);
+            self.nodes[new_node].items.push(id);
+        }
+        true
+    }
+
+    fn detach(&mut self, id: usize, node: usize) {
+        let items = &mut self.nodes[node].items;
+        if let Some(position) = items.iter().position(|item| *item == id) {
+            items.swap_remove(position);
+        }
+    }
+
+    // Nodes are loose by a factor of two: their content may overhang the cell by half a cell
+    fn loose_bounds(&self, node: usize) -> B {
+        let node = &self.nodes[node];
+        B::cell(node.center, 2. * node.half_size)
+    }
+
+    fn find_node(&mut self, bounds: B) -> usize {
+        let center = bounds.center();
+        let extent = bounds.extent();
+        let root = &self.nodes[0];
+        if !B::cell(root.center, root.half_size).contains(center) {
+            return 0;
+        }
+        let mut node = 0;
+        for _ in 0..self.max_depth {
+            let child_half_size = self.nodes[node].half_size * 0.5;
+            if extent > child_half_size {
+                break;
+            }
+            let first_child = match self.nodes[node].children {
+                Some(first_child) => first_child,
+                None => self.split(node),
+            };
+            node = first_child + B::orthant(self.nodes[node].center, center);
+        }
+        node
+    }
+
+    fn split(&mut self, node: usize) -> usize {
+        let LooseNode {
+            center, half_size, ..
+        } = self.nodes[node];
+        let quarter = half_size * 0.5;
+        let first_child = self.nodes.len();
+        for orthant in 0..B::CHILDREN {
+            let offset = B::orthant_offset(orthant, quarter);
+            self.nodes.push(LooseNode::new(center + offset, quarter));
+        }
+        self.nodes[node].children = Some(first_child);
+        first_child
+    }
+
+    fn traverse<P, F>(&self, mut overlaps: P, mut visitor: F)
+    where
+        P: FnMut(B) -> bool,
+        F: FnMut(usize, &T),
+    {
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            for &id in node.items.iter() {
+                if let Some(entry) = self.items[id].as_ref() {
+                    if overlaps(entry.bounds) {
+                        visitor(id, &entry.value);
+                    }
+                }
+            }
+            if let Some(first_child) = node.children {
+                for child in first_child..first_child + B::CHILDREN {
+                    if overlaps(self.loose_bounds(child)) {
+                        stack.push(child);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn query_aabb<F>(&self, aabb: B, visitor: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        self.traverse(|bounds| bounds.intersects(aabb), visitor);
+    }
+
+    pub fn query_radius<F>(&self, center: B::Point, radius: f32, visitor: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        self.traverse(|bounds| bounds.intersects_ball(center, radius), visitor);
+    }
+
+    // Best-first search, distances are measured from the point to the payload bounds
+    pub fn k_nearest(&self, point: B::Point, k: usize) -> Vec<(usize, f32)> {
+        let mut result = Vec::with_capacity(k);
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse(Ranked {
+            distance: 0.,
+            value: Candidate::Node(0),
+        }));
+        while let Some(Reverse(Ranked { distance, value })) = heap.pop() {
+            if result.len() == k {
+                break;
+            }
+            match value {
+                Candidate::Item(id) => result.push((id, distance.sqrt())),
+                Candidate::Node(node) => {
+                    let node = &self.nodes[node];
+                    for &id in node.items.iter() {
+                        if let Some(entry) = self.items[id].as_ref() {
+                            heap.push(Reverse(Ranked {
+                                distance: entry.bounds.distance_squared_to(point),
+                                value: Candidate::Item(id),
+                            }));
+                        }
+                    }
+                    if let Some(first_child) = node.children {
+                        for child in first_child..first_child + B::CHILDREN {
+                            heap.push(Reverse(Ranked {
+                                distance: self.loose_bounds(child).distance_squared_to(point),
+                                value: Candidate::Node(child),
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    pub fn nearest(&self, point: B::Point) -> Option<(usize, f32)> {
+        self.k_nearest(point, 1).pop()
+    }
+}
@@ -0,0 +1,31 @@
+pub mod bvh;
+pub mod grid;
+pub mod kdtree;
+pub mod loose;
+pub mod octree;
+pub mod quadtree;
+
+use std::cmp::Ordering;
+
+// Orders values by an f32 key so they can be kept in a BinaryHeap
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Ranked<T> {
+    pub distance: f32,
+    pub value: T,
+}
+impl<T> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<T> Eq for Ranked<T> {}
+impl<T> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
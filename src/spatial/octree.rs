@@ -0,0 +1,198 @@
+use super::loose::{LooseBounds, LooseTree};
+use crate::{geometry::aabb::Aabb3, Vector3};
+
+impl LooseBounds for Aabb3 {
+    type Point = Vector3;
+
+    const CHILDREN: usize = 8;
+
+    fn cell(center: Vector3, half_size: f32) -> Self {
+        Aabb3::from_center_half_size(center, Vector3::from(half_size))
+    }
+
+    fn center(self) -> Vector3 {
+        Aabb3::center(self)
+    }
+
+    fn extent(self) -> f32 {
+        let Vector3 { x, y, z } = self.half_size();
+        x.max(y).max(z)
+    }
+
+    fn contains(self, point: Vector3) -> bool {
+        Aabb3::contains(self, point)
+    }
+
+    fn intersects(self, other: Self) -> bool {
+        Aabb3::intersects(self, other)
+    }
+
+    fn intersects_ball(self, center: Vector3, radius: f32) -> bool {
+        self.intersects_sphere(center, radius)
+    }
+
+    fn distance_squared_to(self, point: Vector3) -> f32 {
+        Aabb3::distance_squared_to(self, point)
+    }
+
+    fn orthant(center: Vector3, point: Vector3) -> usize {
+        (point.x >= center.x) as usize
+            | ((point.y >= center.y) as usize) << 1
+            | ((point.z >= center.z) as usize) << 2
+    }
+
+    fn orthant_offset(orthant: usize, quarter: f32) -> Vector3 {
+        let sign = |bit: usize| {
+            if orthant & bit != 0 {
+                quarter
+            } else {
+                -quarter
+            }
+        };
+        Vector3::new(sign(1), sign(2), sign(4))
+    }
+}
+
+// Loose octree over Aabb3 bounds, see LooseTree
+pub type Octree<T> = LooseTree<Aabb3, T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Vector};
+
+    fn point_bounds(point: Vector3, radius: f32) -> Aabb3 {
+        Aabb3::from_center_half_size(point, Vector3::from(radius))
+    }
+
+    fn sample_octree() -> (Octree<usize>, Vec<Aabb3>) {
+        let world = Aabb3::new(Vector3::from(-50.), Vector3::from(50.));
+        let mut octree = Octree::new(world, 6);
+        let bounds: Vec<Aabb3> = (0..200)
+            .map(|i| {
+                let t = i as f32;
+                let point = Vector3::new(
+                    (t * 7.31).sin() * 40.,
+                    (t * 3.17).cos() * 40.,
+                    (t * 1.93).sin() * 40.,
+                );
+                point_bounds(point, 0.1 + (i % 5) as f32)
+            })
+            .collect();
+        for (i, aabb) in bounds.iter().enumerate() {
+            octree.insert(i, *aabb);
+        }
+        (octree, bounds)
+    }
+
+    fn query_aabb_ids<T>(octree: &Octree<T>, aabb: Aabb3) -> Vec<usize> {
+        let mut found = Vec::new();
+        octree.query_aabb(aabb, |id, _| found.push(id));
+        found.sort();
+        found
+    }
+
+    fn query_radius_ids<T>(octree: &Octree<T>, center: Vector3, radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        octree.query_radius(center, radius, |id, _| found.push(id));
+        found.sort();
+        found
+    }
+
+    // Methods insert(value, bounds), get(id)
+    #[test]
+    fn test1_octreeinsert() {
+        let (octree, bounds) = sample_octree();
+        assert_eq!(octree.len(), bounds.len());
+        for (i, aabb) in bounds.into_iter().enumerate() {
+            assert_eq!(octree.get(i), Some(&i));
+            assert_eq!(octree.bounds(i), Some(aabb));
+        }
+    }
+    #[test]
+    fn test2_octreeinsert() {
+        let mut octree = Octree::new(Aabb3::new(Vector3::zero(), Vector3::from(1.)), 4);
+        let id = octree.insert("far", point_bounds(Vector3::from(100.), 1.));
+        assert_eq!(
+            query_radius_ids(&octree, Vector3::from(100.), 0.5),
+            vec![id]
+        );
+    }
+    // Method query_aabb(aabb, visitor)
+    #[test]
+    fn test1_octreequery_aabb() {
+        let (octree, bounds) = sample_octree();
+        let query = Aabb3::new(Vector3::new(-10., -20., 0.), Vector3::new(15., 5., 30.));
+        let exact: Vec<usize> = (0..bounds.len())
+            .filter(|i| bounds[*i].intersects(query))
+            .collect();
+        assert!(!exact.is_empty());
+        assert_eq!(query_aabb_ids(&octree, query), exact);
+    }
+    // Method query_radius(center, radius, visitor)
+    #[test]
+    fn test1_octreequery_radius() {
+        let (octree, bounds) = sample_octree();
+        let center = Vector3::new(5., -3., 12.);
+        let exact: Vec<usize> = (0..bounds.len())
+            .filter(|i| bounds[*i].intersects_sphere(center, 18.))
+            .collect();
+        assert!(!exact.is_empty());
+        assert_eq!(query_radius_ids(&octree, center, 18.), exact);
+    }
+    // Methods k_nearest(point, k), nearest(point)
+    #[test]
+    fn test1_octreek_nearest() {
+        let (octree, bounds) = sample_octree();
+        let point = Vector3::new(-7., 11., 2.);
+        let found = octree.k_nearest(point, 10);
+        let mut exact: Vec<f32> = bounds
+            .iter()
+            .map(|aabb| aabb.distance_squared_to(point).sqrt())
+            .collect();
+        exact.sort_by(f32::total_cmp);
+        assert_eq!(found.len(), 10);
+        for ((id, distance), exact_distance) in found.iter().zip(exact) {
+            assert!((distance - exact_distance).abs() < EPSILON);
+            assert!((bounds[*id].distance_squared_to(point).sqrt() - distance).abs() < EPSILON);
+        }
+    }
+    #[test]
+    fn test2_octreek_nearest() {
+        let (octree, _) = sample_octree();
+        assert_eq!(octree.k_nearest(Vector3::zero(), 500).len(), 200);
+        let empty = Octree::<()>::new(Aabb3::new(Vector3::zero(), Vector3::from(1.)), 3);
+        assert_eq!(empty.nearest(Vector3::zero()), None);
+    }
+    // Method remove(id)
+    #[test]
+    fn test1_octreeremove() {
+        let (mut octree, bounds) = sample_octree();
+        for i in (0..bounds.len()).step_by(2) {
+            assert_eq!(octree.remove(i), Some(i));
+        }
+        assert_eq!(octree.remove(0), None);
+        assert_eq!(octree.len(), bounds.len() / 2);
+        let query = Aabb3::new(Vector3::from(-50.), Vector3::from(50.));
+        let exact: Vec<usize> = (1..bounds.len()).step_by(2).collect();
+        assert_eq!(query_aabb_ids(&octree, query), exact);
+    }
+    // Method update(id, bounds)
+    #[test]
+    fn test1_octreeupdate() {
+        let (mut octree, _) = sample_octree();
+        let moved = point_bounds(Vector3::new(45., 45., 45.), 0.5);
+        assert!(octree.update(7, moved));
+        assert_eq!(octree.bounds(7), Some(moved));
+        assert_eq!(octree.nearest(Vector3::new(46., 46., 46.)).unwrap().0, 7);
+        assert!(!octree.update(1000, moved));
+    }
+    // Method clear()
+    #[test]
+    fn test1_octreeclear() {
+        let (mut octree, _) = sample_octree();
+        octree.clear();
+        assert!(octree.is_empty());
+        assert!(query_radius_ids(&octree, Vector3::zero(), 100.).is_empty());
+    }
+}
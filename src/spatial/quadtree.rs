@@ -0,0 +1,192 @@
+use super::loose::{LooseBounds, LooseTree};
+use crate::{geometry::rect::Rect, Vector2};
+
+impl LooseBounds for Rect {
+    type Point = Vector2;
+
+    const CHILDREN: usize = 4;
+
+    fn cell(center: Vector2, half_size: f32) -> Self {
+        Rect::from_center_half_size(center, Vector2::from(half_size))
+    }
+
+    fn center(self) -> Vector2 {
+        Rect::center(self)
+    }
+
+    fn extent(self) -> f32 {
+        let Vector2 { x, y } = self.half_size();
+        x.max(y)
+    }
+
+    fn contains(self, point: Vector2) -> bool {
+        Rect::contains(self, point)
+    }
+
+    fn intersects(self, other: Self) -> bool {
+        Rect::intersects(self, other)
+    }
+
+    fn intersects_ball(self, center: Vector2, radius: f32) -> bool {
+        self.intersects_circle(center, radius)
+    }
+
+    fn distance_squared_to(self, point: Vector2) -> f32 {
+        Rect::distance_squared_to(self, point)
+    }
+
+    fn orthant(center: Vector2, point: Vector2) -> usize {
+        (point.x >= center.x) as usize | ((point.y >= center.y) as usize) << 1
+    }
+
+    fn orthant_offset(orthant: usize, quarter: f32) -> Vector2 {
+        let sign = |bit: usize| {
+            if orthant & bit != 0 {
+                quarter
+            } else {
+                -quarter
+            }
+        };
+        Vector2::new(sign(1), sign(2))
+    }
+}
+
+// Loose quadtree over Rect bounds, see LooseTree
+pub type Quadtree<T> = LooseTree<Rect, T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, Vector};
+
+    fn point_bounds(point: Vector2, radius: f32) -> Rect {
+        Rect::from_center_half_size(point, Vector2::from(radius))
+    }
+
+    fn sample_quadtree() -> (Quadtree<usize>, Vec<Rect>) {
+        let world = Rect::new(Vector2::from(-50.), Vector2::from(50.));
+        let mut quadtree = Quadtree::new(world, 6);
+        let bounds: Vec<Rect> = (0..200)
+            .map(|i| {
+                let t = i as f32;
+                let point = Vector2::new((t * 7.31).sin() * 40., (t * 3.17).cos() * 40.);
+                point_bounds(point, 0.1 + (i % 5) as f32)
+            })
+            .collect();
+        for (i, aabb) in bounds.iter().enumerate() {
+            quadtree.insert(i, *aabb);
+        }
+        (quadtree, bounds)
+    }
+
+    fn query_aabb_ids<T>(quadtree: &Quadtree<T>, aabb: Rect) -> Vec<usize> {
+        let mut found = Vec::new();
+        quadtree.query_aabb(aabb, |id, _| found.push(id));
+        found.sort();
+        found
+    }
+
+    fn query_radius_ids<T>(quadtree: &Quadtree<T>, center: Vector2, radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        quadtree.query_radius(center, radius, |id, _| found.push(id));
+        found.sort();
+        found
+    }
+
+    // Methods insert(value, bounds), get(id)
+    #[test]
+    fn test1_quadtreeinsert() {
+        let (quadtree, bounds) = sample_quadtree();
+        assert_eq!(quadtree.len(), bounds.len());
+        for (i, aabb) in bounds.into_iter().enumerate() {
+            assert_eq!(quadtree.get(i), Some(&i));
+            assert_eq!(quadtree.bounds(i), Some(aabb));
+        }
+    }
+    #[test]
+    fn test2_quadtreeinsert() {
+        let mut quadtree = Quadtree::new(Rect::new(Vector2::zero(), Vector2::from(1.)), 4);
+        let id = quadtree.insert("far", point_bounds(Vector2::from(100.), 1.));
+        assert_eq!(
+            query_radius_ids(&quadtree, Vector2::from(100.), 0.5),
+            vec![id]
+        );
+    }
+    // Method query_aabb(aabb, visitor)
+    #[test]
+    fn test1_quadtreequery_aabb() {
+        let (quadtree, bounds) = sample_quadtree();
+        let query = Rect::new(Vector2::new(-10., -20.), Vector2::new(15., 5.));
+        let exact: Vec<usize> = (0..bounds.len())
+            .filter(|i| bounds[*i].intersects(query))
+            .collect();
+        assert!(!exact.is_empty());
+        assert_eq!(query_aabb_ids(&quadtree, query), exact);
+    }
+    // Method query_radius(center, radius, visitor)
+    #[test]
+    fn test1_quadtreequery_radius() {
+        let (quadtree, bounds) = sample_quadtree();
+        let center = Vector2::new(5., -3.);
+        let exact: Vec<usize> = (0..bounds.len())
+            .filter(|i| bounds[*i].intersects_circle(center, 18.))
+            .collect();
+        assert!(!exact.is_empty());
+        assert_eq!(query_radius_ids(&quadtree, center, 18.), exact);
+    }
+    // Methods k_nearest(point, k), nearest(point)
+    #[test]
+    fn test1_quadtreek_nearest() {
+        let (quadtree, bounds) = sample_quadtree();
+        let point = Vector2::new(-7., 11.);
+        let found = quadtree.k_nearest(point, 10);
+        let mut exact: Vec<f32> = bounds
+            .iter()
+            .map(|aabb| aabb.distance_squared_to(point).sqrt())
+            .collect();
+        exact.sort_by(f32::total_cmp);
+        assert_eq!(found.len(), 10);
+        for ((id, distance), exact_distance) in found.iter().zip(exact) {
+            assert!((distance - exact_distance).abs() < EPSILON);
+            assert!((bounds[*id].distance_squared_to(point).sqrt() - distance).abs() < EPSILON);
+        }
+    }
+    #[test]
+    fn test2_quadtreek_nearest() {
+        let (quadtree, _) = sample_quadtree();
+        assert_eq!(quadtree.k_nearest(Vector2::zero(), 500).len(), 200);
+        let empty = Quadtree::<()>::new(Rect::new(Vector2::zero(), Vector2::from(1.)), 3);
+        assert_eq!(empty.nearest(Vector2::zero()), None);
+    }
+    // Method remove(id)
+    #[test]
+    fn test1_quadtreeremove() {
+        let (mut quadtree, bounds) = sample_quadtree();
+        for i in (0..bounds.len()).step_by(2) {
+            assert_eq!(quadtree.remove(i), Some(i));
+        }
+        assert_eq!(quadtree.remove(0), None);
+        assert_eq!(quadtree.len(), bounds.len() / 2);
+        let query = Rect::new(Vector2::from(-50.), Vector2::from(50.));
+        let exact: Vec<usize> = (1..bounds.len()).step_by(2).collect();
+        assert_eq!(query_aabb_ids(&quadtree, query), exact);
+    }
+    // Method update(id, bounds)
+    #[test]
+    fn test1_quadtreeupdate() {
+        let (mut quadtree, _) = sample_quadtree();
+        let moved = point_bounds(Vector2::new(45., 45.), 0.5);
+        assert!(quadtree.update(7, moved));
+        assert_eq!(quadtree.bounds(7), Some(moved));
+        assert_eq!(quadtree.nearest(Vector2::new(46., 46.)).unwrap().0, 7);
+        assert!(!quadtree.update(1000, moved));
+    }
+    // Method clear()
+    #[test]
+    fn test1_quadtreeclear() {
+        let (mut quadtree, _) = sample_quadtree();
+        quadtree.clear();
+        assert!(quadtree.is_empty());
+        assert!(query_radius_ids(&quadtree, Vector2::zero(), 100.).is_empty());
+    }
+}
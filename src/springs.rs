@@ -0,0 +1,335 @@
+use crate::{functions::constants::PI, Interpolate, Matrix, Matrix3, Vector, Vector3};
+
+const MIN_SMOOTH_TIME: f32 = 1e-4;
+const MIN_FREQUENCY: f32 = 1e-4;
+const MAX_FREQUENCY: f32 = 1e4;
+
+// Critically damped spring towards target reaching it in about smooth_time seconds, velocity
+// is the spring state and must be kept between calls. Stable for any dt
+// (Game Programming Gems 4, 1.10)
+pub fn smooth_damp<T: Interpolate>(
+    current: T,
+    target: T,
+    velocity: &mut T,
+    smooth_time: f32,
+    dt: f32,
+) -> T {
+    let omega = 2. / smooth_time.max(MIN_SMOOTH_TIME);
+    let x = omega * dt;
+    // Pade approximation of exp(-x)
+    let decay = 1. / (1. + x + 0.48 * x * x + 0.235 * x * x * x);
+    let change = current - target;
+    let temp = (*velocity + change * omega) * dt;
+    *velocity = (*velocity - temp * omega) * decay;
+    target + (change + temp) * decay
+}
+
+// smooth_damp for rotation matrices, angular_velocity is a world space rotation vector per second
+pub fn smooth_damp_rotation(
+    current: Matrix3,
+    target: Matrix3,
+    angular_velocity: &mut Vector3,
+    smooth_time: f32,
+    dt: f32,
+) -> Matrix3 {
+    let offset = (current * target.transpose()).rotation_vector();
+    let offset = smooth_damp(offset, Vector3::zero(), angular_velocity, smooth_time, dt);
+    (Matrix3::from_rotation_vector(offset) * target).orthonormalize()
+}
+
+// Coefficients of y + k1 y' + k2 y'' = x + k3 x'
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Coefficients {
+    k1: f32,
+    k2: f32,
+    k3: f32,
+}
+impl Coefficients {
+    // Natural frequency in Hz, damping ratio (1 critical, below 1 oscillates) and response
+    // (0 eases in, 1 reacts immediately, above 1 overshoots, below 0 anticipates)
+    fn new(frequency: f32, damping: f32, response: f32) -> Self {
+        // Zero, negative and NaN frequencies would make every update NaN
+        let frequency = if frequency.is_nan() {
+            MIN_FREQUENCY
+        } else {
+            frequency.clamp(MIN_FREQUENCY, MAX_FREQUENCY)
+        };
+        let angular = 2. * PI * frequency;
+        Self {
+            k1: damping / (PI * frequency),
+            k2: 1. / (angular * angular),
+            k3: response * damping / angular,
+        }
+    }
+
+    // Velocity after an implicit Euler step of dt, where forcing is x + k3 x' - y at the start
+    // of the step. Solving v1 = v0 + dt (forcing - dt v1 - k1 v1) / k2 in closed form keeps the
+    // step stable for any and varying dt
+    fn velocity_step<T: Interpolate>(&self, dt: f32, velocity: T, forcing: T) -> T {
+        (velocity * self.k2 + forcing * dt) * (1. / (self.k2 + dt * self.k1 + dt * dt))
+    }
+}
+
+// Second order system following a moving input, parameterized by frequency, damping ratio and
+// initial response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecondOrderDynamics<T> {
+    coefficients: Coefficients,
+    previous_input: T,
+    value: T,
+    velocity: T,
+}
+impl<T: Interpolate> SecondOrderDynamics<T> {
+    pub fn new(frequency: f32, damping: f32, response: f32, initial: T) -> Self {
+        Self {
+            coefficients: Coefficients::new(frequency, damping, response),
+            previous_input: initial,
+            value: initial,
+            velocity: initial * 0.,
+        }
+    }
+
+    pub fn set_parameters(&mut self, frequency: f32, damping: f32, response: f32) {
+        self.coefficients = Coefficients::new(frequency, damping, response);
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn velocity(&self) -> T {
+        self.velocity
+    }
+
+    // Jumps to value at rest
+    pub fn reset(&mut self, value: T) {
+        self.previous_input = value;
+        self.value = value;
+        self.velocity = value * 0.;
+    }
+
+    // Input velocity is estimated from the previous input
+    pub fn update(&mut self, dt: f32, input: T) -> T {
+        if dt <= 0. {
+            return self.value;
+        }
+        let input_velocity = (input - self.previous_input) * (1. / dt);
+        self.update_with_velocity(dt, input, input_velocity)
+    }
+
+    pub fn update_with_velocity(&mut self, dt: f32, input: T, input_velocity: T) -> T {
+        if dt <= 0. {
+            return self.value;
+        }
+        self.previous_input = input;
+        let forcing = input + input_velocity * self.coefficients.k3 - self.value;
+        self.velocity = self.coefficients.velocity_step(dt, self.velocity, forcing);
+        self.value = self.value + self.velocity * dt;
+        self.value
+    }
+}
+
+// SecondOrderDynamics for rotation matrices, errors and velocities are world space rotation
+// vectors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotationDynamics {
+    coefficients: Coefficients,
+    previous_input: Matrix3,
+    rotation: Matrix3,
+    angular_velocity: Vector3,
+}
+impl RotationDynamics {
+    pub fn new(frequency: f32, damping: f32, response: f32, initial: Matrix3) -> Self {
+        Self {
+            coefficients: Coefficients::new(frequency, damping, response),
+            previous_input: initial,
+            rotation: initial,
+            angular_velocity: Vector3::zero(),
+        }
+    }
+
+    pub fn set_parameters(&mut self, frequency: f32, damping: f32, response: f32) {
+        self.coefficients = Coefficients::new(frequency, damping, response);
+    }
+
+    pub fn rotation(&self) -> Matrix3 {
+        self.rotation
+    }
+
+    pub fn angular_velocity(&self) -> Vector3 {
+        self.angular_velocity
+    }
+
+    pub fn reset(&mut self, rotation: Matrix3) {
+        self.previous_input = rotation;
+        self.rotation = rotation;
+        self.angular_velocity = Vector3::zero();
+    }
+
+    pub fn update(&mut self, dt: f32, input: Matrix3) -> Matrix3 {
+        if dt <= 0. {
+            return self.rotation;
+        }
+        let input_velocity = (input * self.previous_input.transpose()).rotation_vector() / dt;
+        self.update_with_velocity(dt, input, input_velocity)
+    }
+
+    pub fn update_with_velocity(
+        &mut self,
+        dt: f32,
+        input: Matrix3,
+        input_angular_velocity: Vector3,
+    ) -> Matrix3 {
+        if dt <= 0. {
+            return self.rotation;
+        }
+        self.previous_input = input;
+        let error = (input * self.rotation.transpose()).rotation_vector();
+        let forcing = error + input_angular_velocity * self.coefficients.k3;
+        self.angular_velocity = self
+            .coefficients
+            .velocity_step(dt, self.angular_velocity, forcing);
+        self.rotation = (Matrix3::from_rotation_vector(self.angular_velocity * dt) * self.rotation)
+            .orthonormalize();
+        self.rotation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{functions::constants::EPSILON, transform::rotate3, Vector2};
+
+    fn run_smooth_damp(dt: f32, duration: f32) -> f32 {
+        let (mut value, mut velocity) = (0., 0.);
+        for _ in 0..(duration / dt).round() as usize {
+            value = smooth_damp(value, 10., &mut velocity, 0.3, dt);
+        }
+        value
+    }
+
+    // Function smooth_damp()
+    #[test]
+    fn test1_smooth_damp() {
+        let (mut value, mut velocity) = (0., 0.);
+        let mut previous = value;
+        for _ in 0..120 {
+            value = smooth_damp(value, 10., &mut velocity, 0.3, 1. / 60.);
+            // Critically damped, it never overshoots from rest
+            assert!(value >= previous && value <= 10.);
+            previous = value;
+        }
+        assert!((value - 10.).abs() < 0.01);
+        // Frame rate barely changes the motion
+        assert!((run_smooth_damp(1. / 30., 0.5) - run_smooth_damp(1. / 144., 0.5)).abs() < 0.05);
+        // A huge step does not explode
+        let mut velocity = 0.;
+        assert!(smooth_damp(0., 10., &mut velocity, 0.1, 100.) <= 10.);
+    }
+    #[test]
+    fn test2_smooth_damp() {
+        let (mut value, mut velocity) = (Vector2::new(0., 0.), Vector2::zero());
+        let target = Vector2::new(3., -4.);
+        for _ in 0..200 {
+            value = smooth_damp(value, target, &mut velocity, 0.2, 1. / 60.);
+            // Moves along the straight line towards the target
+            assert!((value.x * 4. + value.y * 3.).abs() < EPSILON);
+        }
+        assert_eq!(value, target);
+        assert_eq!(velocity, Vector2::zero());
+    }
+    // Function smooth_damp_rotation()
+    #[test]
+    fn test1_smooth_damp_rotation() {
+        let target = rotate3(0.3, -1.2, 2.);
+        let mut rotation = Matrix3::idenity();
+        let mut angular_velocity = Vector3::zero();
+        for _ in 0..300 {
+            rotation =
+                smooth_damp_rotation(rotation, target, &mut angular_velocity, 0.25, 1. / 60.);
+            assert!(rotation.is_rotation());
+        }
+        assert_eq!(rotation, target);
+    }
+    // SecondOrderDynamics
+    #[test]
+    fn test1_secondorderdynamicsupdate() {
+        let mut critical = SecondOrderDynamics::new(2., 1., 0., 0.);
+        let mut bouncy = SecondOrderDynamics::new(2., 0.2, 0., 0.);
+        let (mut critical_max, mut bouncy_max) = (0_f32, 0_f32);
+        for _ in 0..600 {
+            critical_max = critical_max.max(critical.update(1. / 120., 1.));
+            bouncy_max = bouncy_max.max(bouncy.update(1. / 120., 1.));
+        }
+        assert!(critical_max <= 1. + EPSILON);
+        assert!(bouncy_max > 1.3);
+        assert!((critical.value() - 1.).abs() < EPSILON);
+        assert!((bouncy.value() - 1.).abs() < 0.01);
+    }
+    #[test]
+    fn test2_secondorderdynamicsupdate() {
+        // A higher response reacts faster to a jump of the input
+        let mut eager = SecondOrderDynamics::new(1., 1., 1., Vector3::zero());
+        let mut lazy = SecondOrderDynamics::new(1., 1., 0., Vector3::zero());
+        let target = Vector3::new(1., 0., 0.);
+        let (eager, lazy) = (eager.update(0.01, target), lazy.update(0.01, target));
+        assert!(eager.x > 10. * lazy.x && lazy.x > 0.);
+        assert_eq!((eager.y, eager.z), (0., 0.));
+        // Large and varying dt never diverges
+        let mut dynamics = SecondOrderDynamics::new(10., 0.5, 0., 0.);
+        for _ in 0..100 {
+            for dt in [0.5, 1e-3, 2., 0.1, 0., 1.] {
+                assert!(dynamics.update(dt, 1.).abs() < 100.);
+            }
+        }
+        for _ in 0..120 {
+            dynamics.update(1. / 60., 1.);
+        }
+        assert!((dynamics.value() - 1.).abs() < EPSILON);
+        dynamics.reset(3.);
+        assert_eq!((dynamics.value(), dynamics.velocity()), (3., 0.));
+    }
+    #[test]
+    fn test3_secondorderdynamicsupdate() {
+        // k3 == k1 when response is 2, then a moving target is tracked without lag
+        let mut dynamics = SecondOrderDynamics::new(3., 1., 2., 0.);
+        let dt = 1. / 240.;
+        for i in 1..=1200 {
+            dynamics.update_with_velocity(dt, 2. * i as f32 * dt, 2.);
+        }
+        assert!((dynamics.value() - 10.).abs() < 0.01);
+        assert!((dynamics.velocity() - 2.).abs() < 0.01);
+    }
+    #[test]
+    fn test4_secondorderdynamicsupdate() {
+        // Degenerate frequencies are clamped instead of poisoning the state with NaN
+        for frequency in [0., -2., f32::NAN, f32::INFINITY] {
+            let mut dynamics = SecondOrderDynamics::new(frequency, 1., 0., 0.);
+            let value = dynamics.update(1. / 60., 1.);
+            assert!(value.is_finite());
+            dynamics.set_parameters(frequency, 1., 1.);
+            assert!(dynamics.update(1. / 60., 1.).is_finite());
+        }
+    }
+    // RotationDynamics
+    #[test]
+    fn test1_rotationdynamicsupdate() {
+        let target = rotate3(-0.5, 0.8, 1.5);
+        let mut dynamics = RotationDynamics::new(1.5, 1., 0., Matrix3::idenity());
+        for _ in 0..600 {
+            assert!(dynamics.update(1. / 120., target).is_rotation());
+        }
+        assert_eq!(dynamics.rotation(), target);
+        assert!(dynamics.angular_velocity().len() < EPSILON);
+        dynamics.reset(Matrix3::idenity());
+        assert_eq!(dynamics.rotation(), Matrix3::idenity());
+    }
+    #[test]
+    fn test2_rotationdynamicsupdate() {
+        let target = rotate3(0.1, 0.2, 0.3);
+        for frequency in [0., f32::NAN] {
+            let mut dynamics = RotationDynamics::new(frequency, 1., 0., Matrix3::idenity());
+            assert!(dynamics.update(1. / 60., target).is_rotation());
+        }
+    }
+}
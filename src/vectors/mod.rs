@@ -1,6 +1,7 @@
 pub mod vec2;
 pub mod vec3;
 pub mod vec4;
+pub mod vecn;
 
 use crate::functions::constants::EPSILON;
 use std::ops::Sub;
@@ -1,10 +1,8 @@
-use super::{
-    super::functions::constants::{EPSILON, PRECISION},
-    Vector,
-};
+use super::{vecn::VectorN, Vector};
+use crate::Matrix2;
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -16,6 +14,19 @@ impl Vector2 {
     pub fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
+
+    pub fn min(self, rhs: Self) -> Self {
+        VectorN::from(self).min(rhs.into()).into()
+    }
+
+    pub fn max(self, rhs: Self) -> Self {
+        VectorN::from(self).max(rhs.into()).into()
+    }
+
+    // Outer product self * rhs^T
+    pub fn outer(self, rhs: Self) -> Matrix2 {
+        Matrix2::from_cols(self * rhs.x, self * rhs.y)
+    }
 }
 impl Vector for Vector2 {
     fn dim(self) -> usize {
@@ -23,11 +34,11 @@ impl Vector for Vector2 {
     }
 
     fn len(self) -> f32 {
-        (self.x * self.x + self.y * self.y).sqrt()
+        VectorN::from(self).len()
     }
 
     fn dot(self, vector: Self) -> f32 {
-        self.x * vector.x + self.y * vector.y
+        VectorN::from(self).dot(vector.into())
     }
 }
 impl From<f32> for Vector2 {
@@ -35,19 +46,40 @@ impl From<f32> for Vector2 {
         Self::new(value, value)
     }
 }
+impl From<[f32; 2]> for Vector2 {
+    fn from(value: [f32; 2]) -> Self {
+        let [x, y] = value;
+        Self::new(x, y)
+    }
+}
+impl From<Vector2> for [f32; 2] {
+    fn from(value: Vector2) -> Self {
+        [value.x, value.y]
+    }
+}
+impl Index<usize> for Vector2 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index out of range for Vector2"),
+        }
+    }
+}
+impl IndexMut<usize> for Vector2 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of range for Vector2"),
+        }
+    }
+}
 impl Display for Vector2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_len = [self.x, self.y]
-            .iter()
-            .map(|comp| (*comp as i32).to_string().len())
-            .max()
-            .unwrap();
-        let space = " ".repeat(1 + max_len + 1 + PRECISION + 1);
-        writeln!(f, "\n┌{space}┐").unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.x).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.y).unwrap();
-        writeln!(f, "└{space}┘").unwrap();
-        Ok(())
+        VectorN::from(*self).fmt(f)
     }
 }
 impl Neg for Vector2 {
@@ -61,7 +93,7 @@ impl Add for Vector2 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new(self.x + rhs.x, self.y + rhs.y)
+        (VectorN::from(self) + VectorN::from(rhs)).into()
     }
 }
 impl AddAssign for Vector2 {
@@ -85,7 +117,7 @@ impl Mul<f32> for Vector2 {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::new(self.x * rhs, self.y * rhs)
+        (VectorN::from(self) * rhs).into()
     }
 }
 impl Mul<Vector2> for f32 {
@@ -114,7 +146,7 @@ impl DivAssign<f32> for Vector2 {
 }
 impl PartialEq for Vector2 {
     fn eq(&self, other: &Self) -> bool {
-        (*self - *other).len() < EPSILON
+        VectorN::from(*self) == VectorN::from(*other)
     }
 }
 // impl Iterator for Vector2 {
@@ -131,7 +163,7 @@ impl PartialEq for Vector2 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::functions::constants::PI;
+    use crate::functions::constants::{EPSILON, PI};
 
     // Constructors
     #[test]
@@ -147,6 +179,12 @@ mod tests {
         assert_eq!(vec, vec_from);
     }
     #[test]
+    fn test1_vector2from_array() {
+        let vec = Vector2::from([1., -2.]);
+        assert_eq!(vec, Vector2::new(1., -2.));
+        assert_eq!(<[f32; 2]>::from(vec), [1., -2.]);
+    }
+    #[test]
     fn test1_vector2zero() {
         let vec = Vector2 { x: 0., y: 0. };
         let vec_from = Vector2::zero();
@@ -332,6 +370,48 @@ mod tests {
         assert_ne!(vec1.is_orthogonal_to(vec2), true);
         assert_ne!(vec2.is_orthogonal_to(vec1), true);
     }
+    // Method outer(vector)
+    #[test]
+    fn test1_vector2outer() {
+        let vec1 = Vector2::new(1., 2.);
+        let vec2 = Vector2::new(3., -1.);
+        assert_eq!(vec1.outer(vec2), Matrix2::new([[3., -1.], [6., -2.]]));
+        assert_eq!(vec1.outer(vec2) * vec2, vec1 * vec2.dot(vec2));
+    }
+    // Methods min(vector), max(vector)
+    #[test]
+    fn test1_vector2min() {
+        let vec1 = Vector2::new(1., -4.);
+        let vec2 = Vector2::new(3., -5.);
+        assert_eq!(vec1.min(vec2), vec2.min(vec1));
+        assert_eq!(vec1.min(vec2), Vector2::new(1., -5.));
+    }
+    #[test]
+    fn test1_vector2max() {
+        let vec1 = Vector2::new(1., -4.);
+        let vec2 = Vector2::new(3., -5.);
+        assert_eq!(vec1.max(vec2), vec2.max(vec1));
+        assert_eq!(vec1.max(vec2), Vector2::new(3., -4.));
+    }
+    // Impl Index<usize>
+    #[test]
+    fn test1_vector2index_usize() {
+        let vec = Vector2::new(1., -4.);
+        assert_eq!(vec[0], vec.x);
+        assert_eq!(vec[1], vec.y);
+    }
+    #[test]
+    #[should_panic]
+    fn test2_vector2index_usize() {
+        let _ = Vector2::zero()[2];
+    }
+    // Impl IndexMut<usize>
+    #[test]
+    fn test1_vector2index_mut_usize() {
+        let mut vec = Vector2::zero();
+        vec[1] = 5.;
+        assert_eq!(vec.y, 5.);
+    }
     // Impl Neg
     #[test]
     fn test1_vector2neg() {
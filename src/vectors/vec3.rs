@@ -1,10 +1,8 @@
-use super::{
-    super::functions::constants::{EPSILON, PRECISION},
-    Vector,
-};
+use super::{vecn::VectorN, Vector};
+use crate::Matrix3;
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -18,6 +16,14 @@ impl Vector3 {
         Self { x, y, z }
     }
 
+    pub fn min(self, rhs: Self) -> Self {
+        VectorN::from(self).min(rhs.into()).into()
+    }
+
+    pub fn max(self, rhs: Self) -> Self {
+        VectorN::from(self).max(rhs.into()).into()
+    }
+
     pub fn cross(self, rhs: Self) -> Self {
         Self::new(
             self.y * rhs.z - self.z * rhs.y,
@@ -25,6 +31,11 @@ impl Vector3 {
             self.x * rhs.y - self.y * rhs.x,
         )
     }
+
+    // Outer product self * rhs^T
+    pub fn outer(self, rhs: Self) -> Matrix3 {
+        Matrix3::from_cols(self * rhs.x, self * rhs.y, self * rhs.z)
+    }
 }
 impl Vector for Vector3 {
     fn dim(self) -> usize {
@@ -32,11 +43,11 @@ impl Vector for Vector3 {
     }
 
     fn len(self) -> f32 {
-        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+        VectorN::from(self).len()
     }
 
     fn dot(self, vector: Self) -> f32 {
-        self.x * vector.x + self.y * vector.y + self.z * vector.z
+        VectorN::from(self).dot(vector.into())
     }
 }
 impl From<f32> for Vector3 {
@@ -44,20 +55,42 @@ impl From<f32> for Vector3 {
         Self::new(value, value, value)
     }
 }
+impl From<[f32; 3]> for Vector3 {
+    fn from(value: [f32; 3]) -> Self {
+        let [x, y, z] = value;
+        Self::new(x, y, z)
+    }
+}
+impl From<Vector3> for [f32; 3] {
+    fn from(value: Vector3) -> Self {
+        [value.x, value.y, value.z]
+    }
+}
+impl Index<usize> for Vector3 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of range for Vector3"),
+        }
+    }
+}
+impl IndexMut<usize> for Vector3 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of range for Vector3"),
+        }
+    }
+}
 impl Display for Vector3 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_len = [self.x, self.y, self.z]
-            .iter()
-            .map(|comp| (*comp as i32).to_string().len())
-            .max()
-            .unwrap();
-        let space = " ".repeat(1 + max_len + 1 + PRECISION + 1);
-        writeln!(f, "\n┌{space}┐").unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.x).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.y).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.z).unwrap();
-        writeln!(f, "└{space}┘").unwrap();
-        Ok(())
+        VectorN::from(*self).fmt(f)
     }
 }
 impl Neg for Vector3 {
@@ -71,7 +104,7 @@ impl Add for Vector3 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+        (VectorN::from(self) + VectorN::from(rhs)).into()
     }
 }
 impl AddAssign for Vector3 {
@@ -95,7 +128,7 @@ impl Mul<f32> for Vector3 {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+        (VectorN::from(self) * rhs).into()
     }
 }
 impl Mul<Vector3> for f32 {
@@ -124,14 +157,14 @@ impl DivAssign<f32> for Vector3 {
 }
 impl PartialEq for Vector3 {
     fn eq(&self, other: &Self) -> bool {
-        (*self - *other).len() < EPSILON
+        VectorN::from(*self) == VectorN::from(*other)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::functions::constants::PI;
+    use crate::functions::constants::{EPSILON, PI};
 
     // Constructors
     #[test]
@@ -155,6 +188,12 @@ mod tests {
         assert_eq!(vec, vec_from);
     }
     #[test]
+    fn test1_vector3from_array() {
+        let vec = Vector3::from([1., -2., 3.]);
+        assert_eq!(vec, Vector3::new(1., -2., 3.));
+        assert_eq!(<[f32; 3]>::from(vec), [1., -2., 3.]);
+    }
+    #[test]
     fn test1_vector3zero() {
         let vec = Vector3 {
             x: 0.,
@@ -396,6 +435,52 @@ mod tests {
         assert_eq!(cross_vec, Vector3::zero());
         assert!(cross_vec.is_orthogonal_to(vec));
     }
+    // Method outer(vector)
+    #[test]
+    fn test1_vector3outer() {
+        let vec1 = Vector3::new(1., 2., 3.);
+        let vec2 = Vector3::new(0., 1., -1.);
+        assert_eq!(
+            vec1.outer(vec2),
+            Matrix3::new([[0., 1., -1.], [0., 2., -2.], [0., 3., -3.]])
+        );
+        assert_eq!(vec1.outer(vec2) * vec2, vec1 * vec2.dot(vec2));
+    }
+    // Methods min(vector), max(vector)
+    #[test]
+    fn test1_vector3min() {
+        let vec1 = Vector3::new(1., -4., 2.);
+        let vec2 = Vector3::new(3., -5., 2.);
+        assert_eq!(vec1.min(vec2), vec2.min(vec1));
+        assert_eq!(vec1.min(vec2), Vector3::new(1., -5., 2.));
+    }
+    #[test]
+    fn test1_vector3max() {
+        let vec1 = Vector3::new(1., -4., 2.);
+        let vec2 = Vector3::new(3., -5., 2.);
+        assert_eq!(vec1.max(vec2), vec2.max(vec1));
+        assert_eq!(vec1.max(vec2), Vector3::new(3., -4., 2.));
+    }
+    // Impl Index<usize>
+    #[test]
+    fn test1_vector3index_usize() {
+        let vec = Vector3::new(1., -4., 2.);
+        assert_eq!(vec[0], vec.x);
+        assert_eq!(vec[1], vec.y);
+        assert_eq!(vec[2], vec.z);
+    }
+    #[test]
+    #[should_panic]
+    fn test2_vector3index_usize() {
+        let _ = Vector3::zero()[3];
+    }
+    // Impl IndexMut<usize>
+    #[test]
+    fn test1_vector3index_mut_usize() {
+        let mut vec = Vector3::zero();
+        vec[1] = 5.;
+        assert_eq!(vec.y, 5.);
+    }
     // Impl Neg
     #[test]
     fn test1_vector3neg() {
@@ -1,10 +1,8 @@
-use super::{
-    super::functions::constants::{EPSILON, PRECISION},
-    Vector,
-};
+use super::{vecn::VectorN, Vector};
+use crate::Matrix4;
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -18,6 +16,19 @@ impl Vector4 {
     pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
         Self { x, y, z, w }
     }
+
+    pub fn min(self, rhs: Self) -> Self {
+        VectorN::from(self).min(rhs.into()).into()
+    }
+
+    pub fn max(self, rhs: Self) -> Self {
+        VectorN::from(self).max(rhs.into()).into()
+    }
+
+    // Outer product self * rhs^T
+    pub fn outer(self, rhs: Self) -> Matrix4 {
+        Matrix4::from_cols(self * rhs.x, self * rhs.y, self * rhs.z, self * rhs.w)
+    }
 }
 impl Vector for Vector4 {
     fn dim(self) -> usize {
@@ -25,11 +36,11 @@ impl Vector for Vector4 {
     }
 
     fn len(self) -> f32 {
-        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+        VectorN::from(self).len()
     }
 
     fn dot(self, vector: Self) -> f32 {
-        self.x * vector.x + self.y * vector.y + self.z * vector.z + self.w * vector.w
+        VectorN::from(self).dot(vector.into())
     }
 }
 impl From<f32> for Vector4 {
@@ -37,21 +48,44 @@ impl From<f32> for Vector4 {
         Self::new(value, value, value, value)
     }
 }
+impl From<[f32; 4]> for Vector4 {
+    fn from(value: [f32; 4]) -> Self {
+        let [x, y, z, w] = value;
+        Self::new(x, y, z, w)
+    }
+}
+impl From<Vector4> for [f32; 4] {
+    fn from(value: Vector4) -> Self {
+        [value.x, value.y, value.z, value.w]
+    }
+}
+impl Index<usize> for Vector4 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Index out of range for Vector4"),
+        }
+    }
+}
+impl IndexMut<usize> for Vector4 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Index out of range for Vector4"),
+        }
+    }
+}
 impl Display for Vector4 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_len = [self.x, self.y, self.z, self.w]
-            .iter()
-            .map(|comp| (*comp as i32).to_string().len())
-            .max()
-            .unwrap();
-        let space = " ".repeat(1 + max_len + 1 + PRECISION + 1);
-        writeln!(f, "\n┌{space}┐").unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.x).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.y).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.z).unwrap();
-        writeln!(f, "| {:>max_len$.PRECISION$} |", self.w).unwrap();
-        writeln!(f, "└{space}┘").unwrap();
-        Ok(())
+        VectorN::from(*self).fmt(f)
     }
 }
 impl Neg for Vector4 {
@@ -65,12 +99,7 @@ impl Add for Vector4 {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new(
-            self.x + rhs.x,
-            self.y + rhs.y,
-            self.z + rhs.z,
-            self.w + rhs.w,
-        )
+        (VectorN::from(self) + VectorN::from(rhs)).into()
     }
 }
 impl AddAssign for Vector4 {
@@ -94,7 +123,7 @@ impl Mul<f32> for Vector4 {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::new(self.x * rhs, self.y * rhs, self.z * rhs, self.w * rhs)
+        (VectorN::from(self) * rhs).into()
     }
 }
 impl Mul<Vector4> for f32 {
@@ -123,14 +152,14 @@ impl DivAssign<f32> for Vector4 {
 }
 impl PartialEq for Vector4 {
     fn eq(&self, other: &Self) -> bool {
-        (*self - *other).len() < EPSILON
+        VectorN::from(*self) == VectorN::from(*other)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::functions::constants::PI;
+    use crate::functions::constants::{EPSILON, PI};
 
     // Constructors
     #[test]
@@ -156,6 +185,12 @@ mod tests {
         assert_eq!(vec, vec_from);
     }
     #[test]
+    fn test1_vector4from_array() {
+        let vec = Vector4::from([1., -2., 3., -4.]);
+        assert_eq!(vec, Vector4::new(1., -2., 3., -4.));
+        assert_eq!(<[f32; 4]>::from(vec), [1., -2., 3., -4.]);
+    }
+    #[test]
     fn test1_vector4zero() {
         let vec = Vector4 {
             x: 0.,
@@ -356,6 +391,58 @@ mod tests {
         assert_ne!(vec1.is_orthogonal_to(vec2), true);
         assert_ne!(vec2.is_orthogonal_to(vec1), true);
     }
+    // Method outer(vector)
+    #[test]
+    fn test1_vector4outer() {
+        let vec1 = Vector4::new(1., 0., 2., -1.);
+        let vec2 = Vector4::new(1., 2., 3., 4.);
+        assert_eq!(
+            vec1.outer(vec2),
+            Matrix4::new([
+                [1., 2., 3., 4.],
+                [0., 0., 0., 0.],
+                [2., 4., 6., 8.],
+                [-1., -2., -3., -4.]
+            ])
+        );
+        assert_eq!(vec1.outer(vec2) * vec2, vec1 * vec2.dot(vec2));
+    }
+    // Methods min(vector), max(vector)
+    #[test]
+    fn test1_vector4min() {
+        let vec1 = Vector4::new(1., -4., 2., 0.);
+        let vec2 = Vector4::new(3., -5., 2., -1.);
+        assert_eq!(vec1.min(vec2), vec2.min(vec1));
+        assert_eq!(vec1.min(vec2), Vector4::new(1., -5., 2., -1.));
+    }
+    #[test]
+    fn test1_vector4max() {
+        let vec1 = Vector4::new(1., -4., 2., 0.);
+        let vec2 = Vector4::new(3., -5., 2., -1.);
+        assert_eq!(vec1.max(vec2), vec2.max(vec1));
+        assert_eq!(vec1.max(vec2), Vector4::new(3., -4., 2., 0.));
+    }
+    // Impl Index<usize>
+    #[test]
+    fn test1_vector4index_usize() {
+        let vec = Vector4::new(1., -4., 2., 0.);
+        assert_eq!(vec[0], vec.x);
+        assert_eq!(vec[1], vec.y);
+        assert_eq!(vec[2], vec.z);
+        assert_eq!(vec[3], vec.w);
+    }
+    #[test]
+    #[should_panic]
+    fn test2_vector4index_usize() {
+        let _ = Vector4::zero()[4];
+    }
+    // Impl IndexMut<usize>
+    #[test]
+    fn test1_vector4index_mut_usize() {
+        let mut vec = Vector4::zero();
+        vec[1] = 5.;
+        assert_eq!(vec.y, 5.);
+    }
     // Impl Neg
     #[test]
     fn test1_vector4neg() {
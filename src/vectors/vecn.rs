@@ -0,0 +1,330 @@
+use super::{
+    super::functions::constants::{EPSILON, PRECISION},
+    vec2::Vector2,
+    vec3::Vector3,
+    vec4::Vector4,
+    Vector,
+};
+use crate::MatrixMxN;
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+// Vector of any dimension, converts losslessly to and from Vector2/3/4 of the same size
+#[derive(Debug, Clone, Copy)]
+pub struct VectorN<const N: usize> {
+    data: [f32; N],
+}
+impl<const N: usize> VectorN<N> {
+    pub fn new(data: [f32; N]) -> Self {
+        Self { data }
+    }
+
+    pub fn min(self, rhs: Self) -> Self {
+        Self::new(std::array::from_fn(|i| self[i].min(rhs[i])))
+    }
+
+    pub fn max(self, rhs: Self) -> Self {
+        Self::new(std::array::from_fn(|i| self[i].max(rhs[i])))
+    }
+
+    // Outer product self * rhs^T
+    pub fn outer<const M: usize>(self, rhs: VectorN<M>) -> MatrixMxN<N, M> {
+        MatrixMxN::outer(self, rhs)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.data.iter()
+    }
+}
+impl<const N: usize> Vector for VectorN<N> {
+    fn dim(self) -> usize {
+        N
+    }
+
+    fn len(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    fn dot(self, vector: Self) -> f32 {
+        self.iter().zip(vector.iter()).map(|(a, b)| a * b).sum()
+    }
+}
+impl<const N: usize> Default for VectorN<N> {
+    fn default() -> Self {
+        Self::from(0.)
+    }
+}
+impl<const N: usize> From<f32> for VectorN<N> {
+    fn from(value: f32) -> Self {
+        Self::new([value; N])
+    }
+}
+impl<const N: usize> From<[f32; N]> for VectorN<N> {
+    fn from(value: [f32; N]) -> Self {
+        Self::new(value)
+    }
+}
+impl<const N: usize> From<VectorN<N>> for [f32; N] {
+    fn from(value: VectorN<N>) -> Self {
+        value.data
+    }
+}
+impl From<Vector2> for VectorN<2> {
+    fn from(value: Vector2) -> Self {
+        Self::new(value.into())
+    }
+}
+impl From<VectorN<2>> for Vector2 {
+    fn from(value: VectorN<2>) -> Self {
+        value.data.into()
+    }
+}
+impl From<Vector3> for VectorN<3> {
+    fn from(value: Vector3) -> Self {
+        Self::new(value.into())
+    }
+}
+impl From<VectorN<3>> for Vector3 {
+    fn from(value: VectorN<3>) -> Self {
+        value.data.into()
+    }
+}
+impl From<Vector4> for VectorN<4> {
+    fn from(value: Vector4) -> Self {
+        Self::new(value.into())
+    }
+}
+impl From<VectorN<4>> for Vector4 {
+    fn from(value: VectorN<4>) -> Self {
+        value.data.into()
+    }
+}
+impl<const N: usize> Index<usize> for VectorN<N> {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.data
+            .get(index)
+            .expect("Index out of range for VectorN")
+    }
+}
+impl<const N: usize> IndexMut<usize> for VectorN<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.data
+            .get_mut(index)
+            .expect("Index out of range for VectorN")
+    }
+}
+impl<const N: usize> Display for VectorN<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let max_len = self
+            .iter()
+            .map(|comp| (*comp as i32).to_string().len())
+            .max()
+            .unwrap_or(1);
+        let space = " ".repeat(1 + max_len + 1 + PRECISION + 1);
+        writeln!(f, "\n┌{space}┐")?;
+        for comp in self.data {
+            writeln!(f, "| {:>max_len$.PRECISION$} |", comp)?;
+        }
+        writeln!(f, "└{space}┘")
+    }
+}
+impl<const N: usize> Neg for VectorN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1.
+    }
+}
+impl<const N: usize> Add for VectorN<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(std::array::from_fn(|i| self[i] + rhs[i]))
+    }
+}
+impl<const N: usize> AddAssign for VectorN<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<const N: usize> Sub for VectorN<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+impl<const N: usize> SubAssign for VectorN<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+impl<const N: usize> Mul<f32> for VectorN<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.data.map(|comp| comp * rhs))
+    }
+}
+impl<const N: usize> Mul<VectorN<N>> for f32 {
+    type Output = VectorN<N>;
+
+    fn mul(self, rhs: VectorN<N>) -> Self::Output {
+        rhs * self
+    }
+}
+impl<const N: usize> MulAssign<f32> for VectorN<N> {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+impl<const N: usize> Div<f32> for VectorN<N> {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        self * (1. / rhs)
+    }
+}
+impl<const N: usize> DivAssign<f32> for VectorN<N> {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+impl<const N: usize> PartialEq for VectorN<N> {
+    fn eq(&self, other: &Self) -> bool {
+        (*self - *other).len() < EPSILON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constants::PI;
+
+    // Constructors
+    #[test]
+    fn test1_vectornnew() {
+        let vec = VectorN::new([1., 2., 3., 4., 5., 6.]);
+        assert_eq!(vec[0], 1.);
+        assert_eq!(vec[5], 6.);
+        assert_eq!(vec.dim(), 6);
+    }
+    #[test]
+    fn test1_vectornfrom() {
+        assert_eq!(VectorN::<5>::from(1.), VectorN::new([1.; 5]));
+        assert_eq!(VectorN::<5>::zero(), VectorN::default());
+    }
+    #[test]
+    fn test1_vectornfrom_vector() {
+        let vec = Vector3::new(1., -2., 3.);
+        let vec_n = VectorN::from(vec);
+        assert_eq!(vec_n, VectorN::new([1., -2., 3.]));
+        assert_eq!(Vector3::from(vec_n), vec);
+        assert_eq!(
+            Vector2::from(VectorN::from(Vector2::new(1., 2.))),
+            Vector2::new(1., 2.)
+        );
+        let vec = Vector4::new(1., 2., 3., 4.);
+        assert_eq!(Vector4::from(VectorN::from(vec)), vec);
+    }
+    // Impl Display
+    #[test]
+    fn test1_vectorndisplay() {
+        let vec = VectorN::<6>::from(PI);
+        println!("{vec}");
+    }
+    // Impl PartialEq
+    #[test]
+    fn test1_vectornpartial_eq() {
+        let vec1 = VectorN::new([1., 7., 1., 0., 0.]);
+        let vec2 = VectorN::new([1., 8., 1., 0., 0.]);
+        assert_ne!(vec1, vec2);
+        assert_eq!(vec1, VectorN::new([1., 7., 1., 0., 0.0001]));
+    }
+    // Methods len(), dot(vector), normalize(), angle(vector)
+    #[test]
+    fn test1_vectornlen() {
+        let vec = VectorN::new([1., 1., 1., 1., 2., 2., 2., 2., 2., 2., 2., 2.]);
+        assert_eq!(vec.len(), 6.);
+        assert_eq!(vec.normalize().len(), 1.);
+    }
+    #[test]
+    fn test1_vectorndot() {
+        let vec1 = VectorN::new([1., 2., 3., 4., 5.]);
+        let vec2 = VectorN::new([5., 4., 3., 2., 1.]);
+        assert_eq!(vec1.dot(vec2), 35.);
+        assert!(
+            VectorN::new([1., 0., 0., 0., 0.]).is_orthogonal_to(VectorN::new([0., 0., 0., 0., 3.]))
+        );
+    }
+    #[test]
+    fn test1_vectornnormalize() {
+        assert_eq!(VectorN::<5>::zero().try_normalize(), None);
+    }
+    #[test]
+    fn test1_vectornreflect_with() {
+        let vec = VectorN::new([1., -1., 2., 0., 0.]);
+        let normal = VectorN::new([0., 1., 0., 0., 0.]);
+        assert_eq!(vec.reflect_with(normal), VectorN::new([1., 1., 2., 0., 0.]));
+    }
+    // Methods min(vector), max(vector)
+    #[test]
+    fn test1_vectornmin() {
+        let vec1 = VectorN::new([1., -4., 2., 7., 0.]);
+        let vec2 = VectorN::new([3., -5., 2., 6., 0.]);
+        assert_eq!(vec1.min(vec2), VectorN::new([1., -5., 2., 6., 0.]));
+        assert_eq!(vec1.max(vec2), VectorN::new([3., -4., 2., 7., 0.]));
+    }
+    // Method outer(vector)
+    #[test]
+    fn test1_vectornouter() {
+        let vec1 = VectorN::new([1., 2.]);
+        let vec2 = VectorN::new([1., 0., -1., 2., 3.]);
+        assert_eq!(
+            vec1.outer(vec2),
+            MatrixMxN::new([[1., 0., -1., 2., 3.], [2., 0., -2., 4., 6.]])
+        );
+    }
+    // Impl Index<usize>
+    #[test]
+    #[should_panic]
+    fn test1_vectornindex() {
+        let vec = VectorN::new([1., 2., 3., 4., 5.]);
+        let _ = vec[5];
+    }
+    // Impl IndexMut<usize>
+    #[test]
+    fn test1_vectornindex_mut() {
+        let mut vec = VectorN::<5>::zero();
+        vec[4] = 2.;
+        assert_eq!(vec, VectorN::new([0., 0., 0., 0., 2.]));
+    }
+    // Impl Neg, Add, Sub
+    #[test]
+    fn test1_vectornadd() {
+        let vec1 = VectorN::new([1., 2., 3., 4., 5., 6.]);
+        let vec2 = VectorN::from(1.);
+        assert_eq!(vec1 + vec2, VectorN::new([2., 3., 4., 5., 6., 7.]));
+        assert_eq!(vec1 - vec2 - vec1, -vec2);
+        let mut vec3 = vec1;
+        vec3 += vec2;
+        vec3 -= vec1;
+        assert_eq!(vec3, vec2);
+    }
+    // Impl Mul<f32>, Div<f32>
+    #[test]
+    fn test1_vectornmul() {
+        let vec = VectorN::new([1., -2., 3., 0., 5.]);
+        assert_eq!(vec * 2., VectorN::new([2., -4., 6., 0., 10.]));
+        assert_eq!(2. * vec, vec + vec);
+        let mut vec_mut = vec;
+        vec_mut *= 4.;
+        vec_mut /= 2.;
+        assert_eq!(vec_mut, vec * 2.);
+        assert_eq!(vec / 2., vec * 0.5);
+    }
+}